@@ -21,6 +21,10 @@ cargo check --features async_global
 #
 cargo test --all-features
 
+# --all-features turns on detach_on_drop, which changes the drop behavior of JoinHandle. Also test the default.
+#
+cargo test --features "tokio_ct tokio_tp async_std async_global localpool threadpool"
+
 # checking with rustup for when not running on travis.
 #
 if [[ "$TRAVIS_RUST_VERSION" == nightly ]] || [[ $(rustup default) =~ "nightly" ]]
@@ -0,0 +1,115 @@
+use crate::JoinHandle;
+use futures_util::future::AbortHandle;
+use std::{
+    future::Future,
+    ops::{Deref, DerefMut},
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// Handles that can cancel the task they refer to. Used by [`AbortOnDrop`].
+//
+pub trait Abort {
+    /// Cancel the task. Does nothing if it has already completed.
+    ///
+    /// Returns `false` when the handle can not cancel its task.
+    //
+    fn abort(self) -> bool;
+}
+
+/// Cancels the task, even if the handle was made to [detach on drop](JoinHandle::detach_on_drop).
+/// See [`JoinHandle::abort`] for the handles that can't cancel their task.
+//
+impl<T> Abort for JoinHandle<T> {
+    fn abort(self) -> bool {
+        JoinHandle::abort(self)
+    }
+}
+
+impl Abort for AbortHandle {
+    fn abort(self) -> bool {
+        AbortHandle::abort(&self);
+        true
+    }
+}
+
+/// A guard that aborts the task behind the wrapped handle when dropped, whatever the drop policy of
+/// the handle itself. Use [`disarm`](AbortOnDrop::disarm) to get the handle back without aborting.
+///
+/// It dereferences to the handle and can be awaited directly if the handle is a future.
+///
+/// A handle that can not cancel its task, like a [`JoinHandle`] that is not
+/// [abortable](JoinHandle::is_abortable), gets detached instead. Check before wrapping it if that matters.
+//
+#[must_use = "AbortOnDrop will cancel your task when dropped."]
+#[derive(Debug)]
+//
+pub struct AbortOnDrop<H: Abort> {
+    handle: Option<H>,
+}
+
+impl<H: Abort> AbortOnDrop<H> {
+    /// Wrap `handle`.
+    //
+    pub fn new(handle: H) -> Self {
+        Self {
+            handle: Some(handle),
+        }
+    }
+
+    /// Take the handle out of the guard, so dropping the guard no longer aborts the task.
+    //
+    pub fn disarm(mut self) -> H {
+        self.handle
+            .take()
+            .expect("AbortOnDrop always holds a handle until dropped")
+    }
+}
+
+impl<H: Abort> From<H> for AbortOnDrop<H> {
+    fn from(handle: H) -> Self {
+        Self::new(handle)
+    }
+}
+
+impl<H: Abort> Deref for AbortOnDrop<H> {
+    type Target = H;
+
+    fn deref(&self) -> &H {
+        self.handle
+            .as_ref()
+            .expect("AbortOnDrop always holds a handle until dropped")
+    }
+}
+
+impl<H: Abort> DerefMut for AbortOnDrop<H> {
+    fn deref_mut(&mut self) -> &mut H {
+        self.handle
+            .as_mut()
+            .expect("AbortOnDrop always holds a handle until dropped")
+    }
+}
+
+impl<H: Abort + Future + Unpin> Future for AbortOnDrop<H> {
+    type Output = H::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut **self).poll(cx)
+    }
+}
+
+impl<H: Abort> Drop for AbortOnDrop<H> {
+    fn drop(&mut self) {
+        if let Some(handle) = self.handle.take() {
+            handle.abort();
+        }
+    }
+}
+
+#[cfg(test)]
+//
+mod tests {
+    use super::*;
+
+    static_assertions::assert_impl_all!(AbortOnDrop<JoinHandle<()>>: Send, Sync, Unpin);
+}
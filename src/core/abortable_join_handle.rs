@@ -0,0 +1,205 @@
+use crate::{core::join_handle::JoinFailure, JoinHandle};
+use futures_util::task::AtomicWaker;
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, MutexGuard, PoisonError,
+    },
+    task::{Context, Poll},
+};
+
+/// A join handle that can also be aborted through a [`TaskAbortHandle`].
+/// See [`SpawnHandleExt::spawn_handle_with_abort`](crate::SpawnHandleExt::spawn_handle_with_abort).
+#[derive(Debug)]
+pub struct AbortableJoinHandle<T> {
+    shared: Arc<Shared<T>>,
+}
+
+/// A cheap token to abort a task without holding on to its [`JoinHandle`]. It is `Send`, `Sync` and
+/// `Clone`, and doesn't depend on the output type of the task, so handles of different tasks can be
+/// stored together in a registry shared between tasks.
+///
+/// Aborting cancels the task and makes awaiting the join handle panic, like the
+/// [`JoinHandle`] of async-std does for aborted tasks. It does nothing if the task has already
+/// completed or the join handle has been detached.
+#[derive(Clone)]
+pub struct TaskAbortHandle {
+    shared: Arc<dyn AbortTask>,
+}
+
+// Erases the output type of the task from the abort handle.
+//
+trait AbortTask: Send + Sync {
+    fn abort(&self);
+    fn is_aborted(&self) -> bool;
+}
+
+#[derive(Debug)]
+struct Shared<T> {
+    handle: Mutex<Option<JoinHandle<T>>>,
+    aborted: AtomicBool,
+    waker: AtomicWaker,
+}
+
+impl<T: Send + 'static> AbortableJoinHandle<T> {
+    pub(crate) fn new(handle: JoinHandle<T>) -> (Self, TaskAbortHandle) {
+        let shared = Arc::new(Shared {
+            handle: Mutex::new(Some(handle)),
+            aborted: AtomicBool::new(false),
+            waker: AtomicWaker::new(),
+        });
+
+        let abort = TaskAbortHandle {
+            shared: shared.clone(),
+        };
+
+        (Self { shared }, abort)
+    }
+}
+
+impl<T> AbortableJoinHandle<T> {
+    // Cancel the task, even if the inner handle detaches on drop.
+    //
+    pub(crate) fn abort(&self) {
+        if let Some(handle) = self.shared.take() {
+            handle.abort();
+        }
+    }
+
+    pub(crate) fn detach(&self) {
+        if let Some(handle) = self.shared.take() {
+            handle.detach_any();
+        }
+    }
+}
+
+impl<T> Shared<T> {
+    // A panic while polling the inner handle can poison the lock, but the slot is still consistent.
+    fn lock(&self) -> MutexGuard<'_, Option<JoinHandle<T>>> {
+        self.handle.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn take(&self) -> Option<JoinHandle<T>> {
+        self.lock().take()
+    }
+}
+
+impl<T: 'static> AbortableJoinHandle<T> {
+    // Like poll, but reports an aborted task instead of panicking, see JoinHandle::poll_join.
+    //
+    pub(crate) fn poll_join(&self, cx: &mut Context<'_>) -> Poll<Result<T, JoinFailure>> {
+        // Register first, so an abort in between can't go unnoticed.
+        self.shared.waker.register(cx.waker());
+
+        let mut slot = self.shared.lock();
+
+        if let Some(handle) = slot.as_mut() {
+            let out = futures_util::ready!(handle.poll_join(cx));
+            *slot = None;
+            return Poll::Ready(out);
+        }
+
+        drop(slot);
+
+        if self.shared.aborted.load(Ordering::SeqCst) {
+            return Poll::Ready(Err(JoinFailure::Cancelled));
+        }
+
+        panic!("Cannot poll a completed or detached JoinHandle")
+    }
+}
+
+impl<T: 'static> Future for AbortableJoinHandle<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // Register first, so an abort in between can't go unnoticed.
+        self.shared.waker.register(cx.waker());
+
+        let mut slot = self.shared.lock();
+
+        if let Some(handle) = slot.as_mut() {
+            let out = futures_util::ready!(Pin::new(handle).poll(cx));
+            *slot = None;
+            return Poll::Ready(out);
+        }
+
+        // Don't poison the lock, the other handles still use it.
+        drop(slot);
+
+        if self.shared.aborted.load(Ordering::SeqCst) {
+            panic!("Task has been aborted")
+        }
+
+        panic!("Cannot poll a completed or detached JoinHandle")
+    }
+}
+
+impl<T> Drop for AbortableJoinHandle<T> {
+    fn drop(&mut self) {
+        // The abort handles keep the shared state alive, so drop the inner handle explicitly
+        // to cancel the task.
+        drop(self.shared.take());
+    }
+}
+
+impl<T: Send> AbortTask for Shared<T> {
+    fn abort(&self) {
+        let handle = {
+            let mut slot = self.lock();
+
+            // Set the flag while holding the lock, so poll never sees the slot empty without it.
+            if slot.is_some() {
+                self.aborted.store(true, Ordering::SeqCst);
+            }
+
+            slot.take()
+        };
+
+        if let Some(handle) = handle {
+            handle.abort();
+            self.waker.wake();
+        }
+    }
+
+    fn is_aborted(&self) -> bool {
+        self.aborted.load(Ordering::SeqCst)
+    }
+}
+
+impl std::fmt::Debug for TaskAbortHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TaskAbortHandle")
+            .field("aborted", &self.is_aborted())
+            .finish()
+    }
+}
+
+impl TaskAbortHandle {
+    /// Cancel the task.
+    pub fn abort(&self) {
+        self.shared.abort()
+    }
+
+    /// Whether the task was cancelled through one of its abort handles.
+    pub fn is_aborted(&self) -> bool {
+        self.shared.is_aborted()
+    }
+}
+
+impl<T> From<AbortableJoinHandle<T>> for JoinHandle<T> {
+    fn from(handle: AbortableJoinHandle<T>) -> Self {
+        JoinHandle::Abortable(handle)
+    }
+}
+
+#[cfg(test)]
+//
+mod tests {
+    use super::*;
+
+    static_assertions::assert_impl_all!(TaskAbortHandle: Send, Sync, Clone);
+    static_assertions::assert_impl_all!(AbortableJoinHandle<()>: Send, Sync);
+}
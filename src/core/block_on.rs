@@ -1,9 +1,59 @@
-use std::future::Future;
+use futures_util::future::{poll_fn, select, Either, FutureExt};
+use std::{future::Future, task::Poll};
 
 /// The entry point of the executor
 pub trait BlockOn {
     /// The entry point of the executor
     fn block_on<F: Future>(&self, future: F) -> F::Output;
+
+    /// Run `future` until it completes or `shutdown` resolves, whichever comes first, eg. to stop a server
+    /// gracefully on a signal or a message from an admin channel. Returns `None` when `shutdown` came first,
+    /// in which case `future` is dropped, so the destructors of the values it holds run before this returns.
+    ///
+    /// Tasks spawned by `future` are not stopped, that's up to the executor and how you shut it down.
+    fn block_on_with_shutdown<F, S>(&self, future: F, shutdown: S) -> Option<F::Output>
+    where
+        F: Future,
+        S: Future,
+    {
+        self.block_on(async move {
+            futures_util::pin_mut!(future, shutdown);
+
+            match select(future, shutdown).await {
+                Either::Left((out, _)) => Some(out),
+                Either::Right(_) => None,
+            }
+        })
+    }
+
+    /// Run `future` to completion while also polling `driver` on the same thread, eg. to pump a reactor
+    /// or flush metrics alongside the main future without spawning a separate task for it. `driver` is
+    /// meant to be endless. If it completes anyway, `future` keeps running on it's own.
+    ///
+    /// Returns as soon as `future` completes, dropping `driver`.
+    fn block_on_with_driver<F, D>(&self, future: F, driver: D) -> F::Output
+    where
+        F: Future,
+        D: Future,
+    {
+        self.block_on(async move {
+            let driver = driver.fuse();
+            futures_util::pin_mut!(future, driver);
+
+            poll_fn(|cx| {
+                if let Poll::Ready(out) = future.as_mut().poll(cx) {
+                    return Poll::Ready(out);
+                }
+
+                // A fused future stays pending once it has completed.
+                //
+                let _ = driver.as_mut().poll(cx);
+
+                Poll::Pending
+            })
+            .await
+        })
+    }
 }
 
 /// The entry point of the executor
@@ -0,0 +1,37 @@
+use crate::{SpawnBlocking, SpawnBlockingExt, SpawnError};
+use futures_util::stream::{self, Stream};
+use std::sync::{Arc, Mutex};
+
+/// Consume a blocking iterator, eg. a synchronous database cursor, as a stream. Every call to
+/// [`Iterator::next`] runs on `exec` through [`SpawnBlocking`], so it does not block the executor
+/// that polls the stream.
+///
+/// Items are pulled one at a time, only when the stream is polled. The stream ends when the iterator
+/// returns `None`. If `exec` fails to spawn, the error is yielded and the stream ends after it. Dropping
+/// the stream stops pulling, but a call to `next` that is already running on a blocking thread will still
+/// finish.
+//
+pub fn blocking_iter_stream<'a, E, I>(
+    exec: &'a E,
+    iter: I,
+) -> impl Stream<Item = Result<I::Item, SpawnError>> + 'a
+where
+    E: SpawnBlocking<Option<I::Item>> + ?Sized,
+    I: Iterator + Send + 'static,
+    I::Item: Send + 'static,
+{
+    // The iterator has to go to the blocking thread for every item, so it's shared with the closures.
+    //
+    let iter = Arc::new(Mutex::new(iter));
+
+    stream::unfold(Some(iter), move |iter| async move {
+        let iter = iter?;
+        let shared = iter.clone();
+        let next = move || shared.lock().expect("lock iterator").next();
+
+        match exec.spawn_blocking(next) {
+            Ok(handle) => Some((Ok(handle.await?), Some(iter))),
+            Err(e) => Some((Err(e), None)),
+        }
+    })
+}
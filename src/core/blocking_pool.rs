@@ -0,0 +1,143 @@
+use crate::{JoinHandle, SpawnBlocking, SpawnBlockingExt, SpawnError};
+use futures_util::FutureExt;
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+};
+
+type Job = Box<dyn FnOnce() + Send>;
+
+/// Limits how many blocking closures run at the same time, on top of any [`SpawnBlocking`] executor.
+///
+/// The blocking pools of the runtimes grow on demand, which is what you want most of the time, but
+/// it lets a single subsystem (eg. disk IO) start so many threads that they thrash. `BlockingPool`
+/// only hands `max_concurrent` closures to the executor at a time and queues the rest. Queued
+/// closures are run by the threads that finish their previous closure, so no thread sits waiting
+/// for a slot.
+///
+/// Dropping the returned [`JoinHandle`] before the closure starts means it won't run at all.
+/// Clones share the same limit.
+//
+#[derive(Debug, Clone)]
+//
+pub struct BlockingPool<E> {
+    exec: E,
+    max_concurrent: usize,
+    state: Arc<Mutex<State>>,
+}
+
+#[derive(Default)]
+struct State {
+    running: usize,
+    queue: VecDeque<Job>,
+}
+
+impl std::fmt::Debug for State {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("State")
+            .field("running", &self.running)
+            .field("queued", &self.queue.len())
+            .finish()
+    }
+}
+
+impl<E> BlockingPool<E>
+where
+    E: SpawnBlocking<()>,
+{
+    /// Run at most `max_concurrent` closures on `exec` at the same time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_concurrent` is zero.
+    //
+    pub fn new(exec: E, max_concurrent: usize) -> Self {
+        assert!(
+            max_concurrent > 0,
+            "BlockingPool: max_concurrent must be greater than zero"
+        );
+
+        Self {
+            exec,
+            max_concurrent,
+            state: Default::default(),
+        }
+    }
+
+    /// Run `func` as soon as there is a free slot and get a [`JoinHandle`] for its output.
+    //
+    pub fn spawn<T: Send + 'static>(
+        &self,
+        func: impl FnOnce() -> T + Send + 'static,
+    ) -> Result<JoinHandle<T>, SpawnError> {
+        let (fut, handle) = async move { func() }.remote_handle();
+
+        // Like for RayonPool, a single poll runs the closure, unless the handle has been dropped.
+        //
+        let job: Job = Box::new(move || {
+            let _ = fut.now_or_never();
+        });
+
+        {
+            let mut state = self.state.lock().expect("lock BlockingPool state");
+
+            if state.running >= self.max_concurrent {
+                state.queue.push_back(job);
+                return Ok(handle.into());
+            }
+
+            state.running += 1;
+        }
+
+        let state = self.state.clone();
+
+        match self.exec.spawn_blocking(move || run(&state, job)) {
+            Ok(runner) => runner.detach_any(),
+
+            Err(e) => {
+                self.state.lock().expect("lock BlockingPool state").running -= 1;
+                return Err(e);
+            }
+        }
+
+        Ok(handle.into())
+    }
+
+    /// The executor the closures run on.
+    //
+    pub fn inner(&self) -> &E {
+        &self.exec
+    }
+}
+
+// Keep taking jobs from the queue until it's empty, then give up the slot.
+//
+fn run(state: &Mutex<State>, mut job: Job) {
+    loop {
+        job();
+
+        let mut state = state.lock().expect("lock BlockingPool state");
+
+        match state.queue.pop_front() {
+            Some(next) => job = next,
+
+            None => {
+                state.running -= 1;
+                return;
+            }
+        }
+    }
+}
+
+impl<E, T> SpawnBlocking<T> for BlockingPool<E>
+where
+    E: SpawnBlocking<()>,
+    T: Send + 'static,
+{
+    fn spawn_blocking_obj(
+        &self,
+        func: Box<dyn FnOnce() -> T + Send>,
+    ) -> Result<JoinHandle<T>, SpawnError> {
+        self.spawn(func)
+    }
+}
@@ -0,0 +1,131 @@
+use std::{
+    collections::HashMap,
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    task::{Context, Poll, Waker},
+};
+
+/// A runtime agnostic token to signal cancellation to tasks. All clones share the same state, so
+/// cancelling one cancels them all. Tasks can check [`is_cancelled`](CancellationToken::is_cancelled)
+/// or await [`cancelled`](CancellationToken::cancelled) to stop what they are doing.
+//
+#[derive(Debug, Clone, Default)]
+//
+pub struct CancellationToken {
+    inner: Arc<Inner>,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    cancelled: AtomicBool,
+    waiters: Mutex<Waiters>,
+}
+
+#[derive(Debug, Default)]
+struct Waiters {
+    next_key: usize,
+    wakers: HashMap<usize, Waker>,
+}
+
+impl CancellationToken {
+    /// Create a new token that is not cancelled.
+    //
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancel the token and wake up all tasks waiting on [`cancelled`](CancellationToken::cancelled).
+    //
+    pub fn cancel(&self) {
+        self.inner.cancelled.store(true, Ordering::SeqCst);
+
+        let wakers = std::mem::take(&mut self.inner.waiters.lock().expect("lock waiters").wakers);
+
+        wakers.into_iter().for_each(|(_, waker)| waker.wake());
+    }
+
+    /// Whether the token has been cancelled.
+    //
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// A future that resolves once the token is cancelled.
+    //
+    pub fn cancelled(&self) -> Cancelled<'_> {
+        Cancelled {
+            token: self,
+            key: None,
+        }
+    }
+}
+
+/// Future returned by [`CancellationToken::cancelled`].
+//
+#[derive(Debug)]
+//
+pub struct Cancelled<'a> {
+    token: &'a CancellationToken,
+    key: Option<usize>,
+}
+
+impl Future for Cancelled<'_> {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let inner = &self.token.inner;
+
+        if inner.cancelled.load(Ordering::SeqCst) {
+            return Poll::Ready(());
+        }
+
+        let mut waiters = inner.waiters.lock().expect("lock waiters");
+
+        // Check again with the lock held, cancel might have taken the wakers in between.
+        if inner.cancelled.load(Ordering::SeqCst) {
+            return Poll::Ready(());
+        }
+
+        let key = match self.key {
+            Some(key) => key,
+            None => {
+                waiters.next_key += 1;
+                waiters.next_key
+            }
+        };
+
+        waiters.wakers.insert(key, cx.waker().clone());
+        drop(waiters);
+
+        self.key = Some(key);
+
+        Poll::Pending
+    }
+}
+
+impl Drop for Cancelled<'_> {
+    fn drop(&mut self) {
+        if let Some(key) = self.key {
+            self.token
+                .inner
+                .waiters
+                .lock()
+                .expect("lock waiters")
+                .wakers
+                .remove(&key);
+        }
+    }
+}
+
+#[cfg(test)]
+//
+mod tests {
+    use super::*;
+
+    static_assertions::assert_impl_all!(CancellationToken: Send, Sync, Clone);
+    static_assertions::assert_impl_all!(Cancelled<'static>: Send, Unpin);
+}
@@ -0,0 +1,134 @@
+use crate::{JoinHandle, SpawnError};
+use futures_channel::oneshot;
+use futures_util::FutureExt;
+use std::{
+    io,
+    panic::{catch_unwind, resume_unwind, AssertUnwindSafe},
+    sync::{mpsc, Arc, Mutex},
+};
+
+type Job<Ctx> = Box<dyn FnOnce(&Ctx) + Send>;
+
+/// A pool of worker threads for blocking closures that need some per thread context, eg. a database
+/// connection or a scratch buffer.
+///
+/// Every worker calls the initializer once when it starts and passes a reference to the resulting
+/// context to each closure it runs. The threads are created up front and don't depend on any async
+/// runtime. The closures are run in the order they are spawned, by whichever worker is free.
+///
+/// A panic in a closure is caught, so the worker and its context survive, and resumed when awaiting
+/// the [`JoinHandle`]. Dropping the pool lets the workers finish the closures that are already
+/// queued, after which they exit. Clones share the same workers.
+//
+#[cfg_attr(nightly, doc(cfg(feature = "channel")))]
+//
+pub struct ContextBlockingPool<Ctx> {
+    tx: mpsc::Sender<Job<Ctx>>,
+    threads: usize,
+}
+
+// Not derived, as the context itself doesn't need to be Clone.
+//
+impl<Ctx> Clone for ContextBlockingPool<Ctx> {
+    fn clone(&self) -> Self {
+        Self {
+            tx: self.tx.clone(),
+            threads: self.threads,
+        }
+    }
+}
+
+impl<Ctx: 'static> ContextBlockingPool<Ctx> {
+    /// Start `threads` workers, each with its own context created by `init`.
+    ///
+    /// # Errors
+    ///
+    /// When a thread can not be spawned. The workers that were already started exit.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `threads` is zero.
+    //
+    pub fn new(threads: usize, init: impl Fn() -> Ctx + Send + Sync + 'static) -> io::Result<Self> {
+        assert!(
+            threads > 0,
+            "ContextBlockingPool: threads must be greater than zero"
+        );
+
+        let (tx, rx) = mpsc::channel::<Job<Ctx>>();
+        let rx = Arc::new(Mutex::new(rx));
+        let init = Arc::new(init);
+
+        for i in 0..threads {
+            let rx = rx.clone();
+            let init = init.clone();
+
+            std::thread::Builder::new()
+                .name(format!("context_blocking_pool_{}", i))
+                .spawn(move || {
+                    let ctx = init();
+
+                    // Only hold the lock while waiting for a job, not while running it.
+                    //
+                    loop {
+                        let job = match rx.lock().expect("lock receiver").recv() {
+                            Ok(job) => job,
+                            Err(_) => return,
+                        };
+
+                        job(&ctx);
+                    }
+                })?;
+        }
+
+        Ok(Self { tx, threads })
+    }
+
+    /// Run `func` on one of the workers with the context of that worker.
+    ///
+    /// # Errors
+    ///
+    /// When all workers are gone, which only happens if the initializer panicked.
+    //
+    pub fn spawn<T: Send + 'static>(
+        &self,
+        func: impl FnOnce(&Ctx) -> T + Send + 'static,
+    ) -> Result<JoinHandle<T>, SpawnError> {
+        let (out_tx, out_rx) = oneshot::channel();
+
+        let (fut, handle) = async move {
+            match out_rx.await {
+                Ok(Ok(out)) => out,
+                Ok(Err(panic)) => resume_unwind(panic),
+                Err(_) => panic!("ContextBlockingPool: the closure was dropped without running"),
+            }
+        }
+        .remote_handle();
+
+        // Once the output has been sent, a single poll of the remote half forwards it to the handle.
+        // If the handle has been dropped, the remote half returns without polling.
+        //
+        let job: Job<Ctx> = Box::new(move |ctx| {
+            let _ = out_tx.send(catch_unwind(AssertUnwindSafe(|| func(ctx))));
+            let _ = fut.now_or_never();
+        });
+
+        self.tx.send(job).map_err(|_| SpawnError::shutdown())?;
+
+        Ok(handle.into())
+    }
+
+    /// The number of worker threads.
+    //
+    pub fn threads(&self) -> usize {
+        self.threads
+    }
+}
+
+impl<Ctx> std::fmt::Debug for ContextBlockingPool<Ctx> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ContextBlockingPool")
+            .field("threads", &self.threads)
+            .finish()
+    }
+}
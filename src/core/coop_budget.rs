@@ -0,0 +1,82 @@
+/// Tells a long running loop inside a task when to yield, so it doesn't starve the other tasks on
+/// the executor:
+///
+/// ```rust
+/// # use async_executors::{CoopBudget, YieldNow};
+/// #
+/// async fn crunch(exec: &impl YieldNow, items: &[u64]) -> u64 {
+///     let mut coop = CoopBudget::new(64);
+///     let mut sum = 0;
+///
+///     for item in items {
+///         sum += item;
+///
+///         if coop.should_yield() {
+///             exec.yield_now().await;
+///         }
+///     }
+///
+///     sum
+/// }
+/// ```
+///
+/// It counts the iterations and asks to yield every `interval` of them. When one of the tokio
+/// executors is enabled, it also asks to yield as soon as the task has used up its tokio
+/// [coop budget](https://docs.rs/tokio/latest/tokio/task/coop/index.html), eg. because it has
+/// been doing IO that was always ready. Outside of tokio, only the counter is used.
+//
+#[derive(Debug, Clone)]
+//
+pub struct CoopBudget {
+    interval: u32,
+    count: u32,
+}
+
+impl CoopBudget {
+    /// Yield every `interval` calls to [`should_yield`](CoopBudget::should_yield).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `interval` is zero.
+    //
+    pub fn new(interval: u32) -> Self {
+        assert!(
+            interval > 0,
+            "CoopBudget: interval must be greater than zero"
+        );
+
+        Self { interval, count: 0 }
+    }
+
+    /// Count an iteration and return whether the task should yield now. Returns true at most
+    /// every `interval` calls, or earlier when the tokio budget is used up.
+    //
+    pub fn should_yield(&mut self) -> bool {
+        self.count += 1;
+
+        if self.count >= self.interval || !runtime_has_budget() {
+            self.count = 0;
+            return true;
+        }
+
+        false
+    }
+
+    /// Start counting from zero again, eg. after the task yielded for some other reason.
+    //
+    pub fn reset(&mut self) {
+        self.count = 0;
+    }
+}
+
+#[cfg(any(feature = "tokio_ct", feature = "tokio_tp"))]
+//
+fn runtime_has_budget() -> bool {
+    tokio::task::coop::has_budget_remaining()
+}
+
+#[cfg(not(any(feature = "tokio_ct", feature = "tokio_tp")))]
+//
+fn runtime_has_budget() -> bool {
+    true
+}
@@ -0,0 +1,34 @@
+use crate::{Spawn, SpawnHandle};
+
+/// Bundles the bounds most libraries need on an executor: [`Spawn`], [`SpawnHandle<T>`](SpawnHandle),
+/// `Clone`, `Send`, `Sync` and `'static`. It is automatically implemented for every type that satisfies
+/// them, so you never have to implement it yourself.
+///
+/// Instead of writing out all the bounds on every generic signature:
+///
+/// ```rust
+/// use async_executors::{ Executor, SpawnHandleExt };
+///
+/// async fn need_exec<E: Executor<u8>>( exec: E ) -> u8
+/// {
+///    let exec2 = exec.clone();
+///
+///    exec.spawn_handle( async move
+///    {
+///       exec2.spawn_handle( async { 5 } ).expect( "spawn" ).await
+///
+///    }).expect( "spawn" ).await
+/// }
+/// ```
+//
+pub trait Executor<T: 'static + Send>:
+    Spawn + SpawnHandle<T> + Clone + Send + Sync + 'static
+{
+}
+
+impl<E, T> Executor<T> for E
+where
+    E: Spawn + SpawnHandle<T> + Clone + Send + Sync + 'static,
+    T: 'static + Send,
+{
+}
@@ -0,0 +1,47 @@
+use crate::{core::join_handle::JoinFailure, JoinHandle, TaskFailure};
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// Future returned by [`JoinHandle::flatten_result`].
+//
+#[must_use = "futures do nothing unless polled"]
+//
+#[derive(Debug)]
+//
+pub struct FlattenResult<T, E> {
+    handle: Option<JoinHandle<Result<T, E>>>,
+}
+
+impl<T, E> FlattenResult<T, E> {
+    pub(crate) fn new(handle: JoinHandle<Result<T, E>>) -> Self {
+        Self {
+            handle: Some(handle),
+        }
+    }
+}
+
+impl<T, E> Unpin for FlattenResult<T, E> {}
+
+impl<T: 'static, E: 'static> Future for FlattenResult<T, E> {
+    type Output = Result<T, TaskFailure<E>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let handle = self
+            .handle
+            .as_mut()
+            .expect("FlattenResult polled after completion");
+
+        let out = match futures_util::ready!(handle.poll_join(cx)) {
+            Ok(Ok(out)) => Ok(out),
+            Ok(Err(e)) => Err(TaskFailure::Failed(e)),
+            Err(JoinFailure::Panicked(payload)) => Err(TaskFailure::Panicked(payload)),
+            Err(JoinFailure::Cancelled) => Err(TaskFailure::Cancelled),
+        };
+
+        self.handle = None;
+        Poll::Ready(out)
+    }
+}
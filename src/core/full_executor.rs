@@ -0,0 +1,85 @@
+use crate::{JoinHandle, Spawn, SpawnBlocking, SpawnError, SpawnHandle};
+use futures_task::FutureObj;
+
+/// Combines an executor for async tasks with one for blocking tasks, so a single value can be passed
+/// to code that needs both, eg. a library that takes `impl SpawnHandle<T> + SpawnBlocking<U>`.
+/// Create it with [`SpawnExt::with_blocking`](crate::SpawnExt::with_blocking) or [`FullExecutor::new`].
+///
+/// [`Spawn`] and [`SpawnHandle`] forward to the async executor, [`SpawnBlocking`] to the blocking one.
+//
+#[derive(Debug, Clone)]
+//
+pub struct FullExecutor<A, B> {
+    exec: A,
+    blocking: B,
+}
+
+impl<A, B> FullExecutor<A, B> {
+    /// Spawn futures on `exec` and blocking closures on `blocking`.
+    //
+    pub fn new(exec: A, blocking: B) -> Self {
+        Self { exec, blocking }
+    }
+
+    /// The executor for async tasks.
+    //
+    pub fn inner(&self) -> &A {
+        &self.exec
+    }
+
+    /// The executor for blocking tasks.
+    //
+    pub fn blocking(&self) -> &B {
+        &self.blocking
+    }
+
+    /// Get back both executors.
+    //
+    pub fn into_inner(self) -> (A, B) {
+        (self.exec, self.blocking)
+    }
+}
+
+impl<A: Spawn, B> Spawn for FullExecutor<A, B> {
+    fn spawn_obj(&self, future: FutureObj<'static, ()>) -> Result<(), SpawnError> {
+        self.exec.spawn_obj(future)
+    }
+
+    fn status(&self) -> Result<(), SpawnError> {
+        self.exec.status()
+    }
+}
+
+impl<A, B, Out> SpawnHandle<Out> for FullExecutor<A, B>
+where
+    A: SpawnHandle<Out>,
+    Out: Send + 'static,
+{
+    fn spawn_handle_obj(
+        &self,
+        future: FutureObj<'static, Out>,
+    ) -> Result<JoinHandle<Out>, SpawnError> {
+        self.exec.spawn_handle_obj(future)
+    }
+
+    fn spawn_handle_obj_named(
+        &self,
+        name: &str,
+        future: FutureObj<'static, Out>,
+    ) -> Result<JoinHandle<Out>, SpawnError> {
+        self.exec.spawn_handle_obj_named(name, future)
+    }
+}
+
+impl<A, B, T> SpawnBlocking<T> for FullExecutor<A, B>
+where
+    B: SpawnBlocking<T>,
+    T: Send + 'static,
+{
+    fn spawn_blocking_obj(
+        &self,
+        func: Box<dyn FnOnce() -> T + Send>,
+    ) -> Result<JoinHandle<T>, SpawnError> {
+        self.blocking.spawn_blocking_obj(func)
+    }
+}
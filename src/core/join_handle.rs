@@ -2,8 +2,9 @@
 //
 use {
     futures_util::{
-        future::{AbortHandle, Aborted, RemoteHandle},
+        future::{AbortHandle, Aborted, BoxFuture, FutureExt, LocalBoxFuture, RemoteHandle},
         ready,
+        stream::Stream,
     },
     std::{
         future::Future,
@@ -33,20 +34,39 @@ impl<T: 'static> AsyncJoinHandle for RemoteHandle<T> {
         self.forget()
     }
 }
+/// Dropping a [`RemoteHandle`] cancels the task, like dropping a `JoinHandle`, so the conversion
+/// keeps the drop behavior. With the `detach_on_drop` feature the resulting handle detaches on drop
+/// instead. See [`JoinHandle::try_into_remote_handle`] for the other direction.
+//
 impl<T> From<RemoteHandle<T>> for JoinHandle<T> {
     fn from(x: RemoteHandle<T>) -> Self {
-        Self::RemoteHandle(x)
+        Self::RemoteHandle(x).with_default_drop()
     }
 }
 
 /// A framework agnostic JoinHandle type. Cancels the future on dropping the handle.
-/// You can call [`detach`](JoinHandle::detach) to leave the future running when dropping the handle.
+/// You can call [`detach`](JoinHandle::detach) to leave the future running when dropping the handle,
+/// or [`detach_on_drop`](JoinHandle::detach_on_drop) if you might still want to await it.
+///
+/// # The `detach_on_drop` feature
+///
+/// With the `detach_on_drop` feature enabled, all handles returned by this crate detach on drop by
+/// default, as if [`detach_on_drop`](JoinHandle::detach_on_drop) was called on each of them. This
+/// suits fire-and-forget code, but beware of the tradeoffs:
+///
+/// - it changes the behavior for every crate in your dependency graph that uses async_executors, since
+///   cargo features are unified. Libraries should never enable it, only applications.
+/// - tasks no longer get cancelled when nobody is interested in their output anymore, so forgotten
+///   tasks keep using resources. Use [`abort`](JoinHandle::abort), [`AbortOnDrop`](crate::AbortOnDrop)
+///   or [`JoinSet::abort_all`](crate::JoinSet::abort_all) to cancel a task explicitly.
+/// - the handles are of the [`DetachOnDrop`] variant, so if you match on the variants to get at the
+///   native handle, call [`DetachOnDrop::into_inner`] first. The `as_*` escape hatches look through it.
 ///
 /// This leverages the performance gains from the native join handles compared to
 /// [RemoteHandle](futures_util::future::RemoteHandle) where possible.
 ///
 /// It does wrap futures in [Abortable](futures_util::future::Abortable) where needed as
-/// [tokio] and [async-std](async_std_crate) don't support canceling out of the box.
+/// [tokio], [async-std](async_std_crate) and wasm-bindgen-futures don't support canceling out of the box.
 ///
 /// # Panics
 ///
@@ -66,8 +86,265 @@ pub enum JoinHandle<T> {
     AsyncJoinHandle(crate::AsyncGlobalJoinHandle<T>),
     #[cfg(feature = "async_std")]
     AsyncStdJoinHandle(crate::AsyncStdJoinHandle<T>),
+    #[cfg(feature = "bindgen")]
+    BindgenJoinHandle(crate::BindgenJoinHandle<T>),
+    #[cfg(feature = "compio")]
+    CompioJoinHandle(crate::CompioJoinHandle<T>),
+    DetachOnDrop(DetachOnDrop<T>),
+    Abortable(crate::AbortableJoinHandle<T>),
 }
 impl<T> Unpin for JoinHandle<T> {}
+
+impl<T> JoinHandle<T> {
+    // All conversions from the native handles go through here, so the `detach_on_drop` feature can
+    // change the default drop behavior in one place.
+    //
+    pub(crate) fn with_default_drop(self) -> Self {
+        #[cfg(feature = "detach_on_drop")]
+        //
+        return self.detach_on_drop();
+
+        #[cfg(not(feature = "detach_on_drop"))]
+        //
+        self
+    }
+
+    /// Change the drop policy of this handle, so that dropping it lets the task keep running
+    /// rather than cancelling it. Awaiting the handle still gives you the output.
+    pub fn detach_on_drop(self) -> Self {
+        match self {
+            JoinHandle::DetachOnDrop(_) => self,
+            _ => JoinHandle::DetachOnDrop(DetachOnDrop {
+                handle: Some(Box::new(self)),
+            }),
+        }
+    }
+
+    /// Box the handle, eg. to store handles from different backends in a
+    /// `FuturesUnordered<BoxFuture<'static, T>>`. This does not change the drop behavior, dropping
+    /// the boxed future still cancels the task unless the handle [detaches on drop](JoinHandle::detach_on_drop).
+    pub fn boxed(self) -> BoxFuture<'static, T>
+    where
+        T: Send + 'static,
+    {
+        Box::pin(self)
+    }
+
+    /// Like [`boxed`](JoinHandle::boxed), for outputs that are not `Send`.
+    pub fn boxed_local(self) -> LocalBoxFuture<'static, T>
+    where
+        T: 'static,
+    {
+        Box::pin(self)
+    }
+
+    /// Turn the handle into a stream that yields the output of the task and then ends, eg. to merge
+    /// task completions with other streams in [`select_all`](futures_util::stream::select_all).
+    /// Dropping the stream before it yields drops the handle, with the usual drop behavior.
+    pub fn into_stream(self) -> impl Stream<Item = T>
+    where
+        T: 'static,
+    {
+        futures_util::stream::once(self)
+    }
+
+    /// Get the [`RemoteHandle`] back out of a handle that wraps one, eg. the handles of executors that
+    /// don't have native join handles, to pass it to code that expects a `RemoteHandle`. Returns the
+    /// handle unchanged if it wraps something else, like a native tokio handle.
+    ///
+    /// A handle that [detaches on drop](JoinHandle::detach_on_drop) is unwrapped as well, but mind that
+    /// the drop behavior changes: dropping a `RemoteHandle` always cancels the task. Call
+    /// [`forget`](RemoteHandle::forget) on it to let the task run to completion instead.
+    pub fn try_into_remote_handle(self) -> Result<RemoteHandle<T>, Self> {
+        match self {
+            JoinHandle::RemoteHandle(handle) => Ok(handle),
+
+            JoinHandle::DetachOnDrop(x) => match x.into_inner() {
+                Some(JoinHandle::RemoteHandle(handle)) => Ok(handle),
+                Some(other) => Err(other.detach_on_drop()),
+                None => Err(JoinHandle::DetachOnDrop(DetachOnDrop { handle: None })),
+            },
+
+            other => Err(other),
+        }
+    }
+
+    /// Make the handle safe to await from any runtime, eg. to spawn on [`TokioTp`](crate::TokioTp) and await
+    /// the output from within an [`AsyncStd`](crate::AsyncStd) task.
+    ///
+    /// The native handles of tokio, async-std and async-global-executor are futures like any other, but they
+    /// rely on the machinery of their runtime, like the cooperative scheduling budget of tokio, and they only
+    /// resolve as long as that runtime keeps running. This spawns a small task on the runtime of the
+    /// original task which forwards the output through a oneshot channel, and returns a handle wrapping the
+    /// [`RemoteHandle`] of that channel, which can be polled from anywhere. It costs an extra task.
+    ///
+    /// The drop behavior stays the same: dropping the new handle drops the forwarding task and with it the
+    /// native handle, which cancels the original task, unless the handle [detaches on drop](JoinHandle::detach_on_drop).
+    ///
+    /// Handles that don't have a native join handle are based on a `RemoteHandle` already and are returned
+    /// unchanged, as are handles of runtimes that are bound to a thread, like compio and the runtimes on Wasm,
+    /// and tokio handles when it's not known which runtime the task was spawned on.
+    pub fn make_portable(self) -> Self
+    where
+        T: Send + 'static,
+    {
+        match self {
+            #[cfg(feature = "tokio")]
+            JoinHandle::TokioJoinHandle(x) => match x.runtime().cloned() {
+                Some(rt) => {
+                    let (fut, handle) = x.remote_handle();
+                    rt.spawn(fut);
+                    JoinHandle::RemoteHandle(handle)
+                }
+
+                None => JoinHandle::TokioJoinHandle(x),
+            },
+
+            // On Wasm async-std only runs local tasks.
+            #[cfg(all(feature = "async_std", not(target_arch = "wasm32")))]
+            JoinHandle::AsyncStdJoinHandle(x) => {
+                let (fut, handle) = x.remote_handle();
+                async_std_crate::task::spawn(fut);
+                JoinHandle::RemoteHandle(handle)
+            }
+
+            #[cfg(feature = "async_global")]
+            JoinHandle::AsyncJoinHandle(x) => {
+                let (fut, handle) = x.remote_handle();
+                async_global_executor::spawn(fut).detach();
+                JoinHandle::RemoteHandle(handle)
+            }
+
+            JoinHandle::DetachOnDrop(x) => match x.into_inner() {
+                Some(handle) => handle.make_portable().detach_on_drop(),
+                None => JoinHandle::DetachOnDrop(DetachOnDrop { handle: None }),
+            },
+
+            other => other,
+        }
+    }
+
+    /// Cancel the task, also when the handle was made to [detach on drop](JoinHandle::detach_on_drop).
+    /// Does nothing if the task has already completed. See also [`AbortOnDrop`](crate::AbortOnDrop).
+    ///
+    /// Returns `false` when the handle can not cancel its task, like the handles from
+    /// [`from_async_std`](JoinHandle::from_async_std). The task is detached instead. Use
+    /// [`is_abortable`](JoinHandle::is_abortable) to find out up front.
+    pub fn abort(self) -> bool {
+        let abortable = self.is_abortable();
+
+        match self {
+            JoinHandle::DetachOnDrop(x) => {
+                if let Some(handle) = x.into_inner() {
+                    handle.abort();
+                }
+            }
+
+            JoinHandle::Abortable(x) => x.abort(),
+            handle => drop(handle),
+        }
+
+        abortable
+    }
+
+    /// Whether [`abort`](JoinHandle::abort) can cancel the task. This is the case for all handles returned
+    /// by this crate, but not for tasks spawned on async-std that were not made abortable, see
+    /// [`from_async_std`](JoinHandle::from_async_std).
+    pub fn is_abortable(&self) -> bool {
+        match self {
+            #[cfg(feature = "async_std")]
+            JoinHandle::AsyncStdJoinHandle(x) => x.is_abortable(),
+            JoinHandle::DetachOnDrop(x) => match x.handle.as_deref() {
+                Some(handle) => handle.is_abortable(),
+                None => true,
+            },
+            _ => true,
+        }
+    }
+}
+
+/// A join handle that detaches the task when dropped instead of cancelling it.
+/// See [`JoinHandle::detach_on_drop`].
+#[derive(Debug)]
+pub struct DetachOnDrop<T> {
+    handle: Option<Box<JoinHandle<T>>>,
+}
+
+impl<T: 'static> Future for DetachOnDrop<T> {
+    type Output = T;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let handle = self
+            .handle
+            .as_mut()
+            .expect("Cannot poll a detached JoinHandle");
+
+        let out = ready!(Pin::new(&mut **handle).poll(cx));
+
+        // The task has completed, there is nothing left to detach.
+        self.handle = None;
+
+        Poll::Ready(out)
+    }
+}
+
+impl<T> DetachOnDrop<T> {
+    /// Get back the wrapped handle, which cancels the task when dropped. Returns `None` if the task
+    /// has already been joined.
+    pub fn into_inner(mut self) -> Option<JoinHandle<T>> {
+        self.handle.take().map(|handle| *handle)
+    }
+}
+
+impl<T> Drop for DetachOnDrop<T> {
+    fn drop(&mut self) {
+        if let Some(handle) = self.handle.take() {
+            handle.detach_any();
+        }
+    }
+}
+
+/// Escape hatches to the native join handles, so you can use runtime specific functionality
+/// that isn't exposed by this crate. Each method returns `None` if the handle is of a different
+/// variant. Handles that [detach on drop](JoinHandle::detach_on_drop) are looked through.
+///
+/// Note that relying on these ties your code to a specific backend.
+//
+impl<T> JoinHandle<T> {
+    /// Access the native tokio handle. Also returns `None` once the task has been joined.
+    #[cfg(feature = "tokio")]
+    pub fn as_tokio(&self) -> Option<&tokio::task::JoinHandle<T>> {
+        match self {
+            JoinHandle::TokioJoinHandle(x) => x.inner(),
+            JoinHandle::DetachOnDrop(x) => x.handle.as_deref().and_then(JoinHandle::as_tokio),
+            _ => None,
+        }
+    }
+
+    /// Access the native async-std handle. The output is wrapped in `Result` because
+    /// the task is made abortable. Also returns `None` once the handle has been detached, or when it
+    /// wraps a task that isn't abortable, see [`from_async_std`](JoinHandle::from_async_std).
+    #[cfg(feature = "async_std")]
+    pub fn as_async_std(&self) -> Option<&async_std_crate::task::JoinHandle<Result<T, Aborted>>> {
+        match self {
+            JoinHandle::AsyncStdJoinHandle(x) => x.inner(),
+            JoinHandle::DetachOnDrop(x) => x.handle.as_deref().and_then(JoinHandle::as_async_std),
+            _ => None,
+        }
+    }
+
+    /// Access the native async-global-executor task.
+    #[cfg(feature = "async_global")]
+    pub fn as_async_global(&self) -> Option<&async_global_executor::Task<T>> {
+        match self {
+            JoinHandle::AsyncJoinHandle(x) => Some(x.inner()),
+            JoinHandle::DetachOnDrop(x) => {
+                x.handle.as_deref().and_then(JoinHandle::as_async_global)
+            }
+            _ => None,
+        }
+    }
+}
 impl<T: 'static> Future for JoinHandle<T> {
     type Output = T;
 
@@ -80,22 +357,131 @@ impl<T: 'static> Future for JoinHandle<T> {
             JoinHandle::AsyncJoinHandle(x) => Pin::new(x).poll(cx),
             #[cfg(feature = "async_std")]
             JoinHandle::AsyncStdJoinHandle(x) => Pin::new(x).poll(cx),
+            #[cfg(feature = "bindgen")]
+            JoinHandle::BindgenJoinHandle(x) => Pin::new(x).poll(cx),
+            #[cfg(feature = "compio")]
+            JoinHandle::CompioJoinHandle(x) => Pin::new(x).poll(cx),
+            JoinHandle::DetachOnDrop(x) => Pin::new(x).poll(cx),
+            JoinHandle::Abortable(x) => Pin::new(x).poll(cx),
+        }
+    }
+}
+// Why a task did not complete, as reported by the handle. See `JoinHandle::poll_join`.
+//
+pub(crate) enum JoinFailure {
+    // Only tokio hands us the panic payload.
+    //
+    #[cfg_attr(not(feature = "tokio"), allow(dead_code))]
+    Panicked(Box<dyn std::any::Any + Send + 'static>),
+    Cancelled,
+}
+
+impl<T: 'static> JoinHandle<T> {
+    // Like poll, but reports a task that didn't complete as an error, where the native handle tells us
+    // so. The other handles, like RemoteHandle and the tasks of async-global-executor, only report a
+    // panic or a cancellation by unwinding, so those still unwind like awaiting the handle does.
+    //
+    pub(crate) fn poll_join(&mut self, cx: &mut Context<'_>) -> Poll<Result<T, JoinFailure>> {
+        match self {
+            #[cfg(feature = "tokio")]
+            JoinHandle::TokioJoinHandle(x) => x.poll_join(cx).map_err(|e| match e {
+                crate::JoinErrorKind::Panic(payload) => JoinFailure::Panicked(payload),
+                _ => JoinFailure::Cancelled,
+            }),
+
+            #[cfg(feature = "async_std")]
+            JoinHandle::AsyncStdJoinHandle(x) => {
+                x.poll_join(cx).map_err(|_| JoinFailure::Cancelled)
+            }
+
+            JoinHandle::DetachOnDrop(x) => {
+                let handle = x
+                    .handle
+                    .as_mut()
+                    .expect("Cannot poll a detached JoinHandle");
+
+                let out = ready!(handle.poll_join(cx));
+
+                // The task has completed, there is nothing left to detach.
+                x.handle = None;
+
+                Poll::Ready(out)
+            }
+
+            JoinHandle::Abortable(x) => x.poll_join(cx),
+            other => Pin::new(other).poll(cx).map(Ok),
         }
     }
 }
+
+impl<T: 'static, E: 'static> JoinHandle<Result<T, E>> {
+    /// For tasks that return a `Result`, fold a failure of the task into the error, so `?` covers both.
+    /// The returned future resolves to [`TaskFailure::Failed`](crate::TaskFailure::Failed) with the error
+    /// of the task, to [`TaskFailure::Panicked`](crate::TaskFailure::Panicked) when it panicked, or to
+    /// [`TaskFailure::Cancelled`](crate::TaskFailure::Cancelled) when it was aborted or the runtime
+    /// dropped it.
+    ///
+    /// This relies on the handle to report the failure, so it works for the handles of tokio and
+    /// async-std, and for the handles of [`spawn_handle_with_abort`](crate::SpawnHandleExt::spawn_handle_with_abort)
+    /// that were aborted. The handles based on [`RemoteHandle`], like the ones of the futures executors, and
+    /// the handles of async-global-executor, bindgen and compio only report a panic by unwinding, so for
+    /// those this unwinds the current thread like awaiting the handle would.
+    //
+    pub fn flatten_result(self) -> crate::FlattenResult<T, E> {
+        crate::FlattenResult::new(self)
+    }
+}
+
 impl<T: 'static> AsyncJoinHandle for JoinHandle<T> {
     fn detach(self)
     where
         Self: Sized,
     {
+        self.detach_any()
+    }
+}
+
+impl<T> JoinHandle<T> {
+    /// Wrap a task spawned directly on async-std, eg. by code that doesn't use this crate. The task
+    /// isn't abortable, so dropping the handle detaches it instead of cancelling it. Use
+    /// [`from_async_std_abortable`](JoinHandle::from_async_std_abortable) if you made it abortable.
+    #[cfg(feature = "async_std")]
+    pub fn from_async_std(task: async_std_crate::task::JoinHandle<T>) -> Self {
+        crate::AsyncStdJoinHandle::from_task(task).into()
+    }
+
+    /// Wrap a task spawned directly on async-std that was made [abortable](futures_util::future::abortable),
+    /// like the ones spawned by this crate. Dropping the handle cancels the task.
+    #[cfg(feature = "async_std")]
+    pub fn from_async_std_abortable(
+        task: async_std_crate::task::JoinHandle<Result<T, Aborted>>,
+        abort_handle: AbortHandle,
+    ) -> Self {
+        crate::AsyncStdJoinHandle::new(task, abort_handle).into()
+    }
+
+    /// Wrap a task spawned directly on async-global-executor. Dropping the handle cancels the task.
+    #[cfg(feature = "async_global")]
+    pub fn from_async_global(task: async_global_executor::Task<T>) -> Self {
+        crate::AsyncGlobalJoinHandle::new(task).into()
+    }
+
+    // Same as `AsyncJoinHandle::detach`, without requiring `T: 'static`, so it can be used in `Drop`.
+    pub(crate) fn detach_any(self) {
         match self {
-            JoinHandle::RemoteHandle(x) => x.detach(),
+            JoinHandle::RemoteHandle(x) => x.forget(),
             #[cfg(feature = "tokio")]
             JoinHandle::TokioJoinHandle(x) => x.detach(),
             #[cfg(feature = "async_global")]
             JoinHandle::AsyncJoinHandle(x) => x.detach(),
             #[cfg(feature = "async_std")]
             JoinHandle::AsyncStdJoinHandle(x) => x.detach(),
+            #[cfg(feature = "bindgen")]
+            JoinHandle::BindgenJoinHandle(x) => x.forget(),
+            #[cfg(feature = "compio")]
+            JoinHandle::CompioJoinHandle(x) => x.detach(),
+            JoinHandle::DetachOnDrop(x) => drop(x),
+            JoinHandle::Abortable(x) => x.detach(),
         }
     }
 }
@@ -104,7 +490,7 @@ impl<T: 'static> AsyncJoinHandle for JoinHandle<T> {
 mod tests {
     use super::*;
 
-    // It's important that this is not Send, as we allow spawning !Send futures on it.
+    // Handles of Send tasks must be movable across threads.
     //
-    static_assertions::assert_impl_all!(JoinHandle<()>: Send);
+    static_assertions::assert_impl_all!(JoinHandle<()>: Send, Sync);
 }
@@ -0,0 +1,106 @@
+use crate::{JoinHandle, SpawnError, SpawnHandle, SpawnHandleExt};
+use futures_util::stream::{FusedStream, FuturesUnordered, Stream, StreamExt};
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// A collection of spawned tasks that can be awaited in the order they complete. Works with any
+/// executor that implements [`SpawnHandle`].
+///
+/// Dropping the set cancels the tasks that are still running, also the ones whose handle
+/// [detaches on drop](JoinHandle::detach_on_drop), like [`abort_all`](JoinSet::abort_all) does.
+/// It also implements [`Stream`], yielding the outputs as the tasks complete.
+//
+#[derive(Debug)]
+//
+pub struct JoinSet<T> {
+    handles: FuturesUnordered<JoinHandle<T>>,
+}
+
+impl<T> Default for JoinSet<T> {
+    fn default() -> Self {
+        Self {
+            handles: FuturesUnordered::new(),
+        }
+    }
+}
+
+impl<T: Send + 'static> JoinSet<T> {
+    /// Create an empty set.
+    //
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Spawn `future` on `exec` and add it to the set.
+    //
+    pub fn spawn<E>(
+        &mut self,
+        exec: &E,
+        future: impl Future<Output = T> + Send + 'static,
+    ) -> Result<(), SpawnError>
+    where
+        E: SpawnHandle<T> + ?Sized,
+    {
+        self.push(exec.spawn_handle(future)?);
+        Ok(())
+    }
+
+    /// Add a task that has already been spawned.
+    //
+    pub fn push(&mut self, handle: JoinHandle<T>) {
+        self.handles.push(handle);
+    }
+
+    /// Wait for the next task to complete. Returns `None` when the set is empty.
+    //
+    pub async fn join_next(&mut self) -> Option<T> {
+        self.handles.next().await
+    }
+
+    /// The number of tasks that haven't been joined yet.
+    //
+    pub fn len(&self) -> usize {
+        self.handles.len()
+    }
+
+    /// Whether there are no tasks left to join.
+    //
+    pub fn is_empty(&self) -> bool {
+        self.handles.is_empty()
+    }
+}
+
+impl<T> JoinSet<T> {
+    /// Cancel all the tasks in the set, also the ones whose handle [detaches on drop](JoinHandle::detach_on_drop).
+    //
+    pub fn abort_all(&mut self) {
+        std::mem::take(&mut self.handles)
+            .into_iter()
+            .for_each(|handle| {
+                handle.abort();
+            });
+    }
+}
+
+impl<T> Drop for JoinSet<T> {
+    fn drop(&mut self) {
+        self.abort_all();
+    }
+}
+
+impl<T: 'static> Stream for JoinSet<T> {
+    type Item = T;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        self.handles.poll_next_unpin(cx)
+    }
+}
+
+impl<T: 'static> FusedStream for JoinSet<T> {
+    fn is_terminated(&self) -> bool {
+        self.handles.is_terminated()
+    }
+}
@@ -0,0 +1,73 @@
+use crate::{Abort, Elapsed, Timer};
+use futures_util::future::BoxFuture;
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+/// Extension trait for join handles, eg. [`JoinHandle`](crate::JoinHandle).
+//
+pub trait JoinHandleExt: Future + Abort + Unpin + Sized {
+    /// Wait for the task to complete, giving up once `dur` has elapsed according to the timer of `exec`.
+    ///
+    /// Unlike wrapping the handle in a generic timeout combinator, this aborts the task when the
+    /// deadline passes, also when the handle was made to [detach on drop](crate::JoinHandle::detach_on_drop),
+    /// so the resources held by the task are freed. Dropping the returned future before it resolves
+    /// drops the handle, with the usual drop behavior of the handle.
+    //
+    fn join_timeout<E: Timer + ?Sized>(self, dur: Duration, exec: &E) -> JoinTimeout<Self> {
+        JoinTimeout {
+            handle: Some(self),
+            sleep: exec.sleep(dur),
+        }
+    }
+}
+
+impl<H: Future + Abort + Unpin> JoinHandleExt for H {}
+
+/// Future returned by [`JoinHandleExt::join_timeout`].
+//
+#[must_use = "futures do nothing unless polled"]
+//
+pub struct JoinTimeout<H> {
+    handle: Option<H>,
+    sleep: BoxFuture<'static, ()>,
+}
+
+impl<H: Future + Abort + Unpin> Future for JoinTimeout<H> {
+    type Output = Result<H::Output, Elapsed>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let handle = self
+            .handle
+            .as_mut()
+            .expect("JoinTimeout polled after completion");
+
+        if let Poll::Ready(out) = Pin::new(handle).poll(cx) {
+            self.handle = None;
+            return Poll::Ready(Ok(out));
+        }
+
+        match self.sleep.as_mut().poll(cx) {
+            Poll::Ready(()) => {
+                if let Some(handle) = self.handle.take() {
+                    handle.abort();
+                }
+
+                Poll::Ready(Err(Elapsed))
+            }
+
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl<H> std::fmt::Debug for JoinTimeout<H> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JoinTimeout")
+            .field("done", &self.handle.is_none())
+            .finish()
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{SpawnError, StaticRuntime};
+use crate::{core::spawn_hook::on_spawn, SpawnError, StaticRuntime};
 #[allow(unused_imports)]
 //
 use {
@@ -34,6 +34,26 @@ pub trait LocalSpawnHandleExt<Out: 'static>: LocalSpawnHandle<Out> {
         &self,
         future: impl Future<Output = Out> + 'static,
     ) -> Result<JoinHandle<Out>, SpawnError>;
+
+    /// Spawn a future and catch a panic in it, so the handle resolves to an `Err` with the panic
+    /// payload instead of unwinding the thread that awaits it. That thread is not necessarily the one
+    /// the task ran on, and the code that awaits might not expect to unwind.
+    ///
+    /// The future is wrapped in [`std::panic::AssertUnwindSafe`], so make sure nothing observes
+    /// inconsistent state after a panic. Note that this catches the panic of the task itself. Awaiting
+    /// the handle still panics when the executor is dropped before the task completes.
+    //
+    fn spawn_handle_local_catch_unwind(
+        &self,
+        future: impl Future<Output = Out> + 'static,
+    ) -> Result<JoinHandle<std::thread::Result<Out>>, SpawnError>
+    where
+        Self: LocalSpawnHandle<std::thread::Result<Out>>,
+    {
+        let future = std::panic::AssertUnwindSafe(future).catch_unwind();
+
+        LocalSpawnHandleExt::<std::thread::Result<Out>>::spawn_handle_local(self, future)
+    }
 }
 
 impl<T, Out> LocalSpawnHandleExt<Out> for T
@@ -45,7 +65,9 @@ where
         &self,
         future: impl Future<Output = Out> + 'static,
     ) -> Result<JoinHandle<Out>, SpawnError> {
-        self.spawn_handle_local_obj(LocalFutureObj::new(future.boxed_local()))
+        let handle = self.spawn_handle_local_obj(LocalFutureObj::new(future.boxed_local()))?;
+        on_spawn::<Self>(None);
+        Ok(handle)
     }
 }
 
@@ -0,0 +1,356 @@
+use crate::{JoinHandle, LocalSpawn, LocalSpawnHandle, Spawn, SpawnError, SpawnHandle};
+use futures_task::{FutureObj, LocalFutureObj};
+use futures_util::{
+    future::FutureExt,
+    task::{waker, ArcWake},
+};
+use std::{
+    cell::RefCell,
+    collections::HashSet,
+    future::Future,
+    panic::{catch_unwind, resume_unwind, AssertUnwindSafe},
+    pin::Pin,
+    rc::{Rc, Weak},
+    sync::{Arc, Mutex},
+    task::{Context, Waker},
+};
+
+/// An executor that only runs when you tell it to, for embedding in a loop that is owned by the host,
+/// eg. the main loop of a game. Spawning queues the task, and every call to
+/// [`run_pending`](ManualExecutor::run_pending) polls the tasks that have been woken up since the previous
+/// call, once. Nothing runs in the background, so the host decides when and how much async work happens.
+///
+/// The executor holds at most `capacity` tasks. When it's full, spawning returns a [`SpawnError`] of kind
+/// [`AtCapacity`](crate::SpawnErrorKind::AtCapacity) until some tasks complete.
+///
+/// Tasks spawn on the executor through a [`ManualSpawner`], see [`spawner`](ManualExecutor::spawner).
+/// Dropping the executor drops the tasks that have not completed. `ManualExecutor` can spawn `!Send`
+/// futures, so it's neither `Send` nor `Sync`. Waking up tasks works from any thread.
+///
+/// ```
+/// use async_executors::{ManualExecutor, SpawnHandleExt};
+///
+/// let exec = ManualExecutor::new(16);
+/// let mut handle = exec.spawn_handle(async { 5u8 }).expect("spawn");
+///
+/// // The host loop.
+/// //
+/// while exec.run_pending() > 0 {}
+///
+/// assert_eq!(Some(5), futures::FutureExt::now_or_never(&mut handle));
+/// ```
+//
+pub struct ManualExecutor {
+    inner: Rc<Inner>,
+}
+
+/// Spawns on a [`ManualExecutor`] without keeping it alive, so tasks can hold one. Otherwise the tasks
+/// would own the executor that owns them, and neither would ever be dropped. Get one with
+/// [`ManualExecutor::spawner`].
+///
+/// Spawning returns a [`SpawnError`] of kind [`Shutdown`](crate::SpawnErrorKind::Shutdown) once the
+/// executor has been dropped.
+//
+#[derive(Clone)]
+//
+pub struct ManualSpawner {
+    inner: Weak<Inner>,
+}
+
+struct Inner {
+    capacity: usize,
+    slots: RefCell<Slots>,
+    woken: Arc<Woken>,
+}
+
+#[derive(Default)]
+struct Slots {
+    tasks: Vec<Option<Task>>,
+    free: Vec<usize>,
+    len: usize,
+}
+
+struct Task {
+    future: Option<LocalFutureObj<'static, ()>>,
+    waker: Waker,
+}
+
+// The ids of the tasks that need to be polled on the next run.
+//
+#[derive(Default)]
+struct Woken(Mutex<Vec<usize>>);
+
+struct TaskWaker {
+    id: usize,
+    woken: Arc<Woken>,
+}
+
+impl ArcWake for TaskWaker {
+    fn wake_by_ref(arc_self: &Arc<Self>) {
+        arc_self.woken.push(arc_self.id);
+    }
+}
+
+impl Woken {
+    fn push(&self, id: usize) {
+        self.0.lock().expect("lock woken tasks").push(id);
+    }
+
+    fn take(&self) -> Vec<usize> {
+        std::mem::take(&mut *self.0.lock().expect("lock woken tasks"))
+    }
+}
+
+impl Inner {
+    fn push(&self, future: LocalFutureObj<'static, ()>) -> Result<(), SpawnError> {
+        let mut slots = self.slots.borrow_mut();
+
+        if slots.len >= self.capacity {
+            return Err(SpawnError::at_capacity());
+        }
+
+        let id = slots.free.pop().unwrap_or(slots.tasks.len());
+
+        let waker = waker(Arc::new(TaskWaker {
+            id,
+            woken: self.woken.clone(),
+        }));
+
+        let task = Some(Task {
+            future: Some(future),
+            waker,
+        });
+
+        match slots.tasks.get_mut(id) {
+            Some(slot) => *slot = task,
+            None => slots.tasks.push(task),
+        }
+
+        slots.len += 1;
+        self.woken.push(id);
+
+        Ok(())
+    }
+
+    fn status(&self) -> Result<(), SpawnError> {
+        match self.slots.borrow().len < self.capacity {
+            true => Ok(()),
+            false => Err(SpawnError::at_capacity()),
+        }
+    }
+}
+
+impl ManualExecutor {
+    /// Create an executor that holds at most `capacity` tasks at a time.
+    //
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            inner: Rc::new(Inner {
+                capacity,
+                slots: Default::default(),
+                woken: Default::default(),
+            }),
+        }
+    }
+
+    /// Poll every task that was spawned or woken up since the previous call, once. Tasks that get
+    /// spawned or woken up while this runs will be polled on the next call, so this always returns.
+    ///
+    /// Returns how many tasks were polled. When this returns 0, all tasks are waiting on something
+    /// outside of the executor, eg. a timer or IO, or there are no tasks.
+    ///
+    /// # Panics
+    ///
+    /// When a task panics. The task is dropped, the tasks that still had to be polled will be polled on
+    /// the next call.
+    //
+    pub fn run_pending(&self) -> usize {
+        let mut polled = HashSet::new();
+        let woken = self.inner.woken.take();
+
+        for (i, &id) in woken.iter().enumerate() {
+            // A task can be woken up several times before it gets polled.
+            //
+            if polled.contains(&id) {
+                continue;
+            }
+
+            // Take the future out, so the task can spawn on the executor while it's being polled.
+            // If it's not there, the task has completed already.
+            //
+            let (mut future, waker) = {
+                let mut slots = self.inner.slots.borrow_mut();
+
+                match slots.tasks.get_mut(id).and_then(Option::as_mut) {
+                    Some(task) => match task.future.take() {
+                        Some(future) => (future, task.waker.clone()),
+                        None => continue,
+                    },
+                    None => continue,
+                }
+            };
+
+            polled.insert(id);
+
+            let done = catch_unwind(AssertUnwindSafe(|| {
+                Pin::new(&mut future)
+                    .poll(&mut Context::from_waker(&waker))
+                    .is_ready()
+            }));
+
+            let done = match done {
+                Ok(done) => done,
+
+                Err(payload) => {
+                    drop(future);
+                    self.remove(id);
+                    woken[i + 1..]
+                        .iter()
+                        .for_each(|&id| self.inner.woken.push(id));
+                    resume_unwind(payload)
+                }
+            };
+
+            if done {
+                self.remove(id);
+            } else if let Some(task) = self.inner.slots.borrow_mut().tasks[id].as_mut() {
+                task.future = Some(future);
+            }
+        }
+
+        polled.len()
+    }
+
+    fn remove(&self, id: usize) {
+        let mut slots = self.inner.slots.borrow_mut();
+
+        slots.tasks[id] = None;
+        slots.free.push(id);
+        slots.len -= 1;
+    }
+
+    /// The number of tasks that have not completed yet.
+    //
+    pub fn len(&self) -> usize {
+        self.inner.slots.borrow().len
+    }
+
+    /// Whether all tasks have completed.
+    //
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The maximum number of tasks this executor holds.
+    //
+    pub fn capacity(&self) -> usize {
+        self.inner.capacity
+    }
+
+    fn exec(&self) -> Result<&Inner, SpawnError> {
+        Ok(&self.inner)
+    }
+
+    /// A handle to spawn on this executor that does not keep it alive. Give this to tasks that need
+    /// to spawn.
+    //
+    pub fn spawner(&self) -> ManualSpawner {
+        ManualSpawner {
+            inner: Rc::downgrade(&self.inner),
+        }
+    }
+}
+
+impl ManualSpawner {
+    fn exec(&self) -> Result<Rc<Inner>, SpawnError> {
+        self.inner.upgrade().ok_or_else(SpawnError::shutdown)
+    }
+}
+
+// ManualExecutor and ManualSpawner spawn the same way, they only differ in how `exec` gets to Inner.
+//
+macro_rules! impl_spawn {
+    ($type:ty) => {
+        impl Spawn for $type {
+            fn spawn_obj(&self, future: FutureObj<'static, ()>) -> Result<(), SpawnError> {
+                self.exec()?.push(future.into())
+            }
+
+            fn status(&self) -> Result<(), SpawnError> {
+                self.status_local()
+            }
+        }
+
+        impl LocalSpawn for $type {
+            fn spawn_local_obj(
+                &self,
+                future: LocalFutureObj<'static, ()>,
+            ) -> Result<(), SpawnError> {
+                self.exec()?.push(future)
+            }
+
+            fn status_local(&self) -> Result<(), SpawnError> {
+                self.exec()?.status()
+            }
+        }
+
+        impl<Out: Send + 'static> SpawnHandle<Out> for $type {
+            fn spawn_handle_obj(
+                &self,
+                future: FutureObj<'static, Out>,
+            ) -> Result<JoinHandle<Out>, SpawnError> {
+                let inner = self.exec()?;
+                let (fut, handle) = future.remote_handle();
+
+                inner.push(LocalFutureObj::new(fut.boxed_local()))?;
+
+                Ok(handle.into())
+            }
+        }
+
+        impl<Out: 'static> LocalSpawnHandle<Out> for $type {
+            fn spawn_handle_local_obj(
+                &self,
+                future: LocalFutureObj<'static, Out>,
+            ) -> Result<JoinHandle<Out>, SpawnError> {
+                let inner = self.exec()?;
+                let (fut, handle) = future.remote_handle();
+
+                inner.push(LocalFutureObj::new(fut.boxed_local()))?;
+
+                Ok(handle.into())
+            }
+        }
+    };
+}
+
+impl_spawn!(ManualExecutor);
+impl_spawn!(ManualSpawner);
+
+impl std::fmt::Debug for ManualExecutor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ManualExecutor")
+            .field("capacity", &self.inner.capacity)
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+impl std::fmt::Debug for ManualSpawner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ManualSpawner")
+            .field("alive", &(self.inner.strong_count() > 0))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+//
+mod tests {
+    use super::*;
+
+    // It's important that this is not Send, as we allow spawning !Send futures on it.
+    //
+    static_assertions::assert_not_impl_any!(ManualExecutor: Send, Sync);
+    static_assertions::assert_not_impl_any!(ManualSpawner: Send, Sync);
+}
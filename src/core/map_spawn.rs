@@ -0,0 +1,71 @@
+use crate::{JoinHandle, Spawn, SpawnError, SpawnHandle};
+use futures_task::FutureObj;
+use futures_util::future::{BoxFuture, FutureExt};
+
+/// Wraps an executor so that every spawned future goes through `map` first, eg. to log when tasks
+/// start and stop, catch panics or record metrics without wrapping the future at each call site.
+/// Create it with [`SpawnExt::map_spawn`](crate::SpawnExt::map_spawn) or [`MapSpawn::new`].
+///
+/// `map` only sees futures with output `()`. For [`SpawnHandle`] the output is sent to the
+/// [`JoinHandle`] through a [`RemoteHandle`](futures_util::future::RemoteHandle), so the future that
+/// gets mapped is the one that completes once the output has been handed over. That works for any
+/// output type, but the join handles don't use the native handles of the wrapped executor.
+//
+#[derive(Clone)]
+//
+pub struct MapSpawn<E, F> {
+    exec: E,
+    map: F,
+}
+
+impl<E, F> MapSpawn<E, F>
+where
+    F: Fn(BoxFuture<'static, ()>) -> BoxFuture<'static, ()>,
+{
+    /// Apply `map` to every future spawned on `exec`.
+    //
+    pub fn new(exec: E, map: F) -> Self {
+        Self { exec, map }
+    }
+
+    /// Get back the wrapped executor.
+    //
+    pub fn into_inner(self) -> E {
+        self.exec
+    }
+}
+
+impl<E, F> Spawn for MapSpawn<E, F>
+where
+    E: Spawn,
+    F: Fn(BoxFuture<'static, ()>) -> BoxFuture<'static, ()>,
+{
+    fn spawn_obj(&self, future: FutureObj<'static, ()>) -> Result<(), SpawnError> {
+        self.exec
+            .spawn_obj(FutureObj::new((self.map)(future.boxed())))
+    }
+
+    fn status(&self) -> Result<(), SpawnError> {
+        self.exec.status()
+    }
+}
+
+impl<E, F, Out> SpawnHandle<Out> for MapSpawn<E, F>
+where
+    E: Spawn,
+    F: Fn(BoxFuture<'static, ()>) -> BoxFuture<'static, ()>,
+    Out: Send + 'static,
+{
+    fn spawn_handle_obj(
+        &self,
+        future: FutureObj<'static, Out>,
+    ) -> Result<JoinHandle<Out>, SpawnError> {
+        let (fut, handle) = future.remote_handle();
+
+        self.spawn_obj(FutureObj::new(fut.boxed()))?;
+
+        Ok(handle.into())
+    }
+}
+
+wrapper!(MapSpawn<E, F>);
@@ -0,0 +1,115 @@
+use futures_util::future::poll_fn;
+use std::{
+    future::Future,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    task::{Poll, Waker},
+};
+
+/// Wraps an executor to count the tasks spawned through it that haven't completed yet, so you can
+/// wait for all of them with [`idle`](MeteredExecutor::idle), eg. for deterministic teardown in tests.
+///
+/// A task counts as outstanding from the moment it is spawned until its future completes or gets
+/// dropped, eg. because its [`JoinHandle`] cancelled it. This works for any executor, including the
+/// threaded ones, but only knows about tasks spawned through the wrapper. Clones share the count.
+//
+pub struct MeteredExecutor<E> {
+    exec: E,
+    shared: Arc<Shared>,
+}
+
+#[derive(Debug, Default)]
+struct Shared {
+    outstanding: AtomicUsize,
+    wakers: Mutex<Vec<Waker>>,
+}
+
+// Moved into every task, so the count goes down however the future ends.
+//
+struct TaskGuard(Arc<Shared>);
+
+impl Drop for TaskGuard {
+    fn drop(&mut self) {
+        if self.0.outstanding.fetch_sub(1, Ordering::SeqCst) == 1 {
+            let wakers = std::mem::take(&mut *self.0.wakers.lock().expect("lock wakers"));
+            wakers.into_iter().for_each(Waker::wake);
+        }
+    }
+}
+
+fn track<Fut: Future>(shared: &Arc<Shared>, future: Fut) -> impl Future<Output = Fut::Output> {
+    shared.outstanding.fetch_add(1, Ordering::SeqCst);
+    let guard = TaskGuard(shared.clone());
+
+    async move {
+        let _guard = guard;
+        future.await
+    }
+}
+
+impl<E: Clone> Clone for MeteredExecutor<E> {
+    fn clone(&self) -> Self {
+        Self {
+            exec: self.exec.clone(),
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+impl<E> MeteredExecutor<E> {
+    /// Count the tasks spawned on `exec` through this wrapper.
+    //
+    pub fn new(exec: E) -> Self {
+        Self {
+            exec,
+            shared: Default::default(),
+        }
+    }
+
+    /// The number of tasks that have been spawned and haven't completed or been dropped yet.
+    //
+    pub fn outstanding(&self) -> usize {
+        self.shared.outstanding.load(Ordering::SeqCst)
+    }
+
+    /// Resolves once there are no outstanding tasks. Resolves right away if there are none now.
+    ///
+    /// Tasks spawned after it resolved are not waited for, call it again if you spawn more.
+    //
+    pub fn idle(&self) -> impl Future<Output = ()> + Send + 'static {
+        let shared = self.shared.clone();
+
+        poll_fn(move |cx| {
+            if shared.outstanding.load(Ordering::SeqCst) == 0 {
+                return Poll::Ready(());
+            }
+
+            {
+                let mut wakers = shared.wakers.lock().expect("lock wakers");
+
+                // Polling again with the same waker must not grow the list.
+                //
+                if !wakers.iter().any(|w| w.will_wake(cx.waker())) {
+                    wakers.push(cx.waker().clone());
+                }
+            }
+
+            // The last task might have completed before we registered.
+            //
+            if shared.outstanding.load(Ordering::SeqCst) == 0 {
+                return Poll::Ready(());
+            }
+
+            Poll::Pending
+        })
+    }
+}
+
+wrapper!(MeteredExecutor<E>);
+
+wrap_spawn! {
+    MeteredExecutor<E> where {}
+    wrap: |this, future| track(&this.shared, future);
+}
@@ -1,17 +1,94 @@
+// Declared first, so the modules below can use its macros.
+//
+#[macro_use]
+mod wrap_spawn;
+
+mod abort_on_drop;
+mod abortable_join_handle;
 mod block_on;
+mod blocking_iter_stream;
+mod blocking_pool;
+mod cancellation_token;
+#[cfg(all(feature = "channel", not(target_os = "unknown")))]
+mod context_blocking_pool;
+mod coop_budget;
+mod executor;
+mod flatten_result;
+mod full_executor;
 mod join_handle;
+mod join_set;
+mod join_timeout;
 mod local_spawn_handle;
+mod manual_executor;
+mod map_spawn;
+mod metered;
+mod on_task_panic;
+mod par_map;
+mod prioritized_spawn;
+mod race;
+#[cfg(feature = "channel")]
+mod rate_limited_spawn;
+mod shutdown;
 mod spawn;
 mod spawn_blocking;
 mod spawn_handle;
+mod spawn_hook;
+mod spawn_retry;
+mod spawn_stream;
 mod static_runtime;
+mod step_executor;
+mod supervised;
+#[cfg(feature = "sync")]
+pub mod sync;
+#[cfg(not(target_os = "unknown"))]
+mod timed_spawn;
+mod timer;
+#[cfg(not(target_os = "unknown"))]
+mod timer_wheel;
+mod try_scope;
+mod with_handles;
 mod yield_now;
 
+pub use abort_on_drop::*;
+pub use abortable_join_handle::*;
 pub use block_on::*;
+pub use blocking_iter_stream::*;
+pub use blocking_pool::*;
+pub use cancellation_token::*;
+#[cfg(all(feature = "channel", not(target_os = "unknown")))]
+pub use context_blocking_pool::*;
+pub use coop_budget::*;
+pub use executor::*;
+pub use flatten_result::*;
+pub use full_executor::*;
 pub use join_handle::*;
+pub use join_set::*;
+pub use join_timeout::*;
 pub use local_spawn_handle::*;
+pub use manual_executor::*;
+pub use map_spawn::*;
+pub use metered::*;
+pub use on_task_panic::*;
+pub use par_map::*;
+pub use prioritized_spawn::*;
+pub use race::*;
+#[cfg(feature = "channel")]
+pub use rate_limited_spawn::*;
+pub use shutdown::*;
 pub use spawn::*;
 pub use spawn_blocking::*;
 pub use spawn_handle::*;
+pub use spawn_hook::*;
+pub use spawn_retry::*;
+pub use spawn_stream::*;
 pub use static_runtime::*;
+pub use step_executor::*;
+pub use supervised::*;
+#[cfg(not(target_os = "unknown"))]
+pub use timed_spawn::*;
+pub use timer::*;
+#[cfg(not(target_os = "unknown"))]
+pub use timer_wheel::*;
+pub use try_scope::*;
+pub use with_handles::*;
 pub use yield_now::*;
@@ -0,0 +1,76 @@
+use futures_util::FutureExt;
+use std::{
+    any::Any,
+    future::Future,
+    panic::{resume_unwind, AssertUnwindSafe},
+    sync::Arc,
+};
+
+/// Wraps an executor so that `callback` is called with the payload of every panic in a task spawned
+/// through it, eg. to log or count panics in one place instead of relying on the handles being awaited.
+/// Create it with [`SpawnExt::on_task_panic`](crate::SpawnExt::on_task_panic) or [`OnTaskPanic::new`].
+///
+/// The futures are wrapped in [`catch_unwind`](futures_util::FutureExt::catch_unwind). For tasks
+/// spawned with a [`JoinHandle`], the panic is resumed after calling `callback`, so the behavior of the
+/// handle doesn't change. For tasks spawned without one, the panic is swallowed after calling `callback`,
+/// so it doesn't reach the executor. The same caveats as for the executors that catch panics themselves
+/// apply: the futures are wrapped in [`AssertUnwindSafe`].
+//
+pub struct OnTaskPanic<E, F> {
+    exec: E,
+    callback: Arc<F>,
+}
+
+impl<E, F> OnTaskPanic<E, F>
+where
+    F: Fn(&(dyn Any + Send)) + Send + Sync + 'static,
+{
+    /// Call `callback` for every task spawned on `exec` that panics.
+    //
+    pub fn new(exec: E, callback: F) -> Self {
+        Self {
+            exec,
+            callback: Arc::new(callback),
+        }
+    }
+}
+
+async fn watch<Fut: Future, F: Fn(&(dyn Any + Send))>(
+    callback: Arc<F>,
+    future: Fut,
+) -> Fut::Output {
+    match AssertUnwindSafe(future).catch_unwind().await {
+        Ok(out) => out,
+
+        Err(payload) => {
+            callback(&*payload);
+            resume_unwind(payload)
+        }
+    }
+}
+
+async fn swallow<Fut: Future<Output = ()>, F: Fn(&(dyn Any + Send))>(
+    callback: Arc<F>,
+    future: Fut,
+) {
+    if let Err(payload) = AssertUnwindSafe(future).catch_unwind().await {
+        callback(&*payload);
+    }
+}
+
+impl<E: Clone, F> Clone for OnTaskPanic<E, F> {
+    fn clone(&self) -> Self {
+        Self {
+            exec: self.exec.clone(),
+            callback: self.callback.clone(),
+        }
+    }
+}
+
+wrapper!(OnTaskPanic<E, F>);
+
+wrap_spawn! {
+    OnTaskPanic<E, F> where { F: Fn(&(dyn Any + Send)) + Send + Sync + 'static }
+    wrap: |this, future| watch(this.callback.clone(), future);
+    detached: |this, future| swallow(this.callback.clone(), future);
+}
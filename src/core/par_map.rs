@@ -0,0 +1,86 @@
+use crate::{AbortOnDrop, SpawnError, SpawnHandle, SpawnHandleExt};
+use futures_util::{
+    future::FutureExt,
+    stream::{FuturesUnordered, StreamExt},
+};
+use std::future::Future;
+
+/// Run `f` over `items` on `exec`, with at most `concurrency` tasks running at the same time, and
+/// collect the outputs in the order of the input. Like `buffered`, but each future is spawned, so
+/// they run in parallel on threaded executors. If you want the outputs as they complete, see
+/// [`SpawnHandleExt::spawn_stream`].
+///
+/// `f` is called lazily, when there is room for another task.
+///
+/// Dropping the returned future cancels the tasks that are still in flight, also when the handles
+/// would [detach on drop](crate::JoinHandle::detach_on_drop).
+///
+/// # Errors
+///
+/// When spawning fails, the tasks in flight are cancelled and the error is returned. No more items
+/// are taken from the iterator.
+///
+/// # Panics
+///
+/// Panics if `concurrency` is zero. When a task panics, the panic is propagated when it is joined.
+///
+/// ```
+/// # #[cfg(feature = "tokio_ct")]
+/// # {
+/// use async_executors::{ par_map, TokioCtBuilder };
+///
+/// let exec = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+///
+/// let out = exec.block_on( par_map( &exec, 1..=4u32, 2, |i| async move { i * 10 } ) );
+///
+/// assert_eq!( vec![ 10, 20, 30, 40 ], out.expect( "spawn" ) );
+/// # }
+/// ```
+//
+pub async fn par_map<E, I, F, Fut, O>(
+    exec: &E,
+    items: I,
+    concurrency: usize,
+    mut f: F,
+) -> Result<Vec<O>, SpawnError>
+where
+    E: SpawnHandle<O> + ?Sized,
+    I: IntoIterator,
+    F: FnMut(I::Item) -> Fut,
+    Fut: Future<Output = O> + Send + 'static,
+    O: Send + 'static,
+{
+    assert!(concurrency > 0, "concurrency must be at least 1");
+
+    let mut items = items.into_iter().enumerate();
+    let mut in_flight = FuturesUnordered::new();
+    let mut outputs: Vec<Option<O>> = Vec::new();
+
+    loop {
+        while in_flight.len() < concurrency {
+            let (index, item) = match items.next() {
+                Some(next) => next,
+                None => break,
+            };
+
+            // On error the guards in flight are dropped, which cancels the tasks.
+            //
+            let handle = AbortOnDrop::new(exec.spawn_handle(f(item))?);
+
+            outputs.push(None);
+            in_flight.push(handle.map(move |out| (index, out)));
+        }
+
+        // Since we always fill up in_flight first, this means the iterator is exhausted.
+        //
+        match in_flight.next().await {
+            Some((index, out)) => outputs[index] = Some(out),
+            None => break,
+        }
+    }
+
+    Ok(outputs
+        .into_iter()
+        .map(|out| out.expect("all tasks joined"))
+        .collect())
+}
@@ -0,0 +1,366 @@
+use crate::{JoinHandle, Spawn, SpawnError, SpawnHandle};
+use futures_task::FutureObj;
+use futures_util::{
+    task::{waker_ref, ArcWake, AtomicWaker},
+    FutureExt,
+};
+use std::{
+    collections::VecDeque,
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    task::{Context, Poll},
+};
+
+/// The priority of a task spawned on [`PrioritizedSpawn`]. A turn is one poll of a task that is ready.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub enum Priority {
+    /// Gets 4 turns for every turn of a low priority task.
+    High,
+    /// Gets 2 turns for every turn of a low priority task. This is used by [`Spawn::spawn_obj`].
+    #[default]
+    Normal,
+    /// Gets 1 turn.
+    Low,
+}
+
+impl Priority {
+    const ALL: [Priority; 3] = [Priority::High, Priority::Normal, Priority::Low];
+
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    fn weight(self) -> usize {
+        match self {
+            Priority::High => 4,
+            Priority::Normal => 2,
+            Priority::Low => 1,
+        }
+    }
+}
+
+/// Wraps an executor to add prioritization of tasks on executors that don't support it natively.
+///
+/// The tasks are not spawned on the wrapped executor directly. Instead a number of driver tasks
+/// get spawned on it when creating the wrapper. Each driver keeps a queue per [`Priority`] and
+/// polls the tasks that are ready in a weighted fair way, so higher priority tasks get more turns
+/// but lower priority tasks never starve. New tasks are distributed over the drivers round robin.
+///
+/// Since all tasks of a driver run within a single task of the wrapped executor, they only run in
+/// parallel across drivers. Priorities only matter when several tasks are ready at the same time.
+///
+/// The drivers end once all clones of the wrapper are dropped and all their tasks have completed.
+//
+#[derive(Debug)]
+pub struct PrioritizedSpawn<E> {
+    exec: E,
+    inner: Arc<Inner>,
+}
+
+impl<E: Clone> Clone for PrioritizedSpawn<E> {
+    fn clone(&self) -> Self {
+        Self {
+            exec: self.exec.clone(),
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<E: Spawn> PrioritizedSpawn<E> {
+    /// Wrap `exec`, spawning `drivers` driver tasks on it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `drivers` is zero.
+    pub fn new(exec: E, drivers: usize) -> Result<Self, SpawnError> {
+        assert!(drivers > 0, "PrioritizedSpawn needs at least one driver");
+
+        let mut queues = Vec::with_capacity(drivers);
+
+        for _ in 0..drivers {
+            let queue = Arc::new(Queue::default());
+
+            // The drivers are plumbing, not user tasks, so don't call the spawn hook.
+            //
+            exec.spawn_obj(FutureObj::new(Box::new(Driver::new(queue.clone()))))?;
+            queues.push(queue);
+        }
+
+        let inner = Arc::new(Inner {
+            queues,
+            next: AtomicUsize::new(0),
+        });
+
+        Ok(Self { exec, inner })
+    }
+
+    /// Spawn a future with the given priority.
+    pub fn spawn_with_priority<Fut>(
+        &self,
+        priority: Priority,
+        future: Fut,
+    ) -> Result<(), SpawnError>
+    where
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.spawn_obj_with_priority(priority, FutureObj::new(future.boxed()))
+    }
+
+    /// Spawn a future with the given priority and get a [`JoinHandle`] to await it's output.
+    pub fn spawn_handle_with_priority<Fut, Out>(
+        &self,
+        priority: Priority,
+        future: Fut,
+    ) -> Result<JoinHandle<Out>, SpawnError>
+    where
+        Fut: Future<Output = Out> + Send + 'static,
+        Out: 'static + Send,
+    {
+        let (fut, handle) = future.remote_handle();
+
+        self.spawn_with_priority(priority, fut)?;
+
+        Ok(handle.into())
+    }
+
+    fn spawn_obj_with_priority(
+        &self,
+        priority: Priority,
+        future: FutureObj<'static, ()>,
+    ) -> Result<(), SpawnError> {
+        let next = self.inner.next.fetch_add(1, Ordering::Relaxed);
+        let queue = &self.inner.queues[next % self.inner.queues.len()];
+
+        queue.push(priority, future)
+    }
+}
+
+impl<E: Spawn> Spawn for PrioritizedSpawn<E> {
+    fn spawn_obj(&self, future: FutureObj<'static, ()>) -> Result<(), SpawnError> {
+        self.spawn_obj_with_priority(Priority::Normal, future)
+    }
+
+    fn status(&self) -> Result<(), SpawnError> {
+        self.exec.status()
+    }
+}
+
+impl<E: Spawn, Out: 'static + Send> SpawnHandle<Out> for PrioritizedSpawn<E> {
+    fn spawn_handle_obj(
+        &self,
+        future: FutureObj<'static, Out>,
+    ) -> Result<JoinHandle<Out>, SpawnError> {
+        self.spawn_handle_with_priority(Priority::Normal, future)
+    }
+}
+
+// Shared by all clones of a PrioritizedSpawn. Closes the queues when the last one is dropped.
+//
+#[derive(Debug)]
+struct Inner {
+    queues: Vec<Arc<Queue>>,
+    next: AtomicUsize,
+}
+
+impl Drop for Inner {
+    fn drop(&mut self) {
+        for queue in &self.queues {
+            queue.close();
+        }
+    }
+}
+
+// New tasks for a driver, and the ids of its tasks that have been woken up, per priority.
+//
+#[derive(Debug, Default)]
+struct Queue {
+    tasks: Mutex<[VecDeque<FutureObj<'static, ()>>; 3]>,
+    ready: Mutex<[VecDeque<usize>; 3]>,
+    waker: AtomicWaker,
+    closed: AtomicBool,
+    driver_gone: AtomicBool,
+}
+
+impl Queue {
+    fn push(&self, priority: Priority, future: FutureObj<'static, ()>) -> Result<(), SpawnError> {
+        if self.driver_gone.load(Ordering::SeqCst) {
+            return Err(SpawnError::shutdown());
+        }
+
+        self.tasks.lock().expect("lock queue")[priority.index()].push_back(future);
+        self.waker.wake();
+
+        Ok(())
+    }
+
+    fn close(&self) {
+        self.closed.store(true, Ordering::SeqCst);
+        self.waker.wake();
+    }
+}
+
+// The waker of a task on a driver. Puts the task in the ready queue of its priority.
+//
+struct TaskWaker {
+    id: usize,
+    priority: Priority,
+    queue: Arc<Queue>,
+    scheduled: AtomicBool,
+}
+
+impl ArcWake for TaskWaker {
+    fn wake_by_ref(arc_self: &Arc<Self>) {
+        if !arc_self.scheduled.swap(true, Ordering::SeqCst) {
+            arc_self.queue.ready.lock().expect("lock ready queue")[arc_self.priority.index()]
+                .push_back(arc_self.id);
+
+            arc_self.queue.waker.wake();
+        }
+    }
+}
+
+struct Task {
+    future: FutureObj<'static, ()>,
+    waker: Arc<TaskWaker>,
+}
+
+struct Driver {
+    queue: Arc<Queue>,
+    tasks: Vec<Option<Task>>,
+    free: Vec<usize>,
+    len: usize,
+}
+
+impl Driver {
+    fn new(queue: Arc<Queue>) -> Self {
+        Self {
+            queue,
+            tasks: Vec::new(),
+            free: Vec::new(),
+            len: 0,
+        }
+    }
+
+    fn insert(&mut self, priority: Priority, future: FutureObj<'static, ()>) {
+        let id = self.free.pop().unwrap_or(self.tasks.len());
+
+        let waker = Arc::new(TaskWaker {
+            id,
+            priority,
+            queue: self.queue.clone(),
+            scheduled: AtomicBool::new(false),
+        });
+
+        // A new task is ready to be polled.
+        ArcWake::wake_by_ref(&waker);
+
+        let task = Some(Task { future, waker });
+
+        match self.tasks.get_mut(id) {
+            Some(slot) => *slot = task,
+            None => self.tasks.push(task),
+        }
+
+        self.len += 1;
+    }
+
+    // The id might belong to a task that has completed, or to a new task that reuses its slot.
+    // The latter just gets a spurious poll.
+    //
+    fn poll_task(&mut self, id: usize) {
+        let task = match self.tasks.get_mut(id).and_then(Option::as_mut) {
+            Some(task) => task,
+            None => return,
+        };
+
+        // Clear before polling, so a wake up during the poll schedules the task again.
+        task.waker.scheduled.store(false, Ordering::SeqCst);
+
+        let waker = waker_ref(&task.waker);
+        let mut cx = Context::from_waker(&waker);
+
+        if task.future.poll_unpin(&mut cx).is_ready() {
+            self.tasks[id] = None;
+            self.free.push(id);
+            self.len -= 1;
+        }
+    }
+}
+
+impl Future for Driver {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let this = &mut *self;
+
+        // Register before checking the queue, so we don't miss a push in between.
+        this.queue.waker.register(cx.waker());
+        let closed = this.queue.closed.load(Ordering::SeqCst);
+
+        let mut new = Vec::new();
+
+        {
+            let mut queues = this.queue.tasks.lock().expect("lock queue");
+
+            for priority in Priority::ALL.iter() {
+                new.extend(queues[priority.index()].drain(..).map(|f| (*priority, f)));
+            }
+        }
+
+        for (priority, future) in new {
+            this.insert(priority, future);
+        }
+
+        // Don't hog the thread of the executor when lots of tasks are ready.
+        let mut budget = 32;
+
+        loop {
+            let mut progress = false;
+
+            // Every round, each priority gets as many polls as its weight.
+            //
+            for priority in Priority::ALL.iter() {
+                for _ in 0..priority.weight() {
+                    let next = this.queue.ready.lock().expect("lock ready queue")[priority.index()]
+                        .pop_front();
+
+                    match next {
+                        Some(id) => {
+                            this.poll_task(id);
+                            progress = true;
+                        }
+
+                        None => break,
+                    }
+                }
+            }
+
+            if !progress {
+                break;
+            }
+
+            budget -= 1;
+
+            if budget == 0 {
+                cx.waker().wake_by_ref();
+                return Poll::Pending;
+            }
+        }
+
+        if closed && this.len == 0 {
+            return Poll::Ready(());
+        }
+
+        Poll::Pending
+    }
+}
+
+impl Drop for Driver {
+    fn drop(&mut self) {
+        self.queue.driver_gone.store(true, Ordering::SeqCst);
+    }
+}
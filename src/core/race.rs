@@ -0,0 +1,62 @@
+use crate::{JoinHandle, Timer};
+use futures_util::future::{select, select_all, Either};
+use std::{error::Error, fmt, future::Future, time::Duration};
+
+/// Wait for the first of `handles` to complete, giving up once `dur` has elapsed according
+/// to the timer of `exec`.
+///
+/// Resolves to the output of the first handle that completes together with its index in `handles`.
+/// All other tasks are cancelled, also the ones whose handle [detaches on drop](JoinHandle::detach_on_drop).
+/// If the deadline passes first, all tasks are cancelled and [`Elapsed`] is returned.
+///
+/// With no handles this resolves to [`Elapsed`] once `dur` has elapsed.
+//
+pub fn race_with_timeout<T, E>(
+    exec: &E,
+    handles: Vec<JoinHandle<T>>,
+    dur: Duration,
+) -> impl Future<Output = Result<(T, usize), Elapsed>> + 'static
+where
+    T: 'static,
+    E: Timer + ?Sized,
+{
+    let sleep = exec.sleep(dur);
+
+    async move {
+        if handles.is_empty() {
+            sleep.await;
+            return Err(Elapsed);
+        }
+
+        match select(select_all(handles), sleep).await {
+            Either::Left(((out, index, rest), _)) => {
+                rest.into_iter().for_each(|handle| {
+                    handle.abort();
+                });
+                Ok((out, index))
+            }
+
+            Either::Right(((), race)) => {
+                race.into_inner().into_iter().for_each(|handle| {
+                    handle.abort();
+                });
+                Err(Elapsed)
+            }
+        }
+    }
+}
+
+/// The error returned by [`race_with_timeout`] and [`join_timeout`](crate::JoinHandleExt::join_timeout)
+/// when the deadline passes before any task completes.
+//
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+//
+pub struct Elapsed;
+
+impl fmt::Display for Elapsed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("the deadline elapsed before any task completed")
+    }
+}
+
+impl Error for Elapsed {}
@@ -0,0 +1,239 @@
+use crate::{JoinHandle, Spawn, SpawnError, SpawnHandle, Timer};
+use futures_channel::oneshot;
+use futures_task::FutureObj;
+use futures_util::FutureExt;
+use std::{
+    collections::VecDeque,
+    future::Future,
+    sync::{Arc, Mutex, MutexGuard},
+    time::Duration,
+};
+
+/// Wraps an executor so that at most `rate` of the tasks spawned through it start running per
+/// `interval`, eg. to throttle outbound requests.
+///
+/// Spawning returns right away, but the spawned task waits for a permit before polling the future
+/// you passed in. The permits are refilled by a small task on the executor that uses its [`Timer`].
+/// Every refill starts a new interval, as long as there are tasks waiting. Clones share the same limit.
+///
+/// If the refill task can not be spawned, the tasks that are waiting never get a permit and the futures
+/// you passed in are never polled. Those tasks end right away. Awaiting the [`JoinHandle`] of such a task
+/// panics, like for a task that was dropped by its executor.
+//
+#[derive(Debug)]
+//
+#[cfg_attr(nightly, doc(cfg(feature = "channel")))]
+//
+pub struct RateLimitedSpawn<E> {
+    inner: Arc<Inner<E>>,
+}
+
+#[derive(Debug)]
+struct Inner<E> {
+    exec: E,
+    rate: usize,
+    interval: Duration,
+    state: Mutex<State>,
+}
+
+#[derive(Debug)]
+struct State {
+    tokens: usize,
+    refilling: bool,
+    waiting: VecDeque<oneshot::Sender<()>>,
+}
+
+impl<E> Clone for RateLimitedSpawn<E> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<E> RateLimitedSpawn<E>
+where
+    E: Spawn + Timer + Send + Sync + 'static,
+{
+    /// Let at most `rate` tasks spawned on `exec` start per `interval`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rate` is zero.
+    //
+    pub fn new(exec: E, rate: usize, interval: Duration) -> Self {
+        assert!(rate > 0, "RateLimitedSpawn: rate must be greater than zero");
+
+        let state = State {
+            tokens: rate,
+            refilling: false,
+            waiting: VecDeque::new(),
+        };
+
+        Self {
+            inner: Arc::new(Inner {
+                exec,
+                rate,
+                interval,
+                state: Mutex::new(state),
+            }),
+        }
+    }
+
+    /// The wrapped executor.
+    //
+    pub fn inner(&self) -> &E {
+        &self.inner.exec
+    }
+
+    fn state(&self) -> MutexGuard<'_, State> {
+        self.inner
+            .state
+            .lock()
+            .expect("lock RateLimitedSpawn state")
+    }
+
+    // Wrap the future so it waits for a permit first. Resolves to `None` without polling the future
+    // when no permit can be had.
+    //
+    fn limit<Fut: Future>(&self, future: Fut) -> impl Future<Output = Option<Fut::Output>> {
+        let this = self.clone();
+
+        async move {
+            if this.acquire().await {
+                Some(future.await)
+            } else {
+                None
+            }
+        }
+    }
+
+    // Returns whether a permit was obtained.
+    //
+    async fn acquire(&self) -> bool {
+        let rx = {
+            let mut state = self.state();
+
+            if state.tokens > 0 {
+                state.tokens -= 1;
+                None
+            } else {
+                let (tx, rx) = oneshot::channel();
+                state.waiting.push_back(tx);
+                Some(rx)
+            }
+        };
+
+        self.start_refill();
+
+        // The sender is only dropped without sending when the refill task could not be spawned or
+        // got dropped with the executor, in which case there is no permit coming.
+        //
+        match rx {
+            Some(rx) => rx.await.is_ok(),
+            None => true,
+        }
+    }
+
+    // Make sure a refill is coming after permits have been taken.
+    //
+    fn start_refill(&self) {
+        {
+            let mut state = self.state();
+
+            if state.refilling || state.tokens == self.inner.rate {
+                return;
+            }
+
+            state.refilling = true;
+        }
+
+        let this = self.clone();
+
+        let refill = async move {
+            loop {
+                this.inner.exec.sleep(this.inner.interval).await;
+
+                if !this.refill() {
+                    break;
+                }
+            }
+        };
+
+        // This is plumbing, not a user task, so don't call the spawn hook.
+        //
+        let refill = FutureObj::new(Box::new(refill));
+
+        if self.inner.exec.spawn_obj(refill).is_err() {
+            let mut state = self.state();
+
+            state.refilling = false;
+            state.waiting.clear();
+        }
+    }
+
+    // Refill the permits and hand them to waiting tasks. Returns whether any were taken, in which
+    // case a new interval starts and another refill is needed.
+    //
+    fn refill(&self) -> bool {
+        let mut state = self.state();
+
+        state.tokens = self.inner.rate;
+
+        while state.tokens > 0 {
+            match state.waiting.pop_front() {
+                // Tasks that were dropped while waiting don't use a permit.
+                //
+                Some(tx) => {
+                    if tx.send(()).is_ok() {
+                        state.tokens -= 1;
+                    }
+                }
+
+                None => break,
+            }
+        }
+
+        let more = state.tokens < self.inner.rate;
+
+        state.refilling = more;
+
+        more
+    }
+}
+
+impl<E> Spawn for RateLimitedSpawn<E>
+where
+    E: Spawn + Timer + Send + Sync + 'static,
+{
+    fn spawn_obj(&self, future: FutureObj<'static, ()>) -> Result<(), SpawnError> {
+        let task = self.limit(future).map(drop);
+
+        self.inner.exec.spawn_obj(FutureObj::new(Box::new(task)))
+    }
+
+    fn status(&self) -> Result<(), SpawnError> {
+        self.inner.exec.status()
+    }
+}
+
+// The task is spawned through Spawn and hands its output to a RemoteHandle, so when it never gets a
+// permit, dropping the remote end resolves the handle.
+//
+impl<E, Out> SpawnHandle<Out> for RateLimitedSpawn<E>
+where
+    E: Spawn + Timer + Send + Sync + 'static,
+    Out: Send + 'static,
+{
+    fn spawn_handle_obj(
+        &self,
+        future: FutureObj<'static, Out>,
+    ) -> Result<JoinHandle<Out>, SpawnError> {
+        let (remote, handle) = future.remote_handle();
+        let task = self.limit(remote).map(drop);
+
+        self.inner.exec.spawn_obj(FutureObj::new(Box::new(task)))?;
+
+        Ok(handle.into())
+    }
+}
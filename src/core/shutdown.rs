@@ -0,0 +1,113 @@
+use crate::{CancellationToken, Elapsed, JoinSet, SpawnError, SpawnHandle, Timer};
+use futures_util::{
+    future::{select, Either},
+    StreamExt,
+};
+use std::{future::Future, sync::Mutex, time::Duration};
+
+/// Coordinates the graceful shutdown of a group of tasks.
+///
+/// Tasks get spawned through [`spawn`](Shutdown::spawn), which passes them a [`CancellationToken`].
+/// To shut down, call [`initiate`](Shutdown::initiate) to cancel the token, then await
+/// [`wait`](Shutdown::wait), which gives the tasks a grace period to finish and aborts the ones that
+/// are still running after that.
+///
+/// ```rust
+/// # #[cfg(feature = "tokio_ct")]
+/// # {
+/// use async_executors::{Shutdown, TokioCtBuilder};
+/// use std::time::Duration;
+///
+/// let exec = TokioCtBuilder::new().build().expect("create tokio current thread");
+/// let shutdown = Shutdown::new();
+///
+/// shutdown
+///     .spawn(&exec, |token| async move {
+///         // Serve requests until asked to stop.
+///         token.cancelled().await;
+///     })
+///     .expect("spawn");
+///
+/// exec.block_on(async {
+///     shutdown.initiate();
+///     shutdown.wait(&exec, Duration::from_secs(5)).await.expect("tasks finish in time");
+/// });
+/// # }
+/// ```
+//
+#[derive(Debug, Default)]
+//
+pub struct Shutdown {
+    token: CancellationToken,
+    tasks: Mutex<JoinSet<()>>,
+}
+
+impl Shutdown {
+    /// Create a new coordinator without any tasks.
+    //
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The token passed to the tasks. Cancelled by [`initiate`](Shutdown::initiate).
+    //
+    pub fn token(&self) -> &CancellationToken {
+        &self.token
+    }
+
+    /// Spawn the future returned by `f` on `exec`. `f` gets a clone of the token, which the task should
+    /// watch to know when to stop.
+    ///
+    /// Returns [`SpawnError::shutdown`] when the shutdown has already been initiated.
+    //
+    pub fn spawn<E, F, Fut>(&self, exec: &E, f: F) -> Result<(), SpawnError>
+    where
+        E: SpawnHandle<()> + ?Sized,
+        F: FnOnce(CancellationToken) -> Fut,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        if self.token.is_cancelled() {
+            return Err(SpawnError::shutdown());
+        }
+
+        self.tasks
+            .lock()
+            .expect("lock tasks")
+            .spawn(exec, f(self.token.clone()))
+    }
+
+    /// Signal all tasks to stop by cancelling the token.
+    //
+    pub fn initiate(&self) {
+        self.token.cancel();
+    }
+
+    /// Wait for all tasks spawned so far to complete, for up to `grace` according to the timer of
+    /// `timer`. The tasks that are still running after that get aborted and [`Elapsed`] is returned.
+    ///
+    /// This does not call [`initiate`](Shutdown::initiate), so tasks that wait on the token will only
+    /// stop once you do.
+    //
+    pub async fn wait<E>(&self, timer: &E, grace: Duration) -> Result<(), Elapsed>
+    where
+        E: Timer + ?Sized,
+    {
+        let mut tasks = std::mem::take(&mut *self.tasks.lock().expect("lock tasks"));
+        let mut sleep = timer.sleep(grace);
+
+        let timed_out = loop {
+            match select(tasks.next(), &mut sleep).await {
+                Either::Left((Some(()), _)) => continue,
+                Either::Left((None, _)) => break false,
+                Either::Right(_) => break true,
+            }
+        };
+
+        if timed_out {
+            tasks.abort_all();
+            return Err(Elapsed);
+        }
+
+        Ok(())
+    }
+}
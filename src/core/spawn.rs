@@ -1,9 +1,13 @@
-use crate::StaticRuntime;
+use crate::{
+    core::spawn_hook::on_spawn, FullExecutor, MapSpawn, OnTaskPanic, SpawnRetry, StaticRuntime,
+    Timer, WithHandles,
+};
 use futures_task::{FutureObj, LocalFutureObj};
-use futures_util::future::RemoteHandle;
+use futures_util::future::{BoxFuture, RemoteHandle};
 use futures_util::FutureExt;
 use std::future::Future;
 use std::sync::Arc;
+use std::time::Duration;
 use std::{boxed::Box, rc::Rc};
 
 /// The `Spawn` trait allows for pushing futures onto an executor that will
@@ -57,46 +61,154 @@ pub trait LocalSpawn {
 }
 
 /// An error that occurred during spawning.
-#[derive(Copy, Clone)]
+///
+/// Use [`kind`](SpawnError::kind) to find out why spawning failed. Optionally carries the underlying
+/// error that caused the spawn to fail, which is available through [`std::error::Error::source`]. When
+/// there is no source, no allocation takes place.
+///
+/// Since it can hold a source error, `SpawnError` is `Clone` but no longer `Copy`. Use `clone` or
+/// [`kind`](SpawnError::kind), which is `Copy`, where you used to copy the error.
+#[derive(Clone)]
 pub struct SpawnError {
-    _priv: (),
+    kind: SpawnErrorKind,
+    source: Option<Arc<dyn std::error::Error + Send + Sync + 'static>>,
 }
 impl SpawnError {
+    /// Create a new `SpawnError` without a source, see [`SpawnError::shutdown`].
     pub fn new() -> Self {
-        Self { _priv: () }
+        Self::shutdown()
+    }
+
+    /// Create a new `SpawnError` caused by an underlying (runtime) error. The error
+    /// will be returned by [`std::error::Error::source`]. The kind is [`SpawnErrorKind::Other`],
+    /// use [`with_kind`](SpawnError::with_kind) when you know why spawning failed.
+    pub fn with_source(
+        source: impl Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+    ) -> Self {
+        Self::with_kind(SpawnErrorKind::Other, source)
+    }
+
+    /// Create a new `SpawnError` of the given kind, caused by an underlying (runtime) error.
+    pub fn with_kind(
+        kind: SpawnErrorKind,
+        source: impl Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+    ) -> Self {
+        Self {
+            kind,
+            source: Some(Arc::from(source.into())),
+        }
+    }
+
+    /// Why spawning failed.
+    pub fn kind(&self) -> SpawnErrorKind {
+        self.kind
     }
 }
-impl From<futures_util::task::SpawnError> for SpawnError {
-    fn from(_: futures_util::task::SpawnError) -> Self {
+impl Default for SpawnError {
+    fn default() -> Self {
         Self::new()
     }
 }
+impl From<futures_util::task::SpawnError> for SpawnError {
+    fn from(err: futures_util::task::SpawnError) -> Self {
+        // The executors from the futures library only fail when they are shut down.
+        Self::with_kind(SpawnErrorKind::Shutdown, err)
+    }
+}
+impl From<SpawnErrorKind> for SpawnError {
+    fn from(kind: SpawnErrorKind) -> Self {
+        Self { kind, source: None }
+    }
+}
 impl std::fmt::Debug for SpawnError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_tuple("SpawnError").field(&"shutdown").finish()
+        let mut t = f.debug_tuple("SpawnError");
+        t.field(&self.kind);
+
+        if let Some(source) = &self.source {
+            t.field(source);
+        }
+
+        t.finish()
     }
 }
 
 impl std::fmt::Display for SpawnError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Executor is shutdown")
+        std::fmt::Display::fmt(&self.kind, f)
     }
 }
 
-impl std::error::Error for SpawnError {}
+impl std::error::Error for SpawnError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(self.source.as_deref()?)
+    }
+}
 
 impl SpawnError {
     /// Spawning failed because the executor has been shut down.
     pub fn shutdown() -> Self {
-        Self { _priv: () }
+        SpawnErrorKind::Shutdown.into()
+    }
+
+    /// Spawning failed because the executor can not spawn this task in the current context,
+    /// eg. a local task outside of the thread that runs the executor.
+    pub fn unsupported() -> Self {
+        SpawnErrorKind::Unsupported.into()
+    }
+
+    /// Spawning failed because the executor does not accept more tasks right now.
+    /// Spawning again later might succeed.
+    pub fn at_capacity() -> Self {
+        SpawnErrorKind::AtCapacity.into()
     }
 
     /// Check whether spawning failed to the executor being shut down.
     pub fn is_shutdown(&self) -> bool {
-        true
+        self.kind == SpawnErrorKind::Shutdown
     }
 }
 
+/// Why spawning failed, see [`SpawnError::kind`].
+///
+/// This can be matched on to eg. retry later when an executor is at capacity, or to map the
+/// cause to the error type of a library. New kinds might be added in the future.
+//
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+//
+#[non_exhaustive]
+//
+pub enum SpawnErrorKind {
+    /// The executor has been shut down and no longer accepts tasks.
+    Shutdown,
+    /// The executor can not spawn the task in the current context, eg. a local task from outside
+    /// the thread that runs the executor, or before the executor has been started.
+    Unsupported,
+    /// The executor does not accept more tasks right now.
+    AtCapacity,
+    /// Another error from the runtime, see [`std::error::Error::source`] on the [`SpawnError`].
+    Other,
+}
+
+impl From<&SpawnError> for SpawnErrorKind {
+    fn from(err: &SpawnError) -> Self {
+        err.kind
+    }
+}
+
+impl std::fmt::Display for SpawnErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Shutdown => write!(f, "Executor is shutdown"),
+            Self::Unsupported => write!(f, "Executor can not spawn the task in this context"),
+            Self::AtCapacity => write!(f, "Executor is at capacity"),
+            Self::Other => write!(f, "Executor failed to spawn the task"),
+        }
+    }
+}
+
+impl std::error::Error for SpawnErrorKind {}
+
 impl<Sp: ?Sized + Spawn> Spawn for &Sp {
     fn spawn_obj(&self, future: FutureObj<'static, ()>) -> Result<(), SpawnError> {
         Sp::spawn_obj(self, future)
@@ -230,7 +342,9 @@ pub trait SpawnExt: Spawn {
     where
         Fut: Future<Output = ()> + Send + 'static,
     {
-        self.spawn_obj(FutureObj::new(Box::new(future)))
+        self.spawn_obj(FutureObj::new(Box::new(future)))?;
+        on_spawn::<Self>(None);
+        Ok(())
     }
 
     /// Spawns a task that polls the given future to completion and returns a
@@ -260,6 +374,125 @@ pub trait SpawnExt: Spawn {
         self.spawn(future)?;
         Ok(handle)
     }
+
+    /// Spawn a future, waiting for `backoff` between attempts while [`status`](Spawn::status)
+    /// reports that the executor is [at capacity](SpawnErrorKind::AtCapacity). Gives up after
+    /// `max_attempts` and returns the last error.
+    ///
+    /// Other errors, like [`Shutdown`](SpawnErrorKind::Shutdown), are returned right away, as
+    /// trying again would not help. When `status` is `Ok` but spawning still fails, that error is
+    /// returned right away as well, as the future has been consumed by the attempt.
+    fn spawn_with_retry<Fut>(
+        &self,
+        future: Fut,
+        max_attempts: usize,
+        backoff: Duration,
+    ) -> SpawnRetry<'_, Self, Fut>
+    where
+        Self: Timer,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        SpawnRetry::new(self, future, max_attempts, backoff)
+    }
+
+    /// Wrap the executor so it implements [`SpawnHandle`](crate::SpawnHandle), using a
+    /// [`RemoteHandle`] for the join handles. See [`WithHandles`].
+    fn with_handles(self) -> WithHandles<Self>
+    where
+        Self: Sized,
+    {
+        WithHandles::new(self)
+    }
+
+    /// Wrap the executor so every spawned future goes through `map` first. See [`MapSpawn`].
+    ///
+    /// ```
+    /// use async_executors::{Spawn, SpawnExt};
+    /// use futures::FutureExt;
+    ///
+    /// fn with_logging(exec: impl Spawn) -> impl Spawn {
+    ///     exec.map_spawn(|fut| {
+    ///         async move {
+    ///             println!("task started");
+    ///             fut.await;
+    ///             println!("task done");
+    ///         }
+    ///         .boxed()
+    ///     })
+    /// }
+    /// ```
+    fn map_spawn<F>(self, map: F) -> MapSpawn<Self, F>
+    where
+        Self: Sized,
+        F: Fn(BoxFuture<'static, ()>) -> BoxFuture<'static, ()>,
+    {
+        MapSpawn::new(self, map)
+    }
+
+    /// Wrap the executor so `callback` gets called when a task spawned through it panics.
+    /// See [`OnTaskPanic`].
+    fn on_task_panic<F>(self, callback: F) -> OnTaskPanic<Self, F>
+    where
+        Self: Sized,
+        F: Fn(&(dyn std::any::Any + Send)) + Send + Sync + 'static,
+    {
+        OnTaskPanic::new(self, callback)
+    }
+
+    /// Spawn a fire and forget task that gets logged through `tracing` with `name` when it panics, so
+    /// failing background tasks don't go unnoticed. The panic is logged at the error level and then
+    /// swallowed, like [`OnTaskPanic`] does for tasks without a handle, with the same caveats.
+    ///
+    /// Use [`spawn_logged_result`](SpawnExt::spawn_logged_result) for a future that can fail.
+    #[cfg(feature = "tracing")]
+    #[cfg_attr(nightly, doc(cfg(feature = "tracing")))]
+    fn spawn_logged<Fut>(&self, name: &str, future: Fut) -> Result<(), SpawnError>
+    where
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.spawn_logged_result(name, future.map(Ok::<(), std::convert::Infallible>))
+    }
+
+    /// Like [`spawn_logged`](SpawnExt::spawn_logged), but for a future that resolves to a `Result`.
+    /// An `Err` is logged at the error level as well, with its `Display` output. The `Ok` value is dropped.
+    #[cfg(feature = "tracing")]
+    #[cfg_attr(nightly, doc(cfg(feature = "tracing")))]
+    fn spawn_logged_result<Fut, T, E>(&self, name: &str, future: Fut) -> Result<(), SpawnError>
+    where
+        Fut: Future<Output = Result<T, E>> + Send + 'static,
+        E: std::fmt::Display,
+    {
+        let name = name.to_string();
+
+        let task = async move {
+            match std::panic::AssertUnwindSafe(future).catch_unwind().await {
+                Ok(Ok(_)) => {}
+
+                Ok(Err(e)) => tracing_crate::error!(task = %name, error = %e, "task failed"),
+
+                Err(payload) => {
+                    let msg = payload
+                        .downcast_ref::<&str>()
+                        .copied()
+                        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+                        .unwrap_or("Box<dyn Any>");
+
+                    tracing_crate::error!(task = %name, panic = %msg, "task panicked")
+                }
+            }
+        };
+
+        self.spawn(task)
+    }
+
+    /// Combine the executor with `blocking`, which gets used for [`SpawnBlocking`](crate::SpawnBlocking),
+    /// so a single value provides both. See [`FullExecutor`].
+    fn with_blocking<B>(self, blocking: B) -> FullExecutor<Self, B>
+    where
+        Self: Sized,
+    {
+        FullExecutor::new(self, blocking)
+    }
 }
 
 /// Extension trait for `LocalSpawn`.
@@ -293,7 +526,9 @@ pub trait LocalSpawnExt: LocalSpawn {
     where
         Fut: Future<Output = ()> + 'static,
     {
-        self.spawn_local_obj(LocalFutureObj::new(Box::new(future)))
+        self.spawn_local_obj(LocalFutureObj::new(Box::new(future)))?;
+        on_spawn::<Self>(None);
+        Ok(())
     }
 
     /// Spawns a task that polls the given future to completion and returns a
@@ -325,6 +560,19 @@ pub trait LocalSpawnExt: LocalSpawn {
         self.spawn_local(future)?;
         Ok(handle)
     }
+
+    /// Spawn a `Send` future on a local executor, for generic code that only has an `impl LocalSpawn`.
+    ///
+    /// The local executors of this crate, like `TokioCt` and `GlommioCt`, also implement [`Spawn`], which
+    /// spawns on the same thread as `LocalSpawn` does. So when you have the concrete type, or can add the
+    /// bound, `spawn` works as well. This is the same as [`spawn_local`](LocalSpawnExt::spawn_local), the `Send`
+    /// bound just documents that the future could also have gone to [`Spawn`].
+    fn spawn_send<Fut>(&self, future: Fut) -> Result<(), SpawnError>
+    where
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.spawn_local(future)
+    }
 }
 
 /// The `SpawnStatic` trait allows for pushing futures onto an executor that will
@@ -346,3 +594,49 @@ pub trait LocalSpawnStatic: StaticRuntime {
         Fut: Future<Output = Output> + 'static,
         Output: 'static;
 }
+
+#[cfg(test)]
+//
+mod tests {
+    use super::*;
+    use std::error::Error;
+
+    #[test]
+    fn spawn_error_source() {
+        assert!(SpawnError::shutdown().source().is_none());
+
+        let err = SpawnError::with_source(std::io::Error::from(std::io::ErrorKind::Other));
+        let source = err.source().expect("source");
+
+        assert!(source.downcast_ref::<std::io::Error>().is_some());
+        assert!(err.clone().source().is_some());
+    }
+
+    #[test]
+    fn spawn_error_kind() {
+        assert_eq!(SpawnErrorKind::Shutdown, SpawnError::new().kind());
+        assert_eq!(
+            SpawnErrorKind::Unsupported,
+            SpawnError::unsupported().kind()
+        );
+        assert_eq!(SpawnErrorKind::AtCapacity, SpawnError::at_capacity().kind());
+        assert_eq!(
+            SpawnErrorKind::Other,
+            SpawnError::with_source("oops").kind()
+        );
+
+        let err = SpawnError::from(SpawnErrorKind::AtCapacity);
+
+        assert_eq!(SpawnErrorKind::AtCapacity, SpawnErrorKind::from(&err));
+        assert!(!err.is_shutdown());
+        assert!(err.source().is_none());
+
+        let futures_err = futures_util::task::SpawnError::shutdown();
+
+        assert!(SpawnError::from(futures_err).is_shutdown());
+    }
+
+    static_assertions::assert_impl_all!(SpawnError: Send, Sync, Clone);
+    static_assertions::assert_not_impl_any!(SpawnError: Copy);
+    static_assertions::assert_impl_all!(SpawnErrorKind: Send, Sync, Copy, Error);
+}
@@ -1,4 +1,13 @@
-use crate::{JoinHandle, SpawnError, StaticRuntime};
+use crate::{AsyncJoinHandle, JoinHandle, SpawnBlockingStream, SpawnError, StaticRuntime};
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+};
 
 /// Spawn a blocking task, maybe in a thread pool(tokio), or in current thread and spawns a new thread(std-async)
 pub trait SpawnBlocking<T: Send + 'static> {
@@ -18,6 +27,125 @@ pub trait SpawnBlockingExt<T: Send + 'static>: SpawnBlocking<T> {
     ) -> Result<JoinHandle<T>, SpawnError> {
         self.spawn_blocking_obj(Box::new(func))
     }
+
+    /// Spawn a blocking function and catch a panic in it, so the handle resolves to an `Err` with the
+    /// panic payload instead of propagating the panic when awaited.
+    ///
+    /// The closure is wrapped in [`std::panic::AssertUnwindSafe`], so like for futures spawned on the
+    /// executors, make sure nothing observes inconsistent state after a panic.
+    //
+    fn spawn_blocking_catch_unwind(
+        &self,
+        func: impl FnOnce() -> T + Send + 'static,
+    ) -> Result<JoinHandle<std::thread::Result<T>>, SpawnError>
+    where
+        Self: SpawnBlocking<std::thread::Result<T>>,
+    {
+        let func = move || std::panic::catch_unwind(std::panic::AssertUnwindSafe(func));
+
+        SpawnBlocking::<std::thread::Result<T>>::spawn_blocking_obj(self, Box::new(func))
+    }
+
+    /// Spawn a blocking function that can be cancelled cooperatively. Blocking code can not be
+    /// interrupted, so instead the closure receives a flag that gets set when the returned handle
+    /// is dropped or [`cancel`](CancellableJoinHandle::cancel) is called. The closure should check it
+    /// at points where it can safely stop.
+    ///
+    /// If the closure hasn't started yet when the handle is dropped, some executors will not run it at all.
+    //
+    fn spawn_blocking_cancellable(
+        &self,
+        func: impl FnOnce(&AtomicBool) -> T + Send + 'static,
+    ) -> Result<CancellableJoinHandle<T>, SpawnError> {
+        let flag = Arc::new(AtomicBool::new(false));
+        let flag2 = flag.clone();
+
+        let handle = self.spawn_blocking_obj(Box::new(move || func(&flag2)))?;
+
+        Ok(CancellableJoinHandle {
+            handle: Some(handle),
+            flag,
+        })
+    }
+
+    /// Run the blocking jobs from `jobs` with at most `max_concurrent` of them in flight, and get their
+    /// results as a stream in the order they complete, eg. to process a batch of CPU bound work without
+    /// flooding the blocking pool. When spawning a job fails, the stream yields the error and stops
+    /// taking jobs. See [`SpawnBlockingStream`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_concurrent` is zero.
+    //
+    fn spawn_blocking_stream<I>(
+        &self,
+        jobs: I,
+        max_concurrent: usize,
+    ) -> SpawnBlockingStream<'_, Self, I::IntoIter, T>
+    where
+        I: IntoIterator,
+        I::Item: FnOnce() -> T + Send + 'static,
+    {
+        SpawnBlockingStream::new(self, jobs.into_iter(), max_concurrent)
+    }
+}
+
+impl<T, S> SpawnBlockingExt<T> for S
+where
+    T: Send + 'static,
+    S: SpawnBlocking<T> + ?Sized,
+{
+}
+
+/// The handle returned by [`SpawnBlockingExt::spawn_blocking_cancellable`]. Dropping it sets the
+/// cancellation flag passed to the closure. Call [`detach`](AsyncJoinHandle::detach) to let the
+/// closure run without cancelling it.
+//
+#[must_use = "CancellableJoinHandle will cancel your closure when dropped."]
+#[derive(Debug)]
+pub struct CancellableJoinHandle<T> {
+    handle: Option<JoinHandle<T>>,
+    flag: Arc<AtomicBool>,
+}
+
+impl<T> CancellableJoinHandle<T> {
+    /// Ask the closure to stop without dropping the handle, so you can still await its output.
+    //
+    pub fn cancel(&self) {
+        self.flag.store(true, Ordering::SeqCst);
+    }
+}
+
+impl<T> Unpin for CancellableJoinHandle<T> {}
+
+impl<T: 'static> Future for CancellableJoinHandle<T> {
+    type Output = T;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match self.handle.as_mut() {
+            Some(handle) => Pin::new(handle).poll(cx),
+            None => unreachable!("CancellableJoinHandle polled after detach"),
+        }
+    }
+}
+
+impl<T: 'static> AsyncJoinHandle for CancellableJoinHandle<T> {
+    fn detach(mut self)
+    where
+        Self: Sized,
+    {
+        if let Some(handle) = self.handle.take() {
+            handle.detach();
+        }
+    }
+}
+
+impl<T> Drop for CancellableJoinHandle<T> {
+    fn drop(&mut self) {
+        if self.handle.is_some() {
+            self.cancel();
+        }
+    }
 }
 
 /// Spawn a blocking task, maybe in a thread pool(tokio), or in current thread and spawns a new thread(std-async)
@@ -27,3 +155,11 @@ pub trait SpawnBlockingStatic: StaticRuntime {
         func: impl FnOnce() -> T + Send + 'static,
     ) -> Result<JoinHandle<T>, SpawnError>;
 }
+
+#[cfg(test)]
+//
+mod tests {
+    use super::*;
+
+    static_assertions::assert_impl_all!(CancellableJoinHandle<()>: Send, Unpin);
+}
@@ -1,10 +1,15 @@
-use crate::{SpawnError, StaticRuntime};
+use crate::{
+    core::spawn_hook::on_spawn, AbortableJoinHandle, CancellationToken, RestartPolicy, SpawnError,
+    SpawnStream, StaticRuntime, TaskAbortHandle, TaskFailure, Timer,
+};
+#[cfg(feature = "channel")]
+use futures_channel::{mpsc, oneshot};
 #[allow(unused_imports)]
 use {
     crate::JoinHandle,
     futures_task::FutureObj,
     futures_util::{
-        future::{abortable, FutureExt},
+        future::{abortable, poll_fn, select, Either, FutureExt},
         task::SpawnExt,
     },
     std::{
@@ -12,6 +17,7 @@ use {
         pin::Pin,
         rc::Rc,
         sync::{atomic::AtomicBool, Arc},
+        time::Duration,
     },
 };
 
@@ -64,6 +70,20 @@ pub trait SpawnHandle<Out: 'static + Send> {
         &self,
         future: FutureObj<'static, Out>,
     ) -> Result<JoinHandle<Out>, SpawnError>;
+
+    /// Like [`spawn_handle_obj`](SpawnHandle::spawn_handle_obj), but give the task a name, eg. for `tokio-console`.
+    ///
+    /// The tokio executors pass the name to [`tokio::task::Builder`](https://docs.rs/tokio/latest/tokio/task/struct.Builder.html)
+    /// when the `tokio_tracing` feature is enabled and you compile with `RUSTFLAGS="--cfg tokio_unstable"`.
+    /// Otherwise the name is ignored, so the same code works on all executors.
+    //
+    fn spawn_handle_obj_named(
+        &self,
+        _name: &str,
+        future: FutureObj<'static, Out>,
+    ) -> Result<JoinHandle<Out>, SpawnError> {
+        self.spawn_handle_obj(future)
+    }
 }
 
 /// Convenience trait for passing in a generic future to [`SpawnHandle`]. Much akin to `Spawn` and `SpawnExt` in the
@@ -76,6 +96,159 @@ pub trait SpawnHandleExt<Out: 'static + Send>: SpawnHandle<Out> {
         &self,
         future: impl Future<Output = Out> + Send + 'static,
     ) -> Result<JoinHandle<Out>, SpawnError>;
+
+    /// Like [`spawn_handle`](SpawnHandleExt::spawn_handle), but give the task a name.
+    /// See [`SpawnHandle::spawn_handle_obj_named`].
+    //
+    fn spawn_handle_named(
+        &self,
+        name: &str,
+        future: impl Future<Output = Out> + Send + 'static,
+    ) -> Result<JoinHandle<Out>, SpawnError>;
+
+    /// Spawn the future returned by `f`, which gets a clone of the executor, eg. for a task that spawns
+    /// child tasks of its own. This saves cloning the executor and moving the clone into the future by hand.
+    ///
+    /// The clone is the usual way to give a task access to the executor. For the executors of this crate,
+    /// clones share the same runtime, so the children run on the same executor as their parent.
+    //
+    fn spawn_handle_with_exec<F, Fut>(&self, f: F) -> Result<JoinHandle<Out>, SpawnError>
+    where
+        Self: Clone + Sized,
+        F: FnOnce(Self) -> Fut,
+        Fut: Future<Output = Out> + Send + 'static;
+
+    /// Like [`spawn_handle`](SpawnHandleExt::spawn_handle), but the task keeps running when the
+    /// returned handle is dropped instead of being cancelled. You can still await the handle for the output.
+    //
+    fn spawn_handle_detached_on_drop(
+        &self,
+        future: impl Future<Output = Out> + Send + 'static,
+    ) -> Result<JoinHandle<Out>, SpawnError>;
+
+    /// Spawn a future and get both a [`JoinHandle`] for the output and a [`TaskAbortHandle`] that
+    /// can cancel the task from elsewhere, eg. a cancellation registry. Aborting makes awaiting the
+    /// join handle panic.
+    //
+    fn spawn_handle_with_abort(
+        &self,
+        future: impl Future<Output = Out> + Send + 'static,
+    ) -> Result<(JoinHandle<Out>, TaskAbortHandle), SpawnError>;
+
+    /// Spawn a future and get a receiver that resolves when the task gets polled for the first time,
+    /// eg. to wait until workers have actually started before going on with the setup of a pipeline.
+    /// The signal fires before the future itself gets polled, so it always resolves before the output.
+    ///
+    /// If the task is dropped before it ever ran, eg. because the executor shut down, the receiver
+    /// resolves to [`Canceled`](oneshot::Canceled).
+    //
+    #[cfg(feature = "channel")]
+    #[cfg_attr(nightly, doc(cfg(feature = "channel")))]
+    //
+    fn spawn_handle_with_ready(
+        &self,
+        future: impl Future<Output = Out> + Send + 'static,
+    ) -> Result<(JoinHandle<Out>, oneshot::Receiver<()>), SpawnError>;
+
+    /// Spawn a future and send its output into `tx` when it completes, eg. to collect the outputs of
+    /// many tasks in one place. If the receiver has been dropped by then, the output is dropped quietly.
+    ///
+    /// The returned [`JoinHandle`] resolves once the output has been sent. Like for
+    /// [`spawn_handle`](SpawnHandleExt::spawn_handle), dropping it cancels the task.
+    //
+    #[cfg(feature = "channel")]
+    #[cfg_attr(nightly, doc(cfg(feature = "channel")))]
+    //
+    fn spawn_into_sender(
+        &self,
+        future: impl Future<Output = Out> + Send + 'static,
+        tx: mpsc::Sender<Out>,
+    ) -> Result<JoinHandle<()>, SpawnError>
+    where
+        Self: SpawnHandle<()>;
+
+    /// Spawn a future that gets cancelled when it hasn't completed within `ttl`, as measured by `exec`.
+    /// The returned [`JoinHandle`] resolves to `Some` with the output, or to `None` when the time ran out.
+    ///
+    /// The deadline is enforced inside the spawned task, so it also applies when nobody awaits the handle,
+    /// eg. after calling [`detach`](JoinHandle::detach) on it. Like for [`spawn_handle`](SpawnHandleExt::spawn_handle),
+    /// dropping the handle cancels the task.
+    //
+    fn spawn_handle_ttl<E: Timer + ?Sized>(
+        &self,
+        future: impl Future<Output = Out> + Send + 'static,
+        ttl: Duration,
+        exec: &E,
+    ) -> Result<JoinHandle<Option<Out>>, SpawnError>
+    where
+        Self: SpawnHandle<Option<Out>>;
+
+    /// Spawn a future that gets cancelled when `token` is cancelled. The returned [`JoinHandle`] resolves
+    /// to `Some` with the output, or to `None` when the token was cancelled first.
+    ///
+    /// Unlike [`abort`](JoinHandle::abort), this drops the future inside the task, so cleanup in its `Drop`
+    /// runs on the executor. Cancellation is enforced inside the spawned task, so it also applies when nobody
+    /// awaits the handle. Like for [`spawn_handle`](SpawnHandleExt::spawn_handle), dropping the handle cancels
+    /// the task.
+    //
+    fn spawn_until(
+        &self,
+        future: impl Future<Output = Out> + Send + 'static,
+        token: CancellationToken,
+    ) -> Result<JoinHandle<Option<Out>>, SpawnError>
+    where
+        Self: SpawnHandle<Option<Out>>;
+
+    /// Spawn a supervised task, eg. for a background service that should survive failures. The future
+    /// created by `make_fut` gets created and run again when it resolves to an error or panics, as long
+    /// as `policy` allows it, waiting for the backoff of the policy on `timer` in between.
+    ///
+    /// The returned [`JoinHandle`] resolves to the output once a run succeeds, or to the failure of the
+    /// last run when the policy gives up. The restarts happen inside the spawned task, so they don't depend
+    /// on the handle being awaited. Like for [`spawn_handle`](SpawnHandleExt::spawn_handle), dropping the
+    /// handle cancels the task. Panics are caught with the same caveats as [`OnTaskPanic`](crate::OnTaskPanic).
+    //
+    fn spawn_supervised<F, Fut, Er, Tm>(
+        &self,
+        make_fut: F,
+        policy: RestartPolicy,
+        timer: Tm,
+    ) -> Result<JoinHandle<Result<Out, TaskFailure<Er>>>, SpawnError>
+    where
+        F: FnMut() -> Fut + Send + 'static,
+        Fut: Future<Output = Result<Out, Er>> + Send + 'static,
+        Er: Send + 'static,
+        Tm: Timer + Send + 'static,
+        Self: SpawnHandle<Result<Out, TaskFailure<Er>>>;
+
+    /// Spawn all futures from `iter` and get their handles in the same order, eg. to await them with
+    /// [`join_all`](futures_util::future::join_all) and get the outputs aligned with the inputs.
+    ///
+    /// # Errors
+    ///
+    /// When one of the futures fails to spawn, the tasks that were already spawned are aborted, also
+    /// when the handles detach on drop, and the error is returned. The remaining futures are dropped.
+    //
+    fn spawn_all<I>(&self, iter: I) -> Result<Vec<JoinHandle<Out>>, SpawnError>
+    where
+        I: IntoIterator,
+        I::Item: Future<Output = Out> + Send + 'static;
+
+    /// Spawn the futures from `iter` with at most `max_concurrent` of them running at the same time,
+    /// and get their outputs as a stream in the order they complete. See [`SpawnStream`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_concurrent` is zero.
+    //
+    fn spawn_stream<I>(
+        &self,
+        iter: I,
+        max_concurrent: usize,
+    ) -> SpawnStream<'_, Self, I::IntoIter, Out>
+    where
+        I: IntoIterator,
+        I::Item: Future<Output = Out> + Send + 'static;
 }
 
 impl<T, Out> SpawnHandleExt<Out> for T
@@ -87,7 +260,180 @@ where
         &self,
         future: impl Future<Output = Out> + Send + 'static,
     ) -> Result<JoinHandle<Out>, SpawnError> {
-        self.spawn_handle_obj(FutureObj::new(future.boxed()))
+        let handle = self.spawn_handle_obj(FutureObj::new(future.boxed()))?;
+        on_spawn::<Self>(None);
+        Ok(handle)
+    }
+
+    fn spawn_handle_named(
+        &self,
+        name: &str,
+        future: impl Future<Output = Out> + Send + 'static,
+    ) -> Result<JoinHandle<Out>, SpawnError> {
+        let handle = self.spawn_handle_obj_named(name, FutureObj::new(future.boxed()))?;
+        on_spawn::<Self>(Some(name));
+        Ok(handle)
+    }
+
+    fn spawn_handle_with_exec<F, Fut>(&self, f: F) -> Result<JoinHandle<Out>, SpawnError>
+    where
+        Self: Clone + Sized,
+        F: FnOnce(Self) -> Fut,
+        Fut: Future<Output = Out> + Send + 'static,
+    {
+        self.spawn_handle(f(self.clone()))
+    }
+
+    fn spawn_handle_detached_on_drop(
+        &self,
+        future: impl Future<Output = Out> + Send + 'static,
+    ) -> Result<JoinHandle<Out>, SpawnError> {
+        Ok(self.spawn_handle(future)?.detach_on_drop())
+    }
+
+    fn spawn_handle_with_abort(
+        &self,
+        future: impl Future<Output = Out> + Send + 'static,
+    ) -> Result<(JoinHandle<Out>, TaskAbortHandle), SpawnError> {
+        let (handle, abort) = AbortableJoinHandle::new(self.spawn_handle(future)?);
+
+        Ok((handle.into(), abort))
+    }
+
+    #[cfg(feature = "channel")]
+    //
+    fn spawn_handle_with_ready(
+        &self,
+        future: impl Future<Output = Out> + Send + 'static,
+    ) -> Result<(JoinHandle<Out>, oneshot::Receiver<()>), SpawnError> {
+        let (tx, rx) = oneshot::channel();
+
+        let task = async move {
+            // Fails when nobody is waiting for the signal, which is fine.
+            //
+            let _ = tx.send(());
+            future.await
+        };
+
+        Ok((self.spawn_handle(task)?, rx))
+    }
+
+    #[cfg(feature = "channel")]
+    //
+    fn spawn_into_sender(
+        &self,
+        future: impl Future<Output = Out> + Send + 'static,
+        mut tx: mpsc::Sender<Out>,
+    ) -> Result<JoinHandle<()>, SpawnError>
+    where
+        Self: SpawnHandle<()>,
+    {
+        let task = async move {
+            let out = future.await;
+
+            // Both only fail when the receiver is gone.
+            //
+            if poll_fn(|cx| tx.poll_ready(cx)).await.is_ok() {
+                let _ = tx.start_send(out);
+            }
+        };
+
+        SpawnHandleExt::<()>::spawn_handle(self, task)
+    }
+
+    fn spawn_handle_ttl<E: Timer + ?Sized>(
+        &self,
+        future: impl Future<Output = Out> + Send + 'static,
+        ttl: Duration,
+        exec: &E,
+    ) -> Result<JoinHandle<Option<Out>>, SpawnError>
+    where
+        Self: SpawnHandle<Option<Out>>,
+    {
+        let sleep = exec.sleep(ttl);
+
+        // When the timer wins, the future is dropped together with the select.
+        //
+        let task = select(future.boxed(), sleep).map(|either| match either {
+            Either::Left((out, _)) => Some(out),
+            Either::Right(_) => None,
+        });
+
+        SpawnHandleExt::<Option<Out>>::spawn_handle(self, task)
+    }
+
+    fn spawn_until(
+        &self,
+        future: impl Future<Output = Out> + Send + 'static,
+        token: CancellationToken,
+    ) -> Result<JoinHandle<Option<Out>>, SpawnError>
+    where
+        Self: SpawnHandle<Option<Out>>,
+    {
+        // When the token wins, the future is dropped together with the select.
+        //
+        let task = async move {
+            match select(future.boxed(), token.cancelled()).await {
+                Either::Left((out, _)) => Some(out),
+                Either::Right(_) => None,
+            }
+        };
+
+        SpawnHandleExt::<Option<Out>>::spawn_handle(self, task)
+    }
+
+    fn spawn_supervised<F, Fut, Er, Tm>(
+        &self,
+        make_fut: F,
+        policy: RestartPolicy,
+        timer: Tm,
+    ) -> Result<JoinHandle<Result<Out, TaskFailure<Er>>>, SpawnError>
+    where
+        F: FnMut() -> Fut + Send + 'static,
+        Fut: Future<Output = Result<Out, Er>> + Send + 'static,
+        Er: Send + 'static,
+        Tm: Timer + Send + 'static,
+        Self: SpawnHandle<Result<Out, TaskFailure<Er>>>,
+    {
+        let task = crate::core::supervised::supervise(make_fut, policy, timer);
+
+        SpawnHandleExt::<Result<Out, TaskFailure<Er>>>::spawn_handle(self, task)
+    }
+
+    fn spawn_all<I>(&self, iter: I) -> Result<Vec<JoinHandle<Out>>, SpawnError>
+    where
+        I: IntoIterator,
+        I::Item: Future<Output = Out> + Send + 'static,
+    {
+        let iter = iter.into_iter();
+        let mut handles = Vec::with_capacity(iter.size_hint().0);
+
+        for future in iter {
+            match self.spawn_handle(future) {
+                Ok(handle) => handles.push(handle),
+
+                Err(e) => {
+                    handles.into_iter().for_each(|handle| {
+                        handle.abort();
+                    });
+                    return Err(e);
+                }
+            }
+        }
+
+        Ok(handles)
+    }
+
+    fn spawn_stream<I>(
+        &self,
+        iter: I,
+        max_concurrent: usize,
+    ) -> SpawnStream<'_, Self, I::IntoIter, Out>
+    where
+        I: IntoIterator,
+        I::Item: Future<Output = Out> + Send + 'static,
+    {
+        SpawnStream::new(self, iter.into_iter(), max_concurrent)
     }
 }
 
@@ -102,6 +448,14 @@ where
     ) -> Result<JoinHandle<Out>, SpawnError> {
         (**self).spawn_handle_obj(future)
     }
+
+    fn spawn_handle_obj_named(
+        &self,
+        name: &str,
+        future: FutureObj<'static, Out>,
+    ) -> Result<JoinHandle<Out>, SpawnError> {
+        (**self).spawn_handle_obj_named(name, future)
+    }
 }
 
 impl<T: ?Sized, Out> SpawnHandle<Out> for Arc<T>
@@ -115,6 +469,14 @@ where
     ) -> Result<JoinHandle<Out>, SpawnError> {
         (**self).spawn_handle_obj(future)
     }
+
+    fn spawn_handle_obj_named(
+        &self,
+        name: &str,
+        future: FutureObj<'static, Out>,
+    ) -> Result<JoinHandle<Out>, SpawnError> {
+        (**self).spawn_handle_obj_named(name, future)
+    }
 }
 
 impl<T: ?Sized, Out> SpawnHandle<Out> for Rc<T>
@@ -128,6 +490,14 @@ where
     ) -> Result<JoinHandle<Out>, SpawnError> {
         (**self).spawn_handle_obj(future)
     }
+
+    fn spawn_handle_obj_named(
+        &self,
+        name: &str,
+        future: FutureObj<'static, Out>,
+    ) -> Result<JoinHandle<Out>, SpawnError> {
+        (**self).spawn_handle_obj_named(name, future)
+    }
 }
 
 impl<T, Out> SpawnHandle<Out> for &T
@@ -141,6 +511,14 @@ where
     ) -> Result<JoinHandle<Out>, SpawnError> {
         (**self).spawn_handle_obj(future)
     }
+
+    fn spawn_handle_obj_named(
+        &self,
+        name: &str,
+        future: FutureObj<'static, Out>,
+    ) -> Result<JoinHandle<Out>, SpawnError> {
+        (**self).spawn_handle_obj_named(name, future)
+    }
 }
 
 impl<T, Out> SpawnHandle<Out> for &mut T
@@ -154,6 +532,14 @@ where
     ) -> Result<JoinHandle<Out>, SpawnError> {
         (**self).spawn_handle_obj(future)
     }
+
+    fn spawn_handle_obj_named(
+        &self,
+        name: &str,
+        future: FutureObj<'static, Out>,
+    ) -> Result<JoinHandle<Out>, SpawnError> {
+        (**self).spawn_handle_obj_named(name, future)
+    }
 }
 
 #[cfg(feature = "localpool")]
@@ -0,0 +1,62 @@
+use std::sync::OnceLock;
+
+/// A hook that is called for every task spawned through this crate, see [`set_global_spawn_hook`].
+//
+pub type SpawnHook = Box<dyn Fn(&SpawnInfo<'_>) + Send + Sync + 'static>;
+
+static SPAWN_HOOK: OnceLock<SpawnHook> = OnceLock::new();
+
+/// Information about a spawned task, passed to the [`SpawnHook`].
+//
+#[derive(Debug, Clone, Copy)]
+//
+pub struct SpawnInfo<'a> {
+    name: Option<&'a str>,
+    executor: &'static str,
+}
+
+impl<'a> SpawnInfo<'a> {
+    /// The name of the task, if it was spawned with a name, eg. through
+    /// [`spawn_handle_named`](crate::SpawnHandleExt::spawn_handle_named).
+    //
+    pub fn name(&self) -> Option<&'a str> {
+        self.name
+    }
+
+    /// The type name of the executor the task was spawned on, eg. `async_executors::TokioCt`. This
+    /// comes from [`std::any::type_name`], so it is meant for diagnostics. It includes references and
+    /// wrappers, eg. `&async_executors::TokioCt`, and the exact format is not guaranteed.
+    //
+    pub fn executor(&self) -> &'static str {
+        self.executor
+    }
+}
+
+/// Set a hook that gets called every time a task is spawned successfully through the extension
+/// methods of this crate: [`spawn`](crate::SpawnExt::spawn), [`spawn_local`](crate::LocalSpawnExt::spawn_local),
+/// [`spawn_handle`](crate::SpawnHandleExt::spawn_handle), [`spawn_handle_named`](crate::SpawnHandleExt::spawn_handle_named),
+/// [`spawn_handle_local`](crate::LocalSpawnHandleExt::spawn_handle_local) and the methods built on them.
+/// This gives observability tooling one place to plug in, without having to wrap every executor.
+///
+/// Calling the `*_obj` methods of the traits directly bypasses the hook.
+///
+/// The hook is process wide and can only be set once. If one is already set, you get yours back
+/// as the error. When no hook is set, the only cost is checking that.
+///
+/// The hook runs on the thread that spawns, right after the task was handed to the executor, so keep
+/// it short. Spawning from within the hook calls it again.
+//
+pub fn set_global_spawn_hook(hook: SpawnHook) -> Result<(), SpawnHook> {
+    SPAWN_HOOK.set(hook)
+}
+
+// Called by the extension methods after a task was spawned.
+//
+pub(crate) fn on_spawn<E: ?Sized>(name: Option<&str>) {
+    if let Some(hook) = SPAWN_HOOK.get() {
+        hook(&SpawnInfo {
+            name,
+            executor: std::any::type_name::<E>(),
+        });
+    }
+}
@@ -0,0 +1,91 @@
+use crate::{Spawn, SpawnError, SpawnErrorKind, SpawnExt, Timer};
+use futures_util::future::BoxFuture;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+/// Future returned by [`SpawnExt::spawn_with_retry`]. Resolves once the future has been spawned,
+/// or with the last error if the executor kept refusing it.
+//
+#[must_use = "futures do nothing unless polled"]
+pub struct SpawnRetry<'a, S: ?Sized, Fut> {
+    exec: &'a S,
+    future: Option<Fut>,
+    attempts_left: usize,
+    backoff: Duration,
+    sleep: Option<BoxFuture<'static, ()>>,
+}
+
+impl<'a, S: ?Sized, Fut> SpawnRetry<'a, S, Fut> {
+    pub(crate) fn new(exec: &'a S, future: Fut, max_attempts: usize, backoff: Duration) -> Self {
+        Self {
+            exec,
+            future: Some(future),
+            attempts_left: max_attempts,
+            backoff,
+            sleep: None,
+        }
+    }
+}
+
+impl<S: ?Sized, Fut> std::fmt::Debug for SpawnRetry<'_, S, Fut> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SpawnRetry")
+            .field("attempts_left", &self.attempts_left)
+            .field("backoff", &self.backoff)
+            .finish()
+    }
+}
+
+// The future to spawn is never polled here, so it doesn't need to be pinned.
+//
+impl<S: ?Sized, Fut> Unpin for SpawnRetry<'_, S, Fut> {}
+
+impl<S, Fut> Future for SpawnRetry<'_, S, Fut>
+where
+    S: Spawn + Timer + ?Sized,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    type Output = Result<(), SpawnError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+
+        loop {
+            if let Some(sleep) = this.sleep.as_mut() {
+                futures_util::ready!(sleep.as_mut().poll(cx));
+                this.sleep = None;
+            }
+
+            let err = match this.exec.status() {
+                Ok(()) => {
+                    let future = this
+                        .future
+                        .take()
+                        .expect("SpawnRetry polled after completion");
+
+                    return Poll::Ready(this.exec.spawn(future));
+                }
+
+                // Only an executor at capacity might accept the future later.
+                //
+                Err(e) if e.kind() == SpawnErrorKind::AtCapacity => e,
+
+                Err(e) => {
+                    this.future = None;
+                    return Poll::Ready(Err(e));
+                }
+            };
+
+            this.attempts_left = this.attempts_left.saturating_sub(1);
+
+            if this.attempts_left == 0 {
+                this.future = None;
+                return Poll::Ready(Err(err));
+            }
+
+            this.sleep = Some(this.exec.sleep(this.backoff));
+        }
+    }
+}
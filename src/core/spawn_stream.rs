@@ -0,0 +1,167 @@
+use crate::{JoinHandle, SpawnBlocking, SpawnBlockingExt, SpawnError, SpawnHandle, SpawnHandleExt};
+use futures_util::stream::{FuturesUnordered, Stream, StreamExt};
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// A stream that spawns futures from an iterator with bounded concurrency and yields their
+/// outputs in the order they complete. Created by [`SpawnHandleExt::spawn_stream`].
+///
+/// When a new task gets spawned to replace a finished one, it is spawned on the executor, so
+/// the tasks run in parallel on threaded executors, unlike with `buffer_unordered`.
+///
+/// If spawning fails, the error is yielded and no more futures are taken from the iterator.
+/// The tasks that are already running will still be yielded after that.
+///
+/// Dropping the stream cancels all tasks that are still in flight, also when the executor returns
+/// handles that [detach on drop](JoinHandle::detach_on_drop).
+//
+#[must_use = "streams do nothing unless polled"]
+#[derive(Debug)]
+pub struct SpawnStream<'a, S: ?Sized, I, Out> {
+    exec: &'a S,
+    iter: Option<I>,
+    in_flight: FuturesUnordered<JoinHandle<Out>>,
+    max_concurrent: usize,
+}
+
+impl<'a, S: ?Sized, I, Out> SpawnStream<'a, S, I, Out> {
+    pub(crate) fn new(exec: &'a S, iter: I, max_concurrent: usize) -> Self {
+        assert!(max_concurrent > 0, "max_concurrent must be at least 1");
+
+        Self {
+            exec,
+            iter: Some(iter),
+            in_flight: FuturesUnordered::new(),
+            max_concurrent,
+        }
+    }
+}
+
+// We never pin project to any of the fields.
+//
+impl<S: ?Sized, I, Out> Unpin for SpawnStream<'_, S, I, Out> {}
+
+impl<S: ?Sized, I, Out> Drop for SpawnStream<'_, S, I, Out> {
+    fn drop(&mut self) {
+        std::mem::take(&mut self.in_flight)
+            .into_iter()
+            .for_each(|handle| {
+                handle.abort();
+            });
+    }
+}
+
+impl<S, I, Out> Stream for SpawnStream<'_, S, I, Out>
+where
+    S: SpawnHandle<Out> + ?Sized,
+    I: Iterator,
+    I::Item: Future<Output = Out> + Send + 'static,
+    Out: 'static + Send,
+{
+    type Item = Result<Out, SpawnError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+
+        while this.in_flight.len() < this.max_concurrent {
+            let fut = match this.iter.as_mut().and_then(Iterator::next) {
+                Some(fut) => fut,
+                None => {
+                    this.iter = None;
+                    break;
+                }
+            };
+
+            match this.exec.spawn_handle(fut) {
+                Ok(handle) => this.in_flight.push(handle),
+
+                Err(e) => {
+                    this.iter = None;
+                    return Poll::Ready(Some(Err(e)));
+                }
+            }
+        }
+
+        match futures_util::ready!(this.in_flight.poll_next_unpin(cx)) {
+            Some(out) => Poll::Ready(Some(Ok(out))),
+
+            // Since we always fill up in_flight first, this means the iterator is exhausted.
+            //
+            None => Poll::Ready(None),
+        }
+    }
+}
+
+/// A stream that runs blocking jobs from an iterator with at most `max_concurrent` of them in flight,
+/// and yields their results in the order they complete. Created by
+/// [`SpawnBlockingExt::spawn_blocking_stream`].
+///
+/// If spawning fails, the error is yielded and no more jobs are taken from the iterator. The jobs that
+/// are already running will still be yielded after that.
+///
+/// Dropping the stream stops submitting new jobs. The jobs that are already running can not be
+/// cancelled, as they are blocking, so they run to completion and their results are dropped.
+//
+#[must_use = "streams do nothing unless polled"]
+#[derive(Debug)]
+pub struct SpawnBlockingStream<'a, S: ?Sized, I, T> {
+    exec: &'a S,
+    jobs: Option<I>,
+    in_flight: FuturesUnordered<JoinHandle<T>>,
+    max_concurrent: usize,
+}
+
+impl<'a, S: ?Sized, I, T> SpawnBlockingStream<'a, S, I, T> {
+    pub(crate) fn new(exec: &'a S, jobs: I, max_concurrent: usize) -> Self {
+        assert!(max_concurrent > 0, "max_concurrent must be at least 1");
+
+        Self {
+            exec,
+            jobs: Some(jobs),
+            in_flight: FuturesUnordered::new(),
+            max_concurrent,
+        }
+    }
+}
+
+// We never pin project to any of the fields.
+//
+impl<S: ?Sized, I, T> Unpin for SpawnBlockingStream<'_, S, I, T> {}
+
+impl<S, I, T> Stream for SpawnBlockingStream<'_, S, I, T>
+where
+    S: SpawnBlocking<T> + ?Sized,
+    I: Iterator,
+    I::Item: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    type Item = Result<T, SpawnError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+
+        while this.in_flight.len() < this.max_concurrent {
+            let job = match this.jobs.as_mut().and_then(Iterator::next) {
+                Some(job) => job,
+                None => {
+                    this.jobs = None;
+                    break;
+                }
+            };
+
+            match this.exec.spawn_blocking(job) {
+                Ok(handle) => this.in_flight.push(handle),
+
+                Err(e) => {
+                    this.jobs = None;
+                    return Poll::Ready(Some(Err(e)));
+                }
+            }
+        }
+
+        // When in_flight is empty, the jobs are exhausted or spawning failed.
+        //
+        this.in_flight.poll_next_unpin(cx).map(|out| out.map(Ok))
+    }
+}
@@ -0,0 +1,63 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// Drive a future a bit and return control, so an embedder can interleave async work with its own
+/// loop, eg. a game loop or a GUI event loop.
+///
+/// Each call polls the future, gives the tasks spawned on the executor a chance to run and polls the
+/// future again if it's still pending. It returns `Pending` as soon as nothing can make progress
+/// anymore, without blocking the thread. `cx` is handed to the future, so its waker tells you when
+/// the future wants another step. Tasks on the executor that get woken by something else are only
+/// run on the next step.
+///
+/// This only makes sense for executors that don't run their own loop. The threaded runtimes
+/// (tokio, async-std, glommio, ...) poll their tasks on their own threads, so they don't implement
+/// this trait.
+///
+/// This takes `&mut self`, as stepping an executor generally needs exclusive access to its run queue.
+//
+pub trait StepExecutor {
+    /// Poll `fut` and run the tasks spawned on this executor until they are stalled.
+    ///
+    /// # Panics
+    ///
+    /// Implementations might panic when called from within another executor, like
+    /// [`LocalPool`](crate::LocalPool) does.
+    //
+    fn step<F: Future + ?Sized>(
+        &mut self,
+        fut: Pin<&mut F>,
+        cx: &mut Context<'_>,
+    ) -> Poll<F::Output>;
+}
+
+impl<T: StepExecutor + ?Sized> StepExecutor for &mut T {
+    fn step<F: Future + ?Sized>(
+        &mut self,
+        fut: Pin<&mut F>,
+        cx: &mut Context<'_>,
+    ) -> Poll<F::Output> {
+        (**self).step(fut, cx)
+    }
+}
+
+#[cfg(feature = "localpool")]
+//
+impl StepExecutor for crate::LocalPool {
+    fn step<F: Future + ?Sized>(
+        &mut self,
+        mut fut: Pin<&mut F>,
+        cx: &mut Context<'_>,
+    ) -> Poll<F::Output> {
+        if let Poll::Ready(out) = fut.as_mut().poll(cx) {
+            return Poll::Ready(out);
+        }
+
+        self.run_until_stalled();
+
+        fut.poll(cx)
+    }
+}
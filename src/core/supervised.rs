@@ -0,0 +1,127 @@
+use crate::Timer;
+use futures_util::FutureExt;
+use std::{
+    any::Any,
+    fmt,
+    future::Future,
+    panic::{catch_unwind, AssertUnwindSafe},
+    time::Duration,
+};
+
+/// How often and how fast [`SpawnHandleExt::spawn_supervised`](crate::SpawnHandleExt::spawn_supervised)
+/// restarts a task that failed.
+///
+/// By default the task is restarted right away. With [`backoff`](RestartPolicy::backoff), it waits
+/// before every restart, doubling the delay each time up to a maximum.
+//
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+//
+pub struct RestartPolicy {
+    max_restarts: usize,
+    backoff: Duration,
+    max_backoff: Duration,
+}
+
+impl RestartPolicy {
+    /// Restart the task at most `max_restarts` times. With 0, the task runs once.
+    //
+    pub fn new(max_restarts: usize) -> Self {
+        Self {
+            max_restarts,
+            backoff: Duration::ZERO,
+            max_backoff: Duration::ZERO,
+        }
+    }
+
+    /// Wait `initial` before the first restart, and double that for every following restart,
+    /// without going over `max`. When `initial` is bigger than `max`, every restart waits `max`.
+    //
+    pub fn backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.backoff = std::cmp::min(initial, max);
+        self.max_backoff = max;
+        self
+    }
+
+    /// The maximum number of restarts.
+    //
+    pub fn max_restarts(&self) -> usize {
+        self.max_restarts
+    }
+}
+
+/// Why a task failed, see [`SpawnHandleExt::spawn_supervised`](crate::SpawnHandleExt::spawn_supervised) and
+/// [`JoinHandle::flatten_result`](crate::JoinHandle::flatten_result). When the policy of a supervised task
+/// gives up, the handle resolves to the failure of the last run.
+//
+pub enum TaskFailure<E> {
+    /// The future resolved to an error.
+    Failed(E),
+    /// The future panicked. Contains the panic payload, which you can pass to
+    /// [`std::panic::resume_unwind`].
+    Panicked(Box<dyn Any + Send + 'static>),
+    /// The task was aborted, or the executor dropped it before it completed.
+    Cancelled,
+}
+
+impl<E: fmt::Debug> fmt::Debug for TaskFailure<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Failed(e) => f.debug_tuple("Failed").field(e).finish(),
+            Self::Panicked(_) => f.debug_tuple("Panicked").finish(),
+            Self::Cancelled => f.write_str("Cancelled"),
+        }
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for TaskFailure<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Failed(e) => write!(f, "The task failed: {}", e),
+            Self::Panicked(_) => write!(f, "The task panicked"),
+            Self::Cancelled => write!(f, "The task was cancelled"),
+        }
+    }
+}
+
+impl<E: fmt::Debug + fmt::Display> std::error::Error for TaskFailure<E> {}
+
+// The restart loop runs inside the spawned task, so it works the same on every executor.
+//
+pub(crate) async fn supervise<F, Fut, Out, E, Tm>(
+    mut make_fut: F,
+    policy: RestartPolicy,
+    timer: Tm,
+) -> Result<Out, TaskFailure<E>>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<Out, E>>,
+    Tm: Timer,
+{
+    let mut restarts = 0;
+    let mut backoff = policy.backoff;
+
+    loop {
+        let failure = match catch_unwind(AssertUnwindSafe(&mut make_fut)) {
+            Ok(fut) => match AssertUnwindSafe(fut).catch_unwind().await {
+                Ok(Ok(out)) => return Ok(out),
+                Ok(Err(e)) => TaskFailure::Failed(e),
+                Err(payload) => TaskFailure::Panicked(payload),
+            },
+
+            // Creating the future counts as part of the run.
+            //
+            Err(payload) => TaskFailure::Panicked(payload),
+        };
+
+        if restarts == policy.max_restarts {
+            return Err(failure);
+        }
+
+        restarts += 1;
+
+        if backoff > Duration::ZERO {
+            timer.sleep(backoff).await;
+            backoff = std::cmp::min(backoff.saturating_mul(2), policy.max_backoff);
+        }
+    }
+}
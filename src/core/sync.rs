@@ -0,0 +1,33 @@
+//! Async synchronization primitives that work on every executor.
+//!
+//! Tokio and async-std each come with their own locks, and some of them only work on their own
+//! runtime. Code that is generic over the traits of this crate can use these instead, so it doesn't
+//! have to pull in the types of a specific runtime. They are re-exported from
+//! [async-lock](https://docs.rs/async-lock), which only relies on wakers.
+//!
+//! ```
+//! use
+//! {
+//!    async_executors :: { sync::Mutex, SpawnHandle, SpawnHandleExt } ,
+//!    std             :: { sync::Arc                                 } ,
+//! };
+//!
+//! async fn increment( exec: impl SpawnHandle<()>, counter: Arc<Mutex<u32>> )
+//! {
+//!    let handles: Vec<_> = ( 0..10 ).map( |_|
+//!    {
+//!       let counter = counter.clone();
+//!
+//!       exec.spawn_handle( async move { *counter.lock().await += 1; } ).expect( "spawn" )
+//!
+//!    }).collect();
+//!
+//!    futures::future::join_all( handles ).await;
+//! }
+//! ```
+//
+#![cfg_attr(nightly, doc(cfg(feature = "sync")))]
+
+pub use async_lock::{
+    Mutex, MutexGuard, RwLock, RwLockReadGuard, RwLockWriteGuard, Semaphore, SemaphoreGuard,
+};
@@ -0,0 +1,81 @@
+use std::{
+    future::Future,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// Wraps an executor to time every task spawned through it, from the moment it is spawned until it
+/// completes, so the time spent waiting to be scheduled is included.
+///
+/// The duration is reported to `callback`, eg. to record it in a histogram. When a task gets dropped
+/// before completing, eg. because its [`JoinHandle`] cancelled it, `callback` gets `None`. Tasks that
+/// never started running, like the ones that failed to spawn, are not reported. `callback` is called
+/// on the thread that ran the task, so keep it cheap. Clones share the callback.
+///
+/// Not available on Wasm, where [`Instant`] isn't supported.
+//
+pub struct TimedSpawn<E, F> {
+    exec: E,
+    callback: Arc<F>,
+}
+
+// Created when the task starts running, so we also hear about tasks that get dropped, but not about
+// futures that get dropped because spawning them failed.
+//
+struct Timing<F: Fn(Option<Duration>)> {
+    start: Instant,
+    completed: bool,
+    callback: Arc<F>,
+}
+
+impl<F: Fn(Option<Duration>)> Drop for Timing<F> {
+    fn drop(&mut self) {
+        (self.callback)(self.completed.then(|| self.start.elapsed()));
+    }
+}
+
+async fn timed<Fut: Future, F: Fn(Option<Duration>)>(
+    start: Instant,
+    callback: Arc<F>,
+    future: Fut,
+) -> Fut::Output {
+    let mut timing = Timing {
+        start,
+        completed: false,
+        callback,
+    };
+
+    let out = future.await;
+    timing.completed = true;
+    out
+}
+
+impl<E, F> TimedSpawn<E, F>
+where
+    F: Fn(Option<Duration>) + Send + Sync + 'static,
+{
+    /// Time the tasks spawned on `exec` and report to `callback`.
+    //
+    pub fn new(exec: E, callback: F) -> Self {
+        Self {
+            exec,
+            callback: Arc::new(callback),
+        }
+    }
+}
+
+impl<E: Clone, F> Clone for TimedSpawn<E, F> {
+    fn clone(&self) -> Self {
+        Self {
+            exec: self.exec.clone(),
+            callback: self.callback.clone(),
+        }
+    }
+}
+
+wrapper!(TimedSpawn<E, F>);
+
+wrap_spawn! {
+    TimedSpawn<E, F> where { F: Fn(Option<Duration>) + Send + Sync + 'static }
+    wrap: |this, future| timed(Instant::now(), this.callback.clone(), future);
+}
@@ -0,0 +1,28 @@
+use futures_util::future::{BoxFuture, LocalBoxFuture};
+use std::time::Duration;
+
+/// Indicates that a runtime can create timers.
+///
+/// The returned future is `Send` and `'static`, so it can be awaited from tasks that get spawned
+/// on the executor.
+pub trait Timer {
+    /// Create a future that resolves once `dur` has elapsed.
+    fn sleep(&self, dur: Duration) -> BoxFuture<'static, ()>;
+}
+
+/// Indicates that a runtime can create timers that are not `Send`.
+///
+/// Thread per core runtimes like glommio have timers that are bound to the thread they
+/// were created on, so they can only implement this trait and not [`Timer`]. Every `Timer` also
+/// implements `LocalTimer`, so code that only awaits timers on the current thread should prefer
+/// this bound.
+pub trait LocalTimer {
+    /// Create a future that resolves once `dur` has elapsed.
+    fn sleep_local(&self, dur: Duration) -> LocalBoxFuture<'static, ()>;
+}
+
+impl<T: Timer + ?Sized> LocalTimer for T {
+    fn sleep_local(&self, dur: Duration) -> LocalBoxFuture<'static, ()> {
+        self.sleep(dur)
+    }
+}
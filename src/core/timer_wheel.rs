@@ -0,0 +1,332 @@
+use crate::{StepExecutor, Timer};
+use futures_util::{
+    future::BoxFuture,
+    task::{waker, ArcWake, AtomicWaker},
+};
+use std::{
+    convert::TryFrom,
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    task::{Context, Poll},
+    thread::{self, Thread},
+    time::{Duration, Instant},
+};
+
+const SLOTS: usize = 64;
+
+/// A software timer for executors that don't come with a reactor, like [`LocalPool`](crate::LocalPool)
+/// or a [`ManualExecutor`](crate::ManualExecutor), so generic code that needs a [`Timer`] can run on them,
+/// eg. in tests.
+///
+/// Nothing happens in the background. The sleep futures only complete when the wheel gets driven, either
+/// by calling [`drive_timers`](TimerWheel::drive_timers) between poll rounds of the executor, or by running
+/// the executor with [`block_on`](TimerWheel::block_on), which does that for you and parks the thread until
+/// the next timer is due. [`advance`](TimerWheel::advance) moves the clock of the wheel forward, so tests
+/// don't have to wait for real time to pass.
+///
+/// ## Resolution
+///
+/// Deadlines are rounded up to a multiple of `resolution`, so a sleep takes at least the requested
+/// duration, and up to `resolution` more. On top of that, the timers fire late by however long it takes
+/// before the wheel gets driven again, eg. when a task blocks the executor. So this is fine for timeouts
+/// and backoff, but don't use it where precision matters.
+///
+/// Clones share the same timers.
+//
+#[derive(Debug, Clone)]
+//
+pub struct TimerWheel {
+    inner: Arc<Mutex<Wheel>>,
+}
+
+#[derive(Debug)]
+struct Wheel {
+    start: Instant,
+    offset: Duration,
+    resolution: Duration,
+    // The last tick for which the timers have fired.
+    tick: u64,
+    slots: Vec<Vec<Entry>>,
+}
+
+#[derive(Debug)]
+struct Entry {
+    deadline: u64,
+    state: Arc<SleepState>,
+}
+
+#[derive(Debug, Default)]
+struct SleepState {
+    fired: AtomicBool,
+    // Set when the sleep future is dropped, so the wheel can forget about the entry.
+    cancelled: AtomicBool,
+    waker: AtomicWaker,
+}
+
+impl SleepState {
+    fn fire(&self) {
+        self.fired.store(true, Ordering::SeqCst);
+        self.waker.wake();
+    }
+}
+
+impl Entry {
+    fn cancelled(&self) -> bool {
+        self.state.cancelled.load(Ordering::SeqCst)
+    }
+}
+
+impl Wheel {
+    // The clock saturates, so advancing it by a huge duration doesn't overflow.
+    //
+    fn now(&self) -> Duration {
+        self.start.elapsed().saturating_add(self.offset)
+    }
+
+    fn ticks(&self, time: Duration) -> u64 {
+        u64::try_from(time.as_nanos() / self.resolution.as_nanos()).unwrap_or(u64::MAX)
+    }
+
+    fn time(&self, ticks: u64) -> Duration {
+        let nanos = self.resolution.as_nanos() * u128::from(ticks);
+
+        match u64::try_from(nanos / 1_000_000_000) {
+            Ok(secs) => Duration::new(secs, (nanos % 1_000_000_000) as u32),
+            Err(_) => Duration::MAX,
+        }
+    }
+
+    // Take out the timers that are due. Cancelled timers are removed from the slots that get visited.
+    //
+    fn expired(&mut self) -> Vec<Arc<SleepState>> {
+        let now = self.ticks(self.now());
+        let mut expired = Vec::new();
+
+        if now <= self.tick {
+            return expired;
+        }
+
+        // After a long pause, every slot might hold timers that are due.
+        //
+        let slots: Vec<usize> = match now - self.tick >= SLOTS as u64 {
+            true => (0..SLOTS).collect(),
+            false => (self.tick + 1..=now).map(|t| t as usize % SLOTS).collect(),
+        };
+
+        for slot in slots {
+            self.slots[slot].retain(|entry| {
+                if entry.cancelled() {
+                    return false;
+                }
+
+                match entry.deadline <= now {
+                    true => {
+                        expired.push(entry.state.clone());
+                        false
+                    }
+                    false => true,
+                }
+            });
+        }
+
+        self.tick = now;
+        expired
+    }
+}
+
+impl TimerWheel {
+    /// Create a timer wheel that rounds deadlines up to a multiple of `resolution`.
+    ///
+    /// # Panics
+    ///
+    /// When `resolution` is zero.
+    //
+    pub fn new(resolution: Duration) -> Self {
+        assert!(
+            resolution > Duration::ZERO,
+            "TimerWheel: resolution must be greater than 0"
+        );
+
+        let wheel = Wheel {
+            start: Instant::now(),
+            offset: Duration::ZERO,
+            resolution,
+            tick: 0,
+            slots: (0..SLOTS).map(|_| Vec::new()).collect(),
+        };
+
+        Self {
+            inner: Arc::new(Mutex::new(wheel)),
+        }
+    }
+
+    /// The resolution of the timers.
+    //
+    pub fn resolution(&self) -> Duration {
+        self.lock().resolution
+    }
+
+    /// Fire the timers that are due, waking up the tasks that sleep on them. Call this between poll rounds
+    /// of the executor, the woken tasks run on the next round. Returns how many timers fired.
+    //
+    pub fn drive_timers(&self) -> usize {
+        let expired = self.lock().expired();
+
+        // Wake outside of the lock, in case a waker runs code that uses the wheel.
+        //
+        expired.iter().for_each(|state| state.fire());
+        expired.len()
+    }
+
+    /// Move the clock of the wheel forward by `dur` and fire the timers that are due. Returns how many
+    /// timers fired. Real time keeps passing as well, this adds to it.
+    //
+    pub fn advance(&self, dur: Duration) -> usize {
+        {
+            let mut wheel = self.lock();
+            wheel.offset = wheel.offset.saturating_add(dur);
+        }
+
+        self.drive_timers()
+    }
+
+    /// How long until the next timer is due, or `None` when there are no timers. Timers whose sleep
+    /// future has been dropped don't count.
+    //
+    pub fn next_timer(&self) -> Option<Duration> {
+        let mut wheel = self.lock();
+
+        wheel
+            .slots
+            .iter_mut()
+            .for_each(|slot| slot.retain(|entry| !entry.cancelled()));
+
+        let deadline = wheel
+            .slots
+            .iter()
+            .flatten()
+            .map(|entry| entry.deadline)
+            .min()?;
+
+        Some(wheel.time(deadline).saturating_sub(wheel.now()))
+    }
+
+    /// Run `exec` until `fut` completes, driving the timers between poll rounds. When nothing can make
+    /// progress, the thread is parked until it gets woken up or the next timer is due.
+    ///
+    /// # Panics
+    ///
+    /// When the executor panics when stepped, eg. [`LocalPool`](crate::LocalPool) when this is called
+    /// from within another executor.
+    //
+    pub fn block_on<E, F>(&self, exec: &mut E, fut: F) -> F::Output
+    where
+        E: StepExecutor + ?Sized,
+        F: Future,
+    {
+        let unpark = Arc::new(Unpark {
+            thread: thread::current(),
+            woken: AtomicBool::new(false),
+        });
+
+        let waker = waker(unpark.clone());
+        let mut cx = Context::from_waker(&waker);
+
+        futures_util::pin_mut!(fut);
+
+        loop {
+            if let Poll::Ready(out) = exec.step(fut.as_mut(), &mut cx) {
+                return out;
+            }
+
+            if self.drive_timers() > 0 || unpark.woken.swap(false, Ordering::SeqCst) {
+                continue;
+            }
+
+            // If we get woken up in between, park returns right away.
+            //
+            match self.next_timer() {
+                Some(dur) => thread::park_timeout(dur),
+                None => thread::park(),
+            }
+
+            unpark.woken.store(false, Ordering::SeqCst);
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Wheel> {
+        self.inner.lock().expect("lock timer wheel")
+    }
+}
+
+impl Timer for TimerWheel {
+    fn sleep(&self, dur: Duration) -> BoxFuture<'static, ()> {
+        let state = Arc::new(SleepState::default());
+        let mut wheel = self.lock();
+
+        // Round up, so we never fire early. A deadline that doesn't fit in a Duration is never
+        // reached, so the sleep just never completes.
+        //
+        let deadline = wheel
+            .now()
+            .checked_add(dur)
+            .and_then(|d| d.checked_add(wheel.resolution - Duration::from_nanos(1)))
+            .map(|d| wheel.ticks(d));
+
+        match deadline {
+            Some(deadline) if deadline <= wheel.tick => state.fire(),
+
+            Some(deadline) => wheel.slots[deadline as usize % SLOTS].push(Entry {
+                deadline,
+                state: state.clone(),
+            }),
+
+            None => {}
+        }
+
+        Box::pin(Sleep(state))
+    }
+}
+
+// The future returned by TimerWheel::sleep.
+//
+struct Sleep(Arc<SleepState>);
+
+impl Future for Sleep {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        // Register first, so we don't miss the timer firing in between.
+        //
+        self.0.waker.register(cx.waker());
+
+        match self.0.fired.load(Ordering::SeqCst) {
+            true => Poll::Ready(()),
+            false => Poll::Pending,
+        }
+    }
+}
+
+impl Drop for Sleep {
+    fn drop(&mut self) {
+        self.0.cancelled.store(true, Ordering::SeqCst);
+    }
+}
+
+// Wakes up the thread running TimerWheel::block_on.
+//
+struct Unpark {
+    thread: Thread,
+    woken: AtomicBool,
+}
+
+impl ArcWake for Unpark {
+    fn wake_by_ref(arc_self: &Arc<Self>) {
+        arc_self.woken.store(true, Ordering::SeqCst);
+        arc_self.thread.unpark();
+    }
+}
@@ -0,0 +1,150 @@
+use crate::{JoinHandle, SpawnError, SpawnHandle, SpawnHandleExt};
+use futures_util::stream::{FuturesUnordered, StreamExt};
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// Run a group of fallible tasks, all or nothing. `f` spawns the tasks through the [`TryScope`] it
+/// receives. When one of them returns an `Err`, the others get aborted and the error is returned.
+/// Otherwise this gives the outputs of all tasks, in the order they were spawned. Like
+/// `futures::try_join!`, but the tasks run concurrently on `exec` and there can be any number of them.
+///
+/// When spawning fails, the tasks spawned so far get aborted and the [`SpawnError`] is converted into
+/// your error type. When a task panics, the panic is propagated when it is joined, and the other tasks
+/// get aborted. Dropping the returned future aborts all tasks as well, also the ones whose handle would
+/// [detach on drop](JoinHandle::detach_on_drop), so no task outlives the scope.
+///
+/// ```
+/// # #[cfg(feature = "tokio_ct")]
+/// # {
+/// use
+/// {
+///    async_executors :: { try_scope, SpawnError, TokioCtBuilder } ,
+/// };
+///
+/// #[ derive( Debug, PartialEq ) ]
+/// //
+/// enum Error { Spawn, NotFound }
+///
+/// impl From<SpawnError> for Error
+/// {
+///    fn from( _: SpawnError ) -> Self { Error::Spawn }
+/// }
+///
+/// let exec = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+///
+/// let out = exec.block_on( try_scope( &exec, |s|
+/// {
+///    s.spawn( async { Ok::<_, Error>( 1u8 ) } )?;
+///    s.spawn( async { Ok::<_, Error>( 2u8 ) } )?;
+///    Ok(())
+///
+/// }));
+///
+/// assert_eq!( Ok( vec![ 1, 2 ] ), out );
+///
+/// let out = exec.block_on( try_scope( &exec, |s|
+/// {
+///    s.spawn( async { Ok( 1u8 )              } )?;
+///    s.spawn( async { Err( Error::NotFound ) } )?;
+///    Ok(())
+///
+/// }));
+///
+/// assert_eq!( Err( Error::NotFound ), out );
+/// # }
+/// ```
+//
+pub async fn try_scope<E, T, Er, F>(exec: &E, f: F) -> Result<Vec<T>, Er>
+where
+    E: SpawnHandle<Result<T, Er>> + ?Sized,
+    T: Send + 'static,
+    Er: From<SpawnError> + Send + 'static,
+    F: FnOnce(&mut TryScope<'_, E, T, Er>) -> Result<(), SpawnError>,
+{
+    let mut scope = TryScope {
+        exec,
+        handles: FuturesUnordered::new(),
+        spawned: 0,
+    };
+
+    // Dropping the scope aborts the tasks already spawned.
+    //
+    f(&mut scope)?;
+
+    let mut outputs: Vec<Option<T>> = (0..scope.spawned).map(|_| None).collect();
+
+    while let Some((index, out)) = scope.handles.next().await {
+        outputs[index] = Some(out?);
+    }
+
+    Ok(outputs
+        .into_iter()
+        .map(|out| out.expect("all tasks of the scope joined"))
+        .collect())
+}
+
+/// Spawns the tasks of a [`try_scope`].
+//
+#[derive(Debug)]
+//
+pub struct TryScope<'a, E: ?Sized, T, Er> {
+    exec: &'a E,
+    handles: FuturesUnordered<Indexed<Result<T, Er>>>,
+    spawned: usize,
+}
+
+impl<E, T, Er> TryScope<'_, E, T, Er>
+where
+    E: SpawnHandle<Result<T, Er>> + ?Sized,
+    T: Send + 'static,
+    Er: Send + 'static,
+{
+    /// Spawn a task in the scope.
+    //
+    pub fn spawn(
+        &mut self,
+        future: impl Future<Output = Result<T, Er>> + Send + 'static,
+    ) -> Result<(), SpawnError> {
+        let handle = self.exec.spawn_handle(future)?;
+
+        self.handles.push(Indexed {
+            index: self.spawned,
+            handle,
+        });
+
+        self.spawned += 1;
+        Ok(())
+    }
+}
+
+impl<E: ?Sized, T, Er> Drop for TryScope<'_, E, T, Er> {
+    fn drop(&mut self) {
+        std::mem::take(&mut self.handles)
+            .into_iter()
+            .for_each(|task| {
+                task.handle.abort();
+            });
+    }
+}
+
+// Remembers the spawn order, since the tasks are joined in completion order.
+//
+#[derive(Debug)]
+//
+struct Indexed<T> {
+    index: usize,
+    handle: JoinHandle<T>,
+}
+
+impl<T: 'static> Future for Indexed<T> {
+    type Output = (usize, T);
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let index = self.index;
+
+        Pin::new(&mut self.handle).poll(cx).map(|out| (index, out))
+    }
+}
@@ -0,0 +1,51 @@
+use crate::{JoinHandle, Spawn, SpawnError, SpawnHandle};
+use futures_task::FutureObj;
+use futures_util::FutureExt;
+
+/// Adapter that implements [`SpawnHandle`] for executors that only implement [`Spawn`].
+///
+/// It splits the future with [`remote_handle`](futures_util::FutureExt::remote_handle), spawns the
+/// remote half on the wrapped executor and returns a [`JoinHandle`] wrapping the
+/// [`RemoteHandle`](futures_util::future::RemoteHandle). Like for the other executors, dropping the
+/// join handle cancels the task. Create it with [`SpawnExt::with_handles`](crate::SpawnExt::with_handles) or [`WithHandles::new`].
+//
+#[derive(Debug, Clone, Copy, Default)]
+//
+pub struct WithHandles<E>(pub E);
+
+impl<E> WithHandles<E> {
+    /// Wrap `exec`.
+    //
+    pub fn new(exec: E) -> Self {
+        Self(exec)
+    }
+
+    /// Get back the wrapped executor.
+    //
+    pub fn into_inner(self) -> E {
+        self.0
+    }
+}
+
+impl<E: Spawn, Out: 'static + Send> SpawnHandle<Out> for WithHandles<E> {
+    fn spawn_handle_obj(
+        &self,
+        future: FutureObj<'static, Out>,
+    ) -> Result<JoinHandle<Out>, SpawnError> {
+        let (fut, handle) = future.remote_handle();
+
+        self.0.spawn_obj(FutureObj::new(Box::new(fut)))?;
+
+        Ok(handle.into())
+    }
+}
+
+impl<E: Spawn> Spawn for WithHandles<E> {
+    fn spawn_obj(&self, future: FutureObj<'static, ()>) -> Result<(), SpawnError> {
+        self.0.spawn_obj(future)
+    }
+
+    fn status(&self) -> Result<(), SpawnError> {
+        self.0.status()
+    }
+}
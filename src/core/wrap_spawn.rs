@@ -0,0 +1,120 @@
+//! Shared plumbing for the wrappers that change every future spawned through them, like
+//! `OnTaskPanic`, `TimedSpawn` and `MeteredExecutor`. They all hold the executor in an `exec` field.
+//
+// The spawn traits are foreign, so we can't give every wrapper the impls through a blanket impl on a
+// trait of our own. These macros write them instead, so each wrapper only provides the hook.
+
+// Implements `inner` and `Debug` for the wrapper `$name`, which wraps an executor of type `E`.
+//
+macro_rules! wrapper {
+    ($name:ident<E $(, $param:ident)*>) => {
+        impl<E $(, $param)*> $name<E $(, $param)*> {
+            /// The wrapped executor.
+            //
+            pub fn inner(&self) -> &E {
+                &self.exec
+            }
+        }
+
+        impl<E: std::fmt::Debug $(, $param)*> std::fmt::Debug for $name<E $(, $param)*> {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.debug_struct(stringify!($name))
+                    .field("exec", &self.exec)
+                    .finish()
+            }
+        }
+    };
+}
+
+// Implements Spawn, LocalSpawn, SpawnHandle and LocalSpawnHandle for the wrapper `$name`. Every
+// future goes through `wrap` before it's spawned on the wrapped executor. The futures of the tasks
+// without a JoinHandle go through `detached` instead, when given.
+//
+macro_rules! wrap_spawn {
+    (
+        $name:ident<E $(, $param:ident)*> where { $($bounds:tt)* }
+        wrap: |$this:ident, $future:ident| $wrap:expr;
+    ) => {
+        wrap_spawn! {
+            $name<E $(, $param)*> where { $($bounds)* }
+            wrap: |$this, $future| $wrap;
+            detached: |$this, $future| $wrap;
+        }
+    };
+
+    (
+        $name:ident<E $(, $param:ident)*> where { $($bounds:tt)* }
+        wrap: |$this:ident, $future:ident| $wrap:expr;
+        detached: |$this2:ident, $future2:ident| $detached:expr;
+    ) => {
+        impl<E $(, $param)*> $crate::Spawn for $name<E $(, $param)*>
+        where
+            E: $crate::Spawn,
+            $($bounds)*
+        {
+            fn spawn_obj(
+                &self,
+                $future2: futures_task::FutureObj<'static, ()>,
+            ) -> Result<(), $crate::SpawnError> {
+                let $this2 = self;
+                self.exec
+                    .spawn_obj(futures_task::FutureObj::new(Box::new($detached)))
+            }
+
+            fn status(&self) -> Result<(), $crate::SpawnError> {
+                self.exec.status()
+            }
+        }
+
+        impl<E $(, $param)*> $crate::LocalSpawn for $name<E $(, $param)*>
+        where
+            E: $crate::LocalSpawn,
+            $($bounds)*
+        {
+            fn spawn_local_obj(
+                &self,
+                $future2: futures_task::LocalFutureObj<'static, ()>,
+            ) -> Result<(), $crate::SpawnError> {
+                let $this2 = self;
+                self.exec
+                    .spawn_local_obj(futures_task::LocalFutureObj::new(Box::new($detached)))
+            }
+
+            fn status_local(&self) -> Result<(), $crate::SpawnError> {
+                self.exec.status_local()
+            }
+        }
+
+        impl<E $(, $param)*, Out> $crate::SpawnHandle<Out> for $name<E $(, $param)*>
+        where
+            E: $crate::SpawnHandle<Out>,
+            Out: Send + 'static,
+            $($bounds)*
+        {
+            fn spawn_handle_obj(
+                &self,
+                $future: futures_task::FutureObj<'static, Out>,
+            ) -> Result<$crate::JoinHandle<Out>, $crate::SpawnError> {
+                let $this = self;
+                self.exec
+                    .spawn_handle_obj(futures_task::FutureObj::new(Box::new($wrap)))
+            }
+        }
+
+        impl<E $(, $param)*, Out> $crate::LocalSpawnHandle<Out> for $name<E $(, $param)*>
+        where
+            E: $crate::LocalSpawnHandle<Out>,
+            Out: 'static,
+            $($bounds)*
+        {
+            fn spawn_handle_local_obj(
+                &self,
+                $future: futures_task::LocalFutureObj<'static, Out>,
+            ) -> Result<$crate::JoinHandle<Out>, $crate::SpawnError> {
+                let $this = self;
+                self.exec
+                    .spawn_handle_local_obj(futures_task::LocalFutureObj::new(Box::new($wrap)))
+            }
+        }
+    };
+}
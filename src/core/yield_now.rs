@@ -16,3 +16,56 @@ impl<T: YieldNowStatic> YieldNow for T {
         T::yield_now()
     }
 }
+
+/// Yield to the scheduler of the runtime enabled by the features of this crate, without needing
+/// a handle to an executor. Forwards to [`YieldNowStatic`] on that runtime.
+///
+/// This is only available when exactly one runtime is enabled, and it is one of `tokio_ct`/`tokio_tp`,
+/// `async_std` and `glommio`. Otherwise it would be ambiguous, so use [`YieldNowStatic`] on the runtime
+/// type you want instead, eg. `Tokio::yield_now()`.
+//
+#[cfg(any(
+    all(
+        feature = "tokio",
+        not(any(
+            feature = "async_std",
+            feature = "async_global",
+            feature = "glommio",
+            feature = "compio",
+            feature = "bindgen"
+        ))
+    ),
+    all(
+        feature = "async_std",
+        not(any(
+            feature = "tokio",
+            feature = "async_global",
+            feature = "glommio",
+            feature = "compio",
+            feature = "bindgen"
+        ))
+    ),
+    all(
+        feature = "glommio",
+        not(any(
+            feature = "tokio",
+            feature = "async_std",
+            feature = "async_global",
+            feature = "compio",
+            feature = "bindgen"
+        ))
+    ),
+))]
+//
+pub fn yield_now() -> BoxFuture<'static, ()> {
+    #[cfg(feature = "tokio")]
+    type Runtime = crate::Tokio;
+
+    #[cfg(feature = "async_std")]
+    type Runtime = crate::AsyncStd;
+
+    #[cfg(feature = "glommio")]
+    type Runtime = crate::Glommio;
+
+    <Runtime as YieldNowStatic>::yield_now()
+}
@@ -0,0 +1,44 @@
+//! Query and set the CPU affinity of the current thread, eg. to place your own threads relative to
+//! the cores that [`GlommioTp`](crate::GlommioTp) and [`GlommioCtBuilder`](crate::GlommioCtBuilder)
+//! pin their executors to on NUMA machines.
+//!
+//! Only available on Linux, with the `glommio` feature.
+//!
+//! ```no_run
+//! use async_executors::affinity::{current_cpu_set, pin_current_thread};
+//!
+//! let cpus = current_cpu_set().expect("get affinity");
+//!
+//! std::thread::spawn(move || {
+//!     pin_current_thread(cpus[0]).expect("set affinity");
+//!
+//!     // Runs on the first cpu we were allowed to use.
+//! });
+//! ```
+//
+#![cfg_attr(nightly, doc(cfg(feature = "glommio")))]
+
+use crate::runtime::glommio_static::{bind_to_cpu_set, current_cpu_set as cpu_set, single_cpu_set};
+use nix::sched::CpuSet;
+
+/// The CPUs the current thread is allowed to run on, in ascending order.
+//
+pub fn current_cpu_set() -> std::io::Result<Vec<usize>> {
+    let set = cpu_set()?;
+
+    Ok((0..CpuSet::count())
+        .filter(|&cpu| set.is_set(cpu).unwrap_or(false))
+        .collect())
+}
+
+/// Only let the current thread run on `cpu`. Other threads are not affected, also not the ones
+/// spawned by this thread afterwards, which inherit the affinity at the time they are spawned.
+///
+/// # Errors
+///
+/// When `cpu` is out of range, or when the OS refuses, eg. because `cpu` is not in the set of
+/// CPUs this process may use.
+//
+pub fn pin_current_thread(cpu: usize) -> std::io::Result<()> {
+    bind_to_cpu_set(single_cpu_set(cpu)?)
+}
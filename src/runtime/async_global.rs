@@ -1,4 +1,8 @@
-use crate::{AsyncJoinHandle, LocalSpawn, Spawn, SpawnError};
+use crate::{
+    AsyncJoinHandle, LocalSpawn, LocalSpawnExt, LocalSpawnHandleExt, LocalSpawnHandleStatic,
+    LocalSpawnStatic, Spawn, SpawnError, SpawnExt, SpawnHandleExt, SpawnHandleStatic, SpawnStatic,
+};
+use futures_util::FutureExt;
 use std::future::Future;
 use std::pin::Pin;
 use std::task::{Context, Poll};
@@ -12,6 +16,8 @@ use {
 /// so the threadpool is global.
 ///
 /// It works on Wasm.
+///
+/// `AsyncGlobal` is a zero sized type that is `Send`, `Sync` and `Copy`.
 //
 #[derive(Copy, Clone, Default)]
 //
@@ -28,6 +34,13 @@ impl AsyncGlobal {
 
     /// Wrapper around [async_global_executor::block_on]. This is not available on Wasm
     /// as Wasm does not have threads and you're not allowed to block the only thread you have.
+    ///
+    /// async-global-executor keeps a local executor per thread, next to the global threadpool. While
+    /// this runs, the calling thread drives that local executor, so the tasks spawned with
+    /// [`spawn_local`](crate::LocalSpawnExt::spawn_local) and friends from this thread make progress.
+    /// Local tasks that haven't completed when `future` does are not dropped, they continue the next
+    /// time the thread calls `block_on`.
+    /// For local tasks that are scoped to one call, see [`run_local`](AsyncGlobal::run_local).
     //
     // TODO: is target_arch = "wasm32"  not a better way to express this?
     //
@@ -81,6 +94,12 @@ impl<Out: 'static + Send> SpawnHandle<Out> for AsyncGlobal {
     }
 }
 
+/// Local tasks run on the thread that spawned them. On Wasm that always works. On other targets
+/// the thread only runs them while it is inside [`AsyncGlobal::block_on`] or when it is one of the
+/// executor threads. async-global-executor does not let us detect whether that is the case, so
+/// spawning never fails, but a task spawned from another thread only makes progress once that
+/// thread calls `block_on`.
+//
 impl<Out: 'static> LocalSpawnHandle<Out> for AsyncGlobal {
     fn spawn_handle_local_obj(
         &self,
@@ -90,6 +109,8 @@ impl<Out: 'static> LocalSpawnHandle<Out> for AsyncGlobal {
     }
 }
 
+/// See the [`LocalSpawnHandle`] impl for when local tasks actually run.
+//
 impl LocalSpawn for AsyncGlobal {
     fn spawn_local_obj(&self, future: LocalFutureObj<'static, ()>) -> Result<(), SpawnError> {
         let _ = async_global_executor::spawn_local(future).detach();
@@ -98,6 +119,48 @@ impl LocalSpawn for AsyncGlobal {
     }
 }
 
+// The static traits forward to the methods above, so they behave the same, also on Wasm.
+//
+impl SpawnStatic for AsyncGlobal {
+    fn spawn<Output, Fut>(future: Fut) -> Result<(), SpawnError>
+    where
+        Fut: Future<Output = Output> + Send + 'static,
+        Output: Send + 'static,
+    {
+        AsyncGlobal.spawn(future.map(drop))
+    }
+}
+
+impl SpawnHandleStatic for AsyncGlobal {
+    fn spawn_handle<Output, Fut>(future: Fut) -> Result<JoinHandle<Output>, SpawnError>
+    where
+        Fut: Future<Output = Output> + Send + 'static,
+        Output: 'static + Send,
+    {
+        AsyncGlobal.spawn_handle(future)
+    }
+}
+
+impl LocalSpawnStatic for AsyncGlobal {
+    fn spawn_local<Output, Fut>(future: Fut) -> Result<(), SpawnError>
+    where
+        Fut: Future<Output = Output> + 'static,
+        Output: 'static,
+    {
+        AsyncGlobal.spawn_local(future.map(drop))
+    }
+}
+
+impl LocalSpawnHandleStatic for AsyncGlobal {
+    fn spawn_handle_local<Output, Fut>(future: Fut) -> Result<JoinHandle<Output>, SpawnError>
+    where
+        Fut: Future<Output = Output> + 'static,
+        Output: 'static,
+    {
+        AsyncGlobal.spawn_handle_local(future)
+    }
+}
+
 impl std::fmt::Debug for AsyncGlobal {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "AsyncGlobal executor")
@@ -109,6 +172,11 @@ impl<T> AsyncGlobalJoinHandle<T> {
     pub fn new(task: async_global_executor::Task<T>) -> Self {
         Self(task)
     }
+
+    /// Access the native async-global-executor task.
+    pub fn inner(&self) -> &async_global_executor::Task<T> {
+        &self.0
+    }
 }
 impl<T> Future for AsyncGlobalJoinHandle<T> {
     type Output = T;
@@ -127,6 +195,16 @@ impl<T> AsyncJoinHandle for AsyncGlobalJoinHandle<T> {
 }
 impl<T> Into<JoinHandle<T>> for AsyncGlobalJoinHandle<T> {
     fn into(self) -> JoinHandle<T> {
-        JoinHandle::AsyncJoinHandle(self)
+        JoinHandle::AsyncJoinHandle(self).with_default_drop()
     }
 }
+
+#[cfg(test)]
+//
+mod tests {
+    use super::*;
+
+    // The executor is global, so the wrapper is a zero sized handle.
+    //
+    static_assertions::assert_impl_all!(AsyncGlobal: Send, Sync, Copy, Default);
+}
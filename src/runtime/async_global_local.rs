@@ -0,0 +1,98 @@
+//! Provides AsyncGlobalLocal, a local executor of its own, created with AsyncGlobal::run_local.
+//
+use crate::{
+    AsyncGlobal, AsyncGlobalJoinHandle, JoinHandle, LocalSpawn, LocalSpawnHandle, SpawnError,
+};
+use {
+    async_executor::LocalExecutor,
+    futures_task::LocalFutureObj,
+    std::{
+        future::Future,
+        rc::{Rc, Weak},
+    },
+};
+
+/// A handle to the local executor that [`AsyncGlobal::run_local`] runs on the current thread. Spawns
+/// `!Send` tasks on that executor.
+///
+/// Unlike the local tasks of [`AsyncGlobal`], which go on an implicit executor per thread, these
+/// tasks belong to the `run_local` call. They run while it runs, and they are dropped when it
+/// returns. The handle does not keep the executor alive, so tasks can hold clones of it. Once
+/// `run_local` has returned, spawning fails with [`SpawnError::shutdown`].
+///
+/// ```
+/// use
+/// {
+///    async_executors :: { AsyncGlobal, LocalSpawnHandleExt } ,
+///    std             :: { rc::Rc                           } ,
+/// };
+///
+/// let out = AsyncGlobal::run_local( |local| async move
+/// {
+///    let not_send = Rc::new( 5u8 );
+///
+///    local.spawn_handle_local( async move { *not_send } ).expect( "spawn" ).await
+/// });
+///
+/// assert_eq!( 5, out );
+/// ```
+//
+#[derive(Debug, Clone)]
+//
+#[cfg_attr(nightly, doc(cfg(feature = "async_global")))]
+//
+pub struct AsyncGlobalLocal {
+    exec: Weak<LocalExecutor<'static>>,
+}
+
+impl AsyncGlobal {
+    /// Create a local executor on the current thread, pass a handle to it to `f`, and run the future
+    /// `f` returns to completion. The `!Send` tasks spawned through the [`AsyncGlobalLocal`] run
+    /// concurrently with that future. Tasks that are still running when it completes are dropped.
+    ///
+    /// This runs inside [`block_on`](AsyncGlobal::block_on), so the local tasks spawned directly on
+    /// [`AsyncGlobal`] from this thread make progress as well. Not available on Wasm, as it blocks.
+    //
+    #[cfg_attr(nightly, doc(cfg(not(target_os = "unknown"))))]
+    //
+    pub fn run_local<F, Fut>(f: F) -> Fut::Output
+    where
+        F: FnOnce(AsyncGlobalLocal) -> Fut,
+        Fut: Future,
+    {
+        let exec = Rc::new(LocalExecutor::new());
+
+        let local = AsyncGlobalLocal {
+            exec: Rc::downgrade(&exec),
+        };
+
+        Self::block_on(exec.run(f(local)))
+    }
+}
+
+impl AsyncGlobalLocal {
+    fn exec(&self) -> Result<Rc<LocalExecutor<'static>>, SpawnError> {
+        self.exec.upgrade().ok_or_else(SpawnError::shutdown)
+    }
+}
+
+impl<Out: 'static> LocalSpawnHandle<Out> for AsyncGlobalLocal {
+    fn spawn_handle_local_obj(
+        &self,
+        future: LocalFutureObj<'static, Out>,
+    ) -> Result<JoinHandle<Out>, SpawnError> {
+        Ok(AsyncGlobalJoinHandle::new(self.exec()?.spawn(future)).into())
+    }
+}
+
+impl LocalSpawn for AsyncGlobalLocal {
+    fn spawn_local_obj(&self, future: LocalFutureObj<'static, ()>) -> Result<(), SpawnError> {
+        self.exec()?.spawn(future).detach();
+
+        Ok(())
+    }
+
+    fn status_local(&self) -> Result<(), SpawnError> {
+        self.exec().map(drop)
+    }
+}
@@ -1,5 +1,10 @@
-use crate::{AsyncJoinHandle, LocalSpawn, Spawn, SpawnError};
+use crate::{
+    AsyncJoinHandle, LocalSpawn, LocalSpawnExt, LocalSpawnHandleExt, LocalSpawnHandleStatic,
+    LocalSpawnStatic, Spawn, SpawnError, SpawnExt, SpawnHandleExt, SpawnHandleStatic, SpawnStatic,
+    YieldNowStatic,
+};
 use futures_util::future::{AbortHandle, Aborted};
+use futures_util::FutureExt;
 use std::future::Future;
 use std::pin::Pin;
 use std::task::{Context, Poll};
@@ -14,6 +19,8 @@ use {
 /// so the threadpool is global.
 ///
 /// It works on Wasm.
+///
+/// `AsyncStd` is a zero sized type that is `Send`, `Sync` and `Copy`.
 //
 #[derive(Copy, Clone, Default)]
 //
@@ -43,7 +50,7 @@ impl AsyncStd {
 //
 impl Spawn for AsyncStd {
     fn spawn_obj(&self, future: FutureObj<'static, ()>) -> Result<(), SpawnError> {
-        async_std_crate::task::spawn_local(future);
+        let _ = spawn_local(future)?;
 
         Ok(())
     }
@@ -81,10 +88,47 @@ impl<Out: 'static + Send> SpawnHandle<Out> for AsyncStd {
     ) -> Result<JoinHandle<Out>, SpawnError> {
         let (fut, a_handle) = abortable(future);
 
-        Ok(AsyncStdJoinHandle::new(async_std_crate::task::spawn_local(fut), a_handle).into())
+        Ok(AsyncStdJoinHandle::new(spawn_local(fut)?, a_handle).into())
     }
 }
 
+// Local tasks run on the thread that spawned them. On Wasm that always works. On other targets the
+// thread only runs them while it is running an async-std task, eg. inside `block_on` or in a task on
+// the threadpool. Otherwise the task would never run, so we return an error instead.
+//
+fn spawn_local<T: 'static>(
+    future: impl Future<Output = T> + 'static,
+) -> Result<async_std_crate::task::JoinHandle<T>, SpawnError> {
+    check_local_context()?;
+
+    async_std_crate::task::Builder::new()
+        .local(future)
+        .map_err(SpawnError::with_source)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+//
+fn check_local_context() -> Result<(), SpawnError> {
+    match async_std_crate::task::try_current() {
+        Some(_) => Ok(()),
+        None => Err(SpawnError::with_kind(
+            crate::SpawnErrorKind::Unsupported,
+            std::io::Error::other(
+            "AsyncStd can only spawn local tasks from within an async-std task, eg. inside block_on",
+        ))),
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+//
+fn check_local_context() -> Result<(), SpawnError> {
+    Ok(())
+}
+
+/// On Wasm local spawning is always supported. On other targets it only works from within an
+/// async-std task, eg. inside [`AsyncStd::block_on`]. Outside of that a [`SpawnError`] of kind
+/// [`Unsupported`](crate::SpawnErrorKind::Unsupported) is returned.
+//
 impl<Out: 'static> LocalSpawnHandle<Out> for AsyncStd {
     fn spawn_handle_local_obj(
         &self,
@@ -92,18 +136,83 @@ impl<Out: 'static> LocalSpawnHandle<Out> for AsyncStd {
     ) -> Result<JoinHandle<Out>, SpawnError> {
         let (fut, a_handle) = abortable(future);
 
-        Ok(AsyncStdJoinHandle::new(async_std_crate::task::spawn_local(fut), a_handle).into())
+        Ok(AsyncStdJoinHandle::new(spawn_local(fut)?, a_handle).into())
     }
 }
 
+/// On Wasm local spawning is always supported. On other targets it only works from within an
+/// async-std task, eg. inside [`AsyncStd::block_on`]. Outside of that a [`SpawnError`] is returned.
+//
 impl LocalSpawn for AsyncStd {
     fn spawn_local_obj(&self, future: LocalFutureObj<'static, ()>) -> Result<(), SpawnError> {
         // We drop the JoinHandle, so the task becomes detached.
         //
-        let _ = async_std_crate::task::spawn_local(future);
+        let _ = spawn_local(future)?;
 
         Ok(())
     }
+
+    fn status_local(&self) -> Result<(), SpawnError> {
+        check_local_context()
+    }
+}
+
+// On Wasm the timer future is not `Send`.
+//
+#[cfg(not(target_arch = "wasm32"))]
+//
+impl crate::Timer for AsyncStd {
+    fn sleep(&self, dur: std::time::Duration) -> futures_util::future::BoxFuture<'static, ()> {
+        Box::pin(async_std_crate::task::sleep(dur))
+    }
+}
+
+// The static traits forward to the methods above, so they behave the same, also on Wasm.
+//
+impl SpawnStatic for AsyncStd {
+    fn spawn<Output, Fut>(future: Fut) -> Result<(), SpawnError>
+    where
+        Fut: Future<Output = Output> + Send + 'static,
+        Output: Send + 'static,
+    {
+        AsyncStd.spawn(future.map(drop))
+    }
+}
+
+impl SpawnHandleStatic for AsyncStd {
+    fn spawn_handle<Output, Fut>(future: Fut) -> Result<JoinHandle<Output>, SpawnError>
+    where
+        Fut: Future<Output = Output> + Send + 'static,
+        Output: 'static + Send,
+    {
+        AsyncStd.spawn_handle(future)
+    }
+}
+
+impl LocalSpawnStatic for AsyncStd {
+    fn spawn_local<Output, Fut>(future: Fut) -> Result<(), SpawnError>
+    where
+        Fut: Future<Output = Output> + 'static,
+        Output: 'static,
+    {
+        AsyncStd.spawn_local(future.map(drop))
+    }
+}
+
+impl LocalSpawnHandleStatic for AsyncStd {
+    fn spawn_handle_local<Output, Fut>(future: Fut) -> Result<JoinHandle<Output>, SpawnError>
+    where
+        Fut: Future<Output = Output> + 'static,
+        Output: 'static,
+    {
+        AsyncStd.spawn_handle_local(future)
+    }
+}
+
+impl YieldNowStatic for AsyncStd {
+    fn yield_now() -> futures_util::future::BoxFuture<'static, ()> {
+        Box::pin(async_std_crate::task::yield_now())
+    }
 }
 
 impl std::fmt::Debug for AsyncStd {
@@ -114,17 +223,65 @@ impl std::fmt::Debug for AsyncStd {
 
 #[derive(Debug)]
 pub struct AsyncStdJoinHandle<T> {
-    task: Option<async_std_crate::task::JoinHandle<Result<T, Aborted>>>,
-    a_handle: AbortHandle,
+    task: Option<Task<T>>,
+}
+
+// Tasks spawned by this crate are made abortable, so dropping the handle can cancel them.
+// Tasks spawned elsewhere might not be.
+//
+#[derive(Debug)]
+enum Task<T> {
+    Abortable(
+        async_std_crate::task::JoinHandle<Result<T, Aborted>>,
+        AbortHandle,
+    ),
+    Plain(async_std_crate::task::JoinHandle<T>),
 }
+
 impl<T> AsyncStdJoinHandle<T> {
     pub fn new(
         task: async_std_crate::task::JoinHandle<Result<T, Aborted>>,
         a_handle: AbortHandle,
     ) -> Self {
         Self {
-            task: Some(task),
-            a_handle,
+            task: Some(Task::Abortable(task, a_handle)),
+        }
+    }
+
+    /// Wrap a task that was not made [abortable](futures_util::future::abortable). Since async-std
+    /// can not cancel it synchronously, dropping the handle detaches the task instead of cancelling it.
+    pub fn from_task(task: async_std_crate::task::JoinHandle<T>) -> Self {
+        Self {
+            task: Some(Task::Plain(task)),
+        }
+    }
+
+    /// Whether dropping the handle cancels the task, which is not the case for handles created with
+    /// [`from_task`](AsyncStdJoinHandle::from_task).
+    pub fn is_abortable(&self) -> bool {
+        !matches!(self.task, Some(Task::Plain(_)))
+    }
+
+    /// Access the native async-std handle. The output is wrapped in `Result` because the task
+    /// is made [abortable](futures_util::future::abortable). Returns `None` for handles created with
+    /// [`from_task`](AsyncStdJoinHandle::from_task).
+    pub fn inner(&self) -> Option<&async_std_crate::task::JoinHandle<Result<T, Aborted>>> {
+        match &self.task {
+            Some(Task::Abortable(task, _)) => Some(task),
+            _ => None,
+        }
+    }
+
+    // Like poll, but reports an aborted task instead of panicking.
+    //
+    pub(crate) fn poll_join(&mut self, cx: &mut Context<'_>) -> Poll<Result<T, Aborted>> {
+        match self
+            .task
+            .as_mut()
+            .expect("Cannot poll a detached JoinHandle twice")
+        {
+            Task::Abortable(task, _) => Pin::new(task).poll(cx),
+            Task::Plain(task) => Pin::new(task).poll(cx).map(Ok),
         }
     }
 }
@@ -132,17 +289,9 @@ impl<T> Future for AsyncStdJoinHandle<T> {
     type Output = T;
 
     fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
-        match futures_util::ready!(Pin::new(
-            self.task
-                .as_mut()
-                .expect("Cannot poll a detached JoinHandle twice")
-        )
-        .poll(cx))
-        {
+        match futures_util::ready!(self.poll_join(cx)) {
             Ok(x) => Poll::Ready(x),
-            Err(_) => {
-                panic!("Task has been aborted")
-            }
+            Err(_) => panic!("Task has been aborted"),
         }
     }
 }
@@ -156,13 +305,28 @@ impl<T> AsyncJoinHandle for AsyncStdJoinHandle<T> {
 }
 impl<T> Drop for AsyncStdJoinHandle<T> {
     fn drop(&mut self) {
-        if self.task.is_some() {
-            self.a_handle.abort();
+        if let Some(Task::Abortable(_, a_handle)) = &self.task {
+            a_handle.abort();
         }
     }
 }
 impl<T> Into<JoinHandle<T>> for AsyncStdJoinHandle<T> {
     fn into(self) -> JoinHandle<T> {
-        JoinHandle::AsyncStdJoinHandle(self)
+        JoinHandle::AsyncStdJoinHandle(self).with_default_drop()
     }
 }
+
+#[cfg(test)]
+//
+mod tests {
+    use super::*;
+
+    // The handle must be movable across threads, since the tasks are spawned on a threadpool.
+    //
+    static_assertions::assert_impl_all!(AsyncStdJoinHandle<()>: Send, Sync);
+    static_assertions::assert_impl_all!(JoinHandle<()>: Send, Sync);
+
+    // The executor is global, so the wrapper is a zero sized handle.
+    //
+    static_assertions::assert_impl_all!(AsyncStd: Send, Sync, Copy, Default);
+}
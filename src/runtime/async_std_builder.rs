@@ -0,0 +1,100 @@
+//! Provides AsyncStdBuilder to configure the global async-std threadpool.
+//
+use {
+    crate::AsyncStd,
+    async_global_executor::GlobalExecutorConfig,
+    std::{
+        io,
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc,
+        },
+    },
+};
+
+/// Builder to configure the threadpool of [`AsyncStd`] programmatically instead of through the
+/// `ASYNC_STD_THREAD_COUNT` and `ASYNC_STD_THREAD_NAME` environment variables.
+///
+/// The async-std threadpool is global and gets started lazily the first time something is spawned on
+/// it, whether through this crate or by calling async-std directly. This builder must thus be used
+/// before anything is spawned, otherwise [`build`](AsyncStdBuilder::build) returns an error as
+/// the configuration can no longer be applied.
+///
+/// When the `ASYNC_STD_THREAD_COUNT` environment variable is set, it still takes precedence over
+/// the thread count set here, like it does for async-std.
+///
+/// Not available on Wasm, as there is no threadpool there.
+//
+#[derive(Debug, Default)]
+//
+#[cfg_attr(
+    nightly,
+    doc(cfg(all(feature = "async_std", not(target_os = "unknown"))))
+)]
+//
+pub struct AsyncStdBuilder {
+    threads: Option<usize>,
+    name: Option<String>,
+}
+
+impl AsyncStdBuilder {
+    /// Constructor.
+    //
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of worker threads. Defaults to the number of logical cores.
+    //
+    pub fn threads(&mut self, threads: usize) -> &mut Self {
+        self.threads = Some(threads);
+        self
+    }
+
+    /// The name given to the worker threads. Defaults to `async-std/runtime`.
+    //
+    pub fn name(&mut self, name: impl Into<String>) -> &mut Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Start the threadpool with this configuration.
+    ///
+    /// Returns an error of kind [`AlreadyExists`](io::ErrorKind::AlreadyExists) if the threadpool
+    /// was already running.
+    //
+    pub fn build(&self) -> Result<AsyncStd, io::Error> {
+        let name = self
+            .name
+            .clone()
+            .unwrap_or_else(|| "async-std/runtime".to_string());
+
+        // The global executor silently ignores the config if it was already initialized, so we
+        // detect whether it was ours that spawned the threads.
+        //
+        let used = Arc::new(AtomicBool::new(false));
+        let used2 = used.clone();
+
+        let mut config = GlobalExecutorConfig::default()
+            .with_env_var("ASYNC_STD_THREAD_COUNT")
+            .with_thread_name_fn(move || {
+                used2.store(true, Ordering::SeqCst);
+                name.clone()
+            });
+
+        if let Some(threads) = self.threads {
+            config = config.with_min_threads(threads);
+        }
+
+        async_global_executor::init_with_config(config);
+
+        if !used.load(Ordering::SeqCst) {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                "the async-std threadpool is already running",
+            ));
+        }
+
+        Ok(AsyncStd)
+    }
+}
@@ -1,14 +1,24 @@
-use crate::{LocalSpawn, Spawn, SpawnError};
+use crate::{
+    AsyncJoinHandle, LocalSpawn, LocalSpawnExt, LocalSpawnHandleExt, LocalSpawnHandleStatic,
+    LocalSpawnStatic, Spawn, SpawnError, SpawnExt, SpawnHandleExt, SpawnHandleStatic, SpawnStatic,
+};
+use futures_util::future::{AbortHandle, Aborted, RemoteHandle};
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
 use {
     crate::{JoinHandle, LocalSpawnHandle, SpawnHandle},
     futures_task::{FutureObj, LocalFutureObj},
-    futures_util::FutureExt,
+    futures_util::{future::abortable, FutureExt},
     wasm_bindgen_futures::spawn_local,
 };
 
 /// A type that implements [`Spawn`], [`LocalSpawn`], [`SpawnHandle`](crate::SpawnHandle) and [`LocalSpawnHandle`](crate::LocalSpawnHandle).
 /// Spawns on the _wasm-bingen-futures_ executor. The executor is global, eg. not self contained
 /// and zero sized.
+///
+/// `Bindgen` is a zero sized type that is `Send`, `Sync` and `Copy`. It also implements the static traits,
+/// eg. [`SpawnStatic`] and [`LocalSpawnHandleStatic`], for code that spawns without a handle to an executor.
 //
 #[derive(Copy, Clone, Default)]
 //
@@ -45,10 +55,14 @@ impl<Out: 'static + Send> SpawnHandle<Out> for Bindgen {
         &self,
         future: FutureObj<'static, Out>,
     ) -> Result<JoinHandle<Out>, SpawnError> {
-        let (fut, handle) = future.remote_handle();
+        // wasm-bindgen-futures has no way to cancel a task, so we make it abortable
+        // in order to stop it from being polled when the JoinHandle is dropped.
+        //
+        let (fut, a_handle) = abortable(future);
+        let (fut, handle) = fut.remote_handle();
         spawn_local(fut);
 
-        Ok(handle.into())
+        Ok(BindgenJoinHandle::new(handle, a_handle).into())
     }
 }
 
@@ -57,10 +71,76 @@ impl<Out: 'static> LocalSpawnHandle<Out> for Bindgen {
         &self,
         future: LocalFutureObj<'static, Out>,
     ) -> Result<JoinHandle<Out>, SpawnError> {
-        let (fut, handle) = future.remote_handle();
+        // Like for SpawnHandle, make it abortable so dropping the JoinHandle stops the task.
+        //
+        let (fut, a_handle) = abortable(future);
+        let (fut, handle) = fut.remote_handle();
+        spawn_local(fut);
+
+        Ok(BindgenJoinHandle::new(handle, a_handle).into())
+    }
+}
+
+// The static traits forward to the methods above, so tasks spawned either way behave the same.
+//
+impl SpawnStatic for Bindgen {
+    fn spawn<Output, Fut>(future: Fut) -> Result<(), SpawnError>
+    where
+        Fut: Future<Output = Output> + Send + 'static,
+        Output: Send + 'static,
+    {
+        Bindgen.spawn(future.map(drop))
+    }
+}
+
+impl SpawnHandleStatic for Bindgen {
+    fn spawn_handle<Output, Fut>(future: Fut) -> Result<JoinHandle<Output>, SpawnError>
+    where
+        Fut: Future<Output = Output> + Send + 'static,
+        Output: 'static + Send,
+    {
+        Bindgen.spawn_handle(future)
+    }
+}
+
+impl LocalSpawnStatic for Bindgen {
+    fn spawn_local<Output, Fut>(future: Fut) -> Result<(), SpawnError>
+    where
+        Fut: Future<Output = Output> + 'static,
+        Output: 'static,
+    {
+        Bindgen.spawn_local(future.map(drop))
+    }
+}
+
+impl LocalSpawnHandleStatic for Bindgen {
+    fn spawn_handle_local<Output, Fut>(future: Fut) -> Result<JoinHandle<Output>, SpawnError>
+    where
+        Fut: Future<Output = Output> + 'static,
+        Output: 'static,
+    {
+        Bindgen.spawn_handle_local(future)
+    }
+}
+
+/// Uses the `setTimeout` of the browser. The timeout is cleared when the future is dropped before
+/// it fires. Browsers take the delay as an `i32`, so durations over about 24.8 days are clamped to that.
+//
+#[cfg(target_arch = "wasm32")]
+//
+impl crate::Timer for Bindgen {
+    fn sleep(&self, dur: std::time::Duration) -> futures_util::future::BoxFuture<'static, ()> {
+        // A larger delay overflows in `setTimeout` and fires right away.
+        //
+        let millis = std::cmp::min(dur.as_millis(), i32::MAX as u128) as u32;
+
+        // The timeout is not `Send`, so we run it in a local task and only hand out the
+        // remote handle. Dropping the handle drops the timeout, which clears it.
+        //
+        let (fut, handle) = gloo_timers::future::TimeoutFuture::new(millis).remote_handle();
         spawn_local(fut);
 
-        Ok(handle.into())
+        Box::pin(handle)
     }
 }
 
@@ -69,3 +149,77 @@ impl std::fmt::Debug for Bindgen {
         write!(f, "WASM Bindgen executor")
     }
 }
+
+/// The [`JoinHandle`] variant returned by [`Bindgen`]. Aborts the task when dropped, unless
+/// it has been detached.
+//
+#[derive(Debug)]
+pub struct BindgenJoinHandle<T> {
+    handle: Option<RemoteHandle<Result<T, Aborted>>>,
+    a_handle: AbortHandle,
+}
+impl<T> BindgenJoinHandle<T> {
+    /// Create a new handle from the [`RemoteHandle`] of an abortable future and it's [`AbortHandle`].
+    pub fn new(handle: RemoteHandle<Result<T, Aborted>>, a_handle: AbortHandle) -> Self {
+        Self {
+            handle: Some(handle),
+            a_handle,
+        }
+    }
+}
+impl<T: 'static> Future for BindgenJoinHandle<T> {
+    type Output = T;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match futures_util::ready!(Pin::new(
+            self.handle
+                .as_mut()
+                .expect("Cannot poll a detached JoinHandle twice")
+        )
+        .poll(cx))
+        {
+            Ok(x) => Poll::Ready(x),
+            Err(_) => {
+                panic!("Task has been aborted")
+            }
+        }
+    }
+}
+impl<T> BindgenJoinHandle<T> {
+    /// Drop the handle without aborting the task.
+    pub(crate) fn forget(mut self) {
+        if let Some(handle) = self.handle.take() {
+            handle.forget();
+        }
+    }
+}
+impl<T: 'static> AsyncJoinHandle for BindgenJoinHandle<T> {
+    fn detach(self)
+    where
+        Self: Sized,
+    {
+        self.forget()
+    }
+}
+impl<T> Drop for BindgenJoinHandle<T> {
+    fn drop(&mut self) {
+        if self.handle.is_some() {
+            self.a_handle.abort();
+        }
+    }
+}
+impl<T> From<BindgenJoinHandle<T>> for JoinHandle<T> {
+    fn from(handle: BindgenJoinHandle<T>) -> Self {
+        JoinHandle::BindgenJoinHandle(handle).with_default_drop()
+    }
+}
+
+#[cfg(test)]
+//
+mod tests {
+    use super::*;
+
+    // The executor is global, so the wrapper is a zero sized handle.
+    //
+    static_assertions::assert_impl_all!(Bindgen: Send, Sync, Copy, Default);
+}
@@ -0,0 +1,230 @@
+//! Provides the Compio executor and it's builder.
+//
+use crate::{
+    AsyncJoinHandle, BlockOn, JoinHandle, LocalSpawn, LocalSpawnHandle, Spawn, SpawnError,
+    SpawnHandle, YieldNow,
+};
+use compio_driver::ProactorBuilder;
+use compio_runtime::{JoinError, Runtime, RuntimeBuilder};
+use futures_task::{FutureObj, LocalFutureObj};
+use futures_util::future::BoxFuture;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll};
+
+/// An executor that uses a [compio](https://docs.rs/compio) runtime. Compio does completion based
+/// IO on top of io_uring on Linux and IOCP on Windows.
+///
+/// This is a current thread executor, like [`GlommioCt`](crate::GlommioCt). It can spawn `!Send` futures
+/// and tasks only make progress while a call to `block_on` is running.
+///
+/// ```
+/// use async_executors::{ BlockOn, CompioBuilder, LocalSpawnHandleExt };
+///
+/// let exec = CompioBuilder::new().build().expect( "create compio runtime" );
+///
+/// let result = exec.block_on( async
+/// {
+///    exec.spawn_handle_local( async { 5u8 } ).expect( "spawn" ).await
+/// });
+///
+/// assert_eq!( 5, result );
+/// ```
+///
+/// `Compio` can spawn `!Send` futures, so it's neither `Send` nor `Sync`. Clones share the same runtime.
+//
+#[derive(Debug, Clone)]
+//
+#[cfg_attr(nightly, doc(cfg(feature = "compio")))]
+//
+pub struct Compio {
+    rt: Rc<Runtime>,
+}
+
+impl Compio {
+    /// Create a compio runtime with the default configuration. See [`CompioBuilder`] for more options.
+    pub fn new() -> Result<Self, std::io::Error> {
+        CompioBuilder::new().build()
+    }
+
+    /// Run the future to completion on the current thread. Spawned tasks make progress
+    /// while this is running. See: [compio_runtime::Runtime::block_on].
+    pub fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.rt.block_on(future)
+    }
+}
+
+impl BlockOn for Compio {
+    fn block_on<F: Future>(&self, future: F) -> F::Output {
+        Self::block_on(self, future)
+    }
+}
+
+impl LocalSpawn for Compio {
+    fn spawn_local_obj(&self, future: LocalFutureObj<'static, ()>) -> Result<(), SpawnError> {
+        self.rt.spawn(future).detach();
+
+        Ok(())
+    }
+}
+
+impl<Out: 'static> LocalSpawnHandle<Out> for Compio {
+    fn spawn_handle_local_obj(
+        &self,
+        future: LocalFutureObj<'static, Out>,
+    ) -> Result<JoinHandle<Out>, SpawnError> {
+        Ok(CompioJoinHandle::new(self.rt.spawn(future)).into())
+    }
+}
+
+impl Spawn for Compio {
+    fn spawn_obj(&self, future: FutureObj<'static, ()>) -> Result<(), SpawnError> {
+        self.spawn_local_obj(LocalFutureObj::from(future))
+    }
+}
+
+impl<Out: Send + 'static> SpawnHandle<Out> for Compio {
+    fn spawn_handle_obj(
+        &self,
+        future: FutureObj<'static, Out>,
+    ) -> Result<JoinHandle<Out>, SpawnError> {
+        self.spawn_handle_local_obj(LocalFutureObj::from(future))
+    }
+}
+
+impl YieldNow for Compio {
+    fn yield_now<'a>(&'a self) -> BoxFuture<'a, ()> {
+        Box::pin(CompioYield { yielded: false })
+    }
+}
+
+// Compio does not provide a yield function, so we return pending once after waking ourselves up,
+// which puts the task at the back of the queue.
+//
+struct CompioYield {
+    yielded: bool,
+}
+
+impl Future for CompioYield {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.yielded {
+            return Poll::Ready(());
+        }
+
+        self.yielded = true;
+        cx.waker().wake_by_ref();
+
+        Poll::Pending
+    }
+}
+
+/// Builder to create a [`Compio`] executor.
+///
+/// Allows setting the queue depth of the proactor and gives access to the compio builders
+/// for the other options.
+//
+#[derive(Debug, Default)]
+//
+pub struct CompioBuilder {
+    builder: RuntimeBuilder,
+    proactor: ProactorBuilder,
+}
+
+impl CompioBuilder {
+    /// Constructor.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the queue depth of the proactor, eg. the number of entries of the io_uring submission queue.
+    /// See: [ProactorBuilder::capacity].
+    pub fn capacity(&mut self, capacity: u32) -> &mut Self {
+        self.proactor.capacity(capacity);
+        self
+    }
+
+    /// Returns the runtime builder from compio so you can configure it, see: [RuntimeBuilder].
+    /// The proactor configuration set on this builder takes precedence over [RuntimeBuilder::with_proactor].
+    pub fn compio_builder(&mut self) -> &mut RuntimeBuilder {
+        &mut self.builder
+    }
+
+    /// Returns the proactor builder from compio so you can configure it, see: [ProactorBuilder].
+    pub fn proactor_builder(&mut self) -> &mut ProactorBuilder {
+        &mut self.proactor
+    }
+
+    /// Create the actual executor.
+    pub fn build(&mut self) -> Result<Compio, std::io::Error> {
+        let rt = self.builder.with_proactor(self.proactor.clone()).build()?;
+
+        Ok(Compio { rt: Rc::new(rt) })
+    }
+}
+
+/// The [`JoinHandle`] variant returned by [`Compio`]. Cancels the task when dropped, unless
+/// it has been detached.
+//
+#[derive(Debug)]
+pub struct CompioJoinHandle<T> {
+    handle: Option<compio_runtime::JoinHandle<T>>,
+}
+impl<T> CompioJoinHandle<T> {
+    /// Wrap a compio join handle.
+    pub fn new(handle: compio_runtime::JoinHandle<T>) -> Self {
+        Self {
+            handle: Some(handle),
+        }
+    }
+}
+impl<T> Future for CompioJoinHandle<T> {
+    type Output = T;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let handle = self
+            .handle
+            .as_mut()
+            .expect("Cannot poll after completion/detach");
+
+        match futures_util::ready!(Pin::new(handle).poll(cx)) {
+            Ok(t) => {
+                self.handle = None;
+                Poll::Ready(t)
+            }
+            Err(JoinError::Panicked(payload)) => std::panic::resume_unwind(payload),
+            Err(JoinError::Cancelled) => panic!("Compio runtime ended before joining"),
+        }
+    }
+}
+impl<T> AsyncJoinHandle for CompioJoinHandle<T> {
+    fn detach(mut self)
+    where
+        Self: Sized,
+    {
+        if let Some(handle) = self.handle.take() {
+            handle.detach();
+        }
+    }
+}
+impl<T> From<CompioJoinHandle<T>> for JoinHandle<T> {
+    fn from(handle: CompioJoinHandle<T>) -> Self {
+        JoinHandle::CompioJoinHandle(handle).with_default_drop()
+    }
+}
+
+#[cfg(test)]
+//
+mod tests {
+    use super::*;
+
+    // It's important that this is not Send, as we allow spawning !Send futures on it.
+    //
+    static_assertions::assert_not_impl_any!(Compio: Send, Sync);
+
+    // Clones share the runtime.
+    //
+    static_assertions::assert_impl_all!(Compio: Clone);
+}
@@ -0,0 +1,194 @@
+//! Provides CurrentThread, an executor that doesn't need a runtime.
+//
+use crate::{
+    BlockOn, JoinHandle, LocalSpawn, LocalSpawnHandle, Spawn, SpawnError, SpawnHandle, YieldNow,
+};
+use futures_executor::{LocalPool, LocalSpawner};
+use futures_task::{FutureObj, LocalFutureObj, LocalSpawn as _};
+use futures_util::future::{BoxFuture, FutureExt};
+use std::{
+    cell::RefCell,
+    fmt,
+    future::Future,
+    pin::Pin,
+    rc::Rc,
+    task::{Context, Poll},
+};
+
+/// A single threaded executor that doesn't need a runtime, built on [`futures_executor::LocalPool`].
+/// It implements the spawn traits, so it can be used with code that is generic over them, eg. in tests
+/// or in constrained environments where pulling in a full runtime isn't an option.
+///
+/// Spawned tasks only make progress while [`block_on`](BlockOn::block_on) or
+/// [`run_until_stalled`](CurrentThread::run_until_stalled) runs. There is no IO or timer driver, so
+/// futures have to be woken up by something else, eg. channels, other tasks or another executor.
+///
+/// Since it can spawn `!Send` futures, `CurrentThread` is `!Send`. Clones spawn on the same pool.
+///
+/// Not available on Wasm, as you're not allowed to block the only thread you have.
+///
+/// ```
+/// use
+/// {
+///    async_executors :: { BlockOn, CurrentThread, LocalSpawnHandleExt } ,
+///    std             :: { rc::Rc                                      } ,
+/// };
+///
+/// let exec     = CurrentThread::new();
+/// let not_send = Rc::new( 5u8 );
+///
+/// let join_handle = exec.spawn_handle_local( async move { *not_send } ).expect( "spawn" );
+///
+/// assert_eq!( 5, exec.block_on( join_handle ) );
+/// ```
+//
+#[derive(Clone)]
+//
+#[cfg_attr(
+    nightly,
+    doc(cfg(all(feature = "block_on", not(target_os = "unknown"))))
+)]
+//
+pub struct CurrentThread {
+    pool: Rc<RefCell<LocalPool>>,
+    spawner: LocalSpawner,
+}
+
+impl CurrentThread {
+    /// Create a new executor.
+    //
+    pub fn new() -> Self {
+        let pool = LocalPool::new();
+        let spawner = pool.spawner();
+
+        Self {
+            pool: Rc::new(RefCell::new(pool)),
+            spawner,
+        }
+    }
+
+    /// Run the spawned tasks until none of them can make progress anymore, without blocking.
+    ///
+    /// # Panics
+    ///
+    /// When called from within a task on this executor, or from within another executor.
+    //
+    pub fn run_until_stalled(&self) {
+        self.pool().run_until_stalled()
+    }
+
+    fn pool(&self) -> std::cell::RefMut<'_, LocalPool> {
+        self.pool
+            .try_borrow_mut()
+            .expect("CurrentThread: can not run the executor from within one of it's tasks")
+    }
+}
+
+impl Default for CurrentThread {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for CurrentThread {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "CurrentThread")
+    }
+}
+
+/// # Panics
+///
+/// When called from within a task on this executor, or from within another executor.
+//
+impl BlockOn for CurrentThread {
+    fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.pool().run_until(future)
+    }
+}
+
+impl LocalSpawn for CurrentThread {
+    fn spawn_local_obj(&self, future: LocalFutureObj<'static, ()>) -> Result<(), SpawnError> {
+        self.spawner
+            .spawn_local_obj(future)
+            .map_err(SpawnError::from)
+    }
+}
+
+impl<Out: 'static> LocalSpawnHandle<Out> for CurrentThread {
+    fn spawn_handle_local_obj(
+        &self,
+        future: LocalFutureObj<'static, Out>,
+    ) -> Result<JoinHandle<Out>, SpawnError> {
+        let (fut, handle) = future.remote_handle();
+
+        self.spawn_local_obj(LocalFutureObj::new(fut.boxed_local()))?;
+
+        Ok(handle.into())
+    }
+}
+
+impl Spawn for CurrentThread {
+    fn spawn_obj(&self, future: FutureObj<'static, ()>) -> Result<(), SpawnError> {
+        self.spawn_local_obj(LocalFutureObj::from(future))
+    }
+}
+
+impl<Out: Send + 'static> SpawnHandle<Out> for CurrentThread {
+    fn spawn_handle_obj(
+        &self,
+        future: FutureObj<'static, Out>,
+    ) -> Result<JoinHandle<Out>, SpawnError> {
+        self.spawn_handle_local_obj(LocalFutureObj::from(future))
+    }
+}
+
+impl YieldNow for CurrentThread {
+    fn yield_now<'a>(&'a self) -> BoxFuture<'a, ()> {
+        Box::pin(YieldOnce { yielded: false })
+    }
+}
+
+// Return pending once after waking ourselves up, which puts the task at the back of the queue
+// of the pool.
+//
+struct YieldOnce {
+    yielded: bool,
+}
+
+impl Future for YieldOnce {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.yielded {
+            return Poll::Ready(());
+        }
+
+        self.yielded = true;
+        cx.waker().wake_by_ref();
+
+        Poll::Pending
+    }
+}
+
+/// Run `future` to completion on the current thread with [`futures_executor::block_on`]. Use this
+/// when you don't need to spawn, otherwise see [`CurrentThread`].
+//
+#[cfg_attr(
+    nightly,
+    doc(cfg(all(feature = "block_on", not(target_os = "unknown"))))
+)]
+//
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    futures_executor::block_on(future)
+}
+
+#[cfg(test)]
+//
+mod tests {
+    use super::*;
+
+    // It can spawn !Send futures, so it must not be sent to another thread.
+    //
+    static_assertions::assert_impl_all!(CurrentThread: Clone, Default);
+    static_assertions::assert_not_impl_any!(CurrentThread: Send, Sync);
+}
@@ -0,0 +1,61 @@
+// The cargo features that provide an executor, in alphabetical order.
+//
+const RUNTIMES: [(&str, bool); 11] = [
+    ("async_global", cfg!(feature = "async_global")),
+    ("async_std", cfg!(feature = "async_std")),
+    ("bindgen", cfg!(feature = "bindgen")),
+    ("block_on", cfg!(feature = "block_on")),
+    ("compio", cfg!(feature = "compio")),
+    ("glommio", cfg!(feature = "glommio")),
+    ("localpool", cfg!(feature = "localpool")),
+    ("rayon", cfg!(feature = "rayon")),
+    ("threadpool", cfg!(feature = "threadpool")),
+    ("tokio_ct", cfg!(feature = "tokio_ct")),
+    ("tokio_tp", cfg!(feature = "tokio_tp")),
+];
+
+const ENABLED_COUNT: usize = {
+    let mut count = 0;
+    let mut i = 0;
+
+    while i < RUNTIMES.len() {
+        if RUNTIMES[i].1 {
+            count += 1;
+        }
+
+        i += 1;
+    }
+
+    count
+};
+
+const ENABLED: [&str; ENABLED_COUNT] = {
+    let mut enabled = [""; ENABLED_COUNT];
+    let mut next = 0;
+    let mut i = 0;
+
+    while i < RUNTIMES.len() {
+        if RUNTIMES[i].1 {
+            enabled[next] = RUNTIMES[i].0;
+            next += 1;
+        }
+
+        i += 1;
+    }
+
+    enabled
+};
+
+/// The names of the cargo features of this crate that enable an executor, for the features this
+/// crate was compiled with. Handy for diagnostics and bug reports. The list is computed at compile
+/// time and sorted alphabetically.
+///
+/// ```rust
+/// let runtimes = async_executors::enabled_runtimes();
+///
+/// println!("Executors: {}", runtimes.join(", "));
+/// ```
+//
+pub const fn enabled_runtimes() -> &'static [&'static str] {
+    &ENABLED
+}
@@ -1,39 +1,157 @@
+use crate::runtime::glommio_static::spawn_task;
+use crate::GlommioCtBuilder;
 use crate::{
-    BlockOn, CoreAffinityGuard, JoinHandle, LocalSpawn, LocalSpawnHandle, Spawn, SpawnError,
-    SpawnHandle, SpawnHandleStatic,
+    BlockOn, CoreAffinityGuard, JoinHandle, LocalSpawn, LocalSpawnHandle, LocalTimer, Spawn,
+    SpawnError, SpawnErrorKind, SpawnHandle,
 };
 use crate::{Glommio, LocalSpawnHandleStatic};
 use futures_task::FutureObj;
-use futures_util::future::LocalFutureObj;
-use glommio_crate::{LocalExecutor, LocalExecutorBuilder};
+use futures_util::future::{LocalBoxFuture, LocalFutureObj};
+use futures_util::FutureExt;
+use glommio_crate::{Latency, LocalExecutor, Shares, Task, TaskQueueHandle};
+use std::cell::Cell;
 use std::future::Future;
 use std::rc::Rc;
+use std::time::{Duration, Instant};
 
-/// A simple glommio runtime builder
+/// A glommio executor on the current thread. Create it with [`GlommioCtBuilder`].
+///
+/// `GlommioCt` can spawn `!Send` futures, so it's neither `Send` nor `Sync`. Clones share the same executor.
+//
 #[derive(Debug, Clone)]
 pub struct GlommioCt {
-    guard: Rc<CoreAffinityGuard>,
-    executor: Rc<LocalExecutor>,
+    pub(crate) tasks: Rc<Cell<usize>>,
+    pub(crate) guard: Rc<CoreAffinityGuard>,
+    pub(crate) executor: Rc<LocalExecutor>,
 }
 
 impl GlommioCt {
-    /// new Glommio Local Executor
+    /// new Glommio Local Executor. Use [`GlommioCtBuilder`] for more options.
     pub fn new(name: &str, cpu_set: Option<usize>) -> Self {
-        let guard = Rc::new(CoreAffinityGuard::new().unwrap());
-        let mut builder = LocalExecutorBuilder::new().name(&name);
+        let mut builder = GlommioCtBuilder::new();
+        builder.name(name);
         if let Some(binding) = cpu_set {
-            builder = builder.pin_to_cpu(binding);
-        }
-        let executor = builder.make().unwrap();
-        Self {
-            guard,
-            executor: Rc::new(executor),
+            builder.pin_to_cpu(binding);
         }
+        builder.build().unwrap()
     }
     /// execute the code until completion
     pub fn block_on<F: Future>(&self, future: F) -> <F as Future>::Output {
-        let val = self.executor.run(future);
-        val
+        Glommio::run(&self.executor, future)
+    }
+
+    /// Spawn a local task that keeps running when the returned handle is dropped. You can still
+    /// await the handle for the output, or call [`detach`](JoinHandle::detach) on it when you
+    /// know you won't need it.
+    ///
+    /// This must be called from within [`block_on`](GlommioCt::block_on), otherwise it returns an error.
+    pub fn spawn_local_detached<Out: 'static>(
+        &self,
+        future: impl Future<Output = Out> + 'static,
+    ) -> Result<JoinHandle<Out>, SpawnError> {
+        Ok(Glommio::spawn_handle_local(self.track(future))?.detach_on_drop())
+    }
+
+    /// Create a task queue on this executor with the given share of the cpu time and latency
+    /// requirements. Spawn on it with [`spawn_handle_into`](GlommioCt::spawn_handle_into).
+    /// See [`Task::create_task_queue`].
+    ///
+    /// This must be called from within [`block_on`](GlommioCt::block_on).
+    pub fn create_task_queue(
+        &self,
+        shares: Shares,
+        latency: Latency,
+        name: &str,
+    ) -> TaskQueueHandle {
+        Task::<()>::create_task_queue(shares, latency, name)
+    }
+
+    /// Spawn a local task on the given task queue, so it gets scheduled according to the shares
+    /// and latency of that queue instead of those of the default queue.
+    ///
+    /// This must be called from within [`block_on`](GlommioCt::block_on), otherwise it returns an error.
+    /// Returns an error of kind [`Unsupported`](SpawnErrorKind::Unsupported), with the error from glommio
+    /// as source, when the queue does not exist on this executor.
+    pub fn spawn_handle_into<Out: 'static>(
+        &self,
+        queue: TaskQueueHandle,
+        future: impl Future<Output = Out> + 'static,
+    ) -> Result<JoinHandle<Out>, SpawnError> {
+        Glommio::status()?;
+
+        let (remote, handle) = self.track(future).remote_handle();
+
+        Task::local_into(remote, queue)
+            .map_err(|e| SpawnError::with_kind(SpawnErrorKind::Unsupported, e))?
+            .detach();
+
+        Ok(handle.into())
+    }
+
+    /// Wait for `dur` with the timer of glommio. This must be awaited from within
+    /// [`block_on`](GlommioCt::block_on). The future is not `Send`, see [`LocalTimer`].
+    pub async fn sleep(&self, dur: Duration) {
+        glommio_crate::timer::sleep(dur).await
+    }
+
+    /// Wait until `deadline`. Resolves right away if it has already passed.
+    pub async fn sleep_until(&self, deadline: Instant) {
+        self.sleep(deadline.saturating_duration_since(Instant::now()))
+            .await
+    }
+
+    /// Stop the executor and restore the cpu affinity the thread had before it was created, so a new
+    /// executor can be created on this thread. Returns how many of the tasks spawned through this
+    /// executor had not completed yet. Those are dropped with the executor.
+    ///
+    /// This works only if no other clones are around. If this is not the only reference, self will be
+    /// returned to you as an error. Dropping the last clone does the same, this just lets you know
+    /// when it happened.
+    //
+    pub fn shutdown(self) -> Result<usize, Self> {
+        if Rc::strong_count(&self.executor) > 1 {
+            return Err(self);
+        }
+
+        let GlommioCt {
+            tasks,
+            guard,
+            executor,
+        } = self;
+
+        let pending = tasks.get();
+
+        // The affinity guard is only released once the executor is gone.
+        drop(executor);
+        drop(guard);
+
+        Ok(pending)
+    }
+
+    fn track<F: Future>(&self, future: F) -> impl Future<Output = F::Output> {
+        let task = TaskGuard::new(&self.tasks);
+
+        async move {
+            let _task = task;
+            future.await
+        }
+    }
+}
+
+// Keeps count of the tasks that are alive, so shutdown can report how many did not complete.
+//
+struct TaskGuard(Rc<Cell<usize>>);
+
+impl TaskGuard {
+    fn new(tasks: &Rc<Cell<usize>>) -> Self {
+        tasks.set(tasks.get() + 1);
+        Self(tasks.clone())
+    }
+}
+
+impl Drop for TaskGuard {
+    fn drop(&mut self) {
+        self.0.set(self.0.get() - 1);
     }
 }
 
@@ -45,9 +163,14 @@ impl BlockOn for GlommioCt {
 
 impl LocalSpawn for GlommioCt {
     fn spawn_local_obj(&self, future: LocalFutureObj<'static, ()>) -> Result<(), SpawnError> {
-        glommio_crate::Task::local(future).detach();
+        spawn_task(self.track(future))?.detach();
         Ok(())
     }
+
+    /// Glommio can only spawn from within [`block_on`](GlommioCt::block_on), see [`Glommio::status`].
+    fn status_local(&self) -> Result<(), SpawnError> {
+        Glommio::status()
+    }
 }
 
 impl<Out: 'static> LocalSpawnHandle<Out> for GlommioCt {
@@ -55,7 +178,7 @@ impl<Out: 'static> LocalSpawnHandle<Out> for GlommioCt {
         &self,
         future: LocalFutureObj<'static, Out>,
     ) -> Result<JoinHandle<Out>, SpawnError> {
-        Glommio::spawn_handle_local(future)
+        Glommio::spawn_handle_local(self.track(future))
     }
 }
 
@@ -63,6 +186,10 @@ impl Spawn for GlommioCt {
     fn spawn_obj(&self, future: FutureObj<'static, ()>) -> Result<(), SpawnError> {
         self.spawn_local_obj(LocalFutureObj::from(future))
     }
+
+    fn status(&self) -> Result<(), SpawnError> {
+        Glommio::status()
+    }
 }
 
 impl<Out: Send + 'static> SpawnHandle<Out> for GlommioCt {
@@ -70,7 +197,14 @@ impl<Out: Send + 'static> SpawnHandle<Out> for GlommioCt {
         &self,
         future: FutureObj<'static, Out>,
     ) -> Result<JoinHandle<Out>, SpawnError> {
-        Glommio::spawn_handle(future)
+        // The task counter is not Send, but the task stays on this thread anyway.
+        Glommio::spawn_handle_local(self.track(future))
+    }
+}
+
+impl LocalTimer for GlommioCt {
+    fn sleep_local(&self, dur: Duration) -> LocalBoxFuture<'static, ()> {
+        Glommio.sleep_local(dur)
     }
 }
 
@@ -82,4 +216,8 @@ mod tests {
     // It's important that this is not Send, as we allow spawning !Send futures on it.
     //
     static_assertions::assert_not_impl_any!(GlommioCt: Send, Sync);
+
+    // Clones share the executor.
+    //
+    static_assertions::assert_impl_all!(GlommioCt: Clone);
 }
@@ -0,0 +1,204 @@
+//! Provides GlommioCtBuilder to configure the glommio reactor behind GlommioCt.
+//
+use {
+    crate::{
+        BlockOn, CoreAffinityGuard, GlommioCt, JoinHandle, LocalSpawn, LocalSpawnHandle, SpawnError,
+    },
+    futures_task::LocalFutureObj,
+    glommio_crate::LocalExecutorBuilder,
+    std::{cell::RefCell, future::Future, io, rc::Rc, time::Duration},
+};
+
+/// Builder to create a [`GlommioCt`] executor. Forwards the options to glommio's
+/// [LocalExecutorBuilder], see there for the defaults.
+///
+/// [`build`](GlommioCtBuilder::build) creates the executor on the current thread, so the thread
+/// name and stack size can not be applied. Use [`spawn`](GlommioCtBuilder::spawn) to run the
+/// executor on a new thread that gets the configured name and stack size.
+///
+/// The builder also implements [`BlockOn`]. The first call creates an executor which is kept by
+/// the builder and reused by later calls, so tasks spawned during one `block_on` survive until the
+/// next one, like with [`GlommioCt::block_on`]. Since it holds on to a thread local executor, the
+/// builder is not `Send`. Options changed after the first `block_on` only apply to executors
+/// created afterwards by [`build`](GlommioCtBuilder::build) or [`spawn`](GlommioCtBuilder::spawn).
+///
+/// [`LocalSpawn`] and [`LocalSpawnHandle`] spawn on that same executor, so from within the future
+/// passed to `block_on` the builder can be used as an executor. Before the first `block_on` there
+/// is no executor to spawn on, and spawning returns a [`SpawnError`] of kind
+/// [`Unsupported`](crate::SpawnErrorKind::Unsupported).
+//
+#[derive(Debug, Clone, Default)]
+//
+pub struct GlommioCtBuilder {
+    config: Config,
+    executor: RefCell<Option<GlommioCt>>,
+}
+
+// The options, kept apart from the cached executor so they can be sent to the thread created by spawn.
+//
+#[derive(Debug, Clone, Default)]
+//
+struct Config {
+    name: Option<String>,
+    pin_to_cpu: Option<usize>,
+    io_memory: Option<usize>,
+    preempt_timer: Option<Duration>,
+    stack_size: Option<usize>,
+}
+
+impl GlommioCtBuilder {
+    /// Constructor.
+    //
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The name of the executor.
+    //
+    pub fn name(&mut self, name: impl Into<String>) -> &mut Self {
+        self.config.name = Some(name.into());
+        self
+    }
+
+    /// Pin the executor to the given cpu.
+    //
+    pub fn pin_to_cpu(&mut self, cpu: usize) -> &mut Self {
+        self.config.pin_to_cpu = Some(cpu);
+        self
+    }
+
+    /// The amount of memory in bytes reserved for the io_uring buffers of the reactor.
+    //
+    pub fn io_memory(&mut self, io_memory: usize) -> &mut Self {
+        self.config.io_memory = Some(io_memory);
+        self
+    }
+
+    /// How long a task queue can run before it has to yield to the others. Lower values
+    /// improve latency at the cost of throughput.
+    //
+    pub fn preempt_timer(&mut self, preempt_timer: Duration) -> &mut Self {
+        self.config.preempt_timer = Some(preempt_timer);
+        self
+    }
+
+    /// The stack size of the thread created by [`spawn`](GlommioCtBuilder::spawn). Defaults to the
+    /// default of [std::thread::Builder].
+    //
+    pub fn spawn_stack_size(&mut self, stack_size: usize) -> &mut Self {
+        self.config.stack_size = Some(stack_size);
+        self
+    }
+
+    /// Create the actual executor.
+    //
+    pub fn build(&self) -> Result<GlommioCt, io::Error> {
+        self.config.build()
+    }
+
+    /// Run `future` to completion on the executor owned by this builder, creating it on the first call.
+    ///
+    /// # Panics
+    ///
+    /// When the executor can not be created.
+    //
+    pub fn block_on<F: Future>(&self, future: F) -> F::Output {
+        let exec = self
+            .executor
+            .borrow_mut()
+            .get_or_insert_with(|| self.build().expect("create glommio executor"))
+            .clone();
+
+        exec.block_on(future)
+    }
+
+    /// Spawn a new thread, named after the executor, create the executor on it and pass it to `f`.
+    /// Typically `f` will call [`block_on`](GlommioCt::block_on) to run your program.
+    ///
+    /// If creating the executor fails, the error is returned when joining the thread.
+    //
+    pub fn spawn<T, F>(&self, f: F) -> Result<std::thread::JoinHandle<io::Result<T>>, io::Error>
+    where
+        F: FnOnce(GlommioCt) -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        let mut thread = std::thread::Builder::new();
+
+        if let Some(name) = &self.config.name {
+            thread = thread.name(name.clone());
+        }
+
+        if let Some(stack_size) = self.config.stack_size {
+            thread = thread.stack_size(stack_size);
+        }
+
+        let config = self.config.clone();
+
+        thread.spawn(move || Ok(f(config.build()?)))
+    }
+}
+
+impl BlockOn for GlommioCtBuilder {
+    fn block_on<F: Future>(&self, future: F) -> F::Output {
+        Self::block_on(self, future)
+    }
+}
+
+impl GlommioCtBuilder {
+    fn executor(&self) -> Result<GlommioCt, SpawnError> {
+        self.executor
+            .borrow()
+            .clone()
+            .ok_or_else(SpawnError::unsupported)
+    }
+}
+
+impl LocalSpawn for GlommioCtBuilder {
+    fn spawn_local_obj(&self, future: LocalFutureObj<'static, ()>) -> Result<(), SpawnError> {
+        self.executor()?.spawn_local_obj(future)
+    }
+
+    fn status_local(&self) -> Result<(), SpawnError> {
+        self.executor()?.status_local()
+    }
+}
+
+impl<Out: 'static> LocalSpawnHandle<Out> for GlommioCtBuilder {
+    fn spawn_handle_local_obj(
+        &self,
+        future: LocalFutureObj<'static, Out>,
+    ) -> Result<JoinHandle<Out>, SpawnError> {
+        self.executor()?.spawn_handle_local_obj(future)
+    }
+}
+
+impl Config {
+    fn build(&self) -> Result<GlommioCt, io::Error> {
+        let mut builder = LocalExecutorBuilder::new();
+
+        if let Some(name) = &self.name {
+            builder = builder.name(name);
+        }
+
+        if let Some(cpu) = self.pin_to_cpu {
+            builder = builder.pin_to_cpu(cpu);
+        }
+
+        if let Some(io_memory) = self.io_memory {
+            builder = builder.io_memory(io_memory);
+        }
+
+        if let Some(preempt_timer) = self.preempt_timer {
+            builder = builder.preempt_timer(preempt_timer);
+        }
+
+        let guard = Rc::new(CoreAffinityGuard::new()?);
+        let executor = Rc::new(builder.make()?);
+
+        Ok(GlommioCt {
+            tasks: Rc::default(),
+            guard,
+            executor,
+        })
+    }
+}
@@ -1,27 +1,87 @@
 use crate::{
-    JoinHandle, LocalSpawnHandleStatic, LocalSpawnStatic, SpawnBlockingStatic, SpawnError,
-    SpawnHandleStatic, SpawnStatic, YieldNowStatic,
+    JoinHandle, LocalSpawnHandleStatic, LocalSpawnStatic, LocalTimer, SpawnBlockingStatic,
+    SpawnError, SpawnHandleStatic, SpawnStatic, YieldNowStatic,
 };
-use futures_util::future::BoxFuture;
+use futures_util::future::{BoxFuture, LocalBoxFuture};
 use futures_util::FutureExt;
-use glommio_crate::Task;
+use glommio_crate::{LocalExecutor, Task};
 use nix::sched::CpuSet;
 use std::cell::Cell;
 use std::future::Future;
 use std::marker::PhantomData;
 use std::rc::Rc;
 
-/// A simple glommio runtime builder
+/// Static access to the glommio executor running on the current thread.
+///
+/// `Glommio` is a zero sized type that is `Send`, `Sync` and `Copy`.
+//
 #[derive(Debug, Clone, Copy, Default)]
 pub struct Glommio;
 
+thread_local! {
+    // How many glommio executors started through Glommio::run are running on this thread. Glommio
+    // does not tell, and spawning without one panics.
+    static RUNNING: Cell<usize> = const { Cell::new(0) };
+}
+
+// Decrements RUNNING when the executor returns, also when it unwinds.
+//
+struct Running;
+
+impl Running {
+    fn enter() -> Self {
+        RUNNING.with(|r| r.set(r.get() + 1));
+        Self
+    }
+}
+
+impl Drop for Running {
+    fn drop(&mut self) {
+        RUNNING.with(|r| r.set(r.get() - 1));
+    }
+}
+
+// Spawn on the glommio executor of this thread. Task::local panics when there is none, so check first.
+//
+pub(crate) fn spawn_task<T: 'static>(
+    future: impl Future<Output = T> + 'static,
+) -> Result<Task<T>, SpawnError> {
+    Glommio::status()?;
+    Ok(Task::local(future))
+}
+
+impl Glommio {
+    /// Run `future` to completion on `executor`. Use this instead of [`LocalExecutor::run`] when you
+    /// create the executor yourself, otherwise spawning through [`Glommio`] fails, see
+    /// [`status`](Glommio::status). [`GlommioCt`](crate::GlommioCt) and [`GlommioTp`](crate::GlommioTp)
+    /// do this for you.
+    pub fn run<F: Future>(executor: &LocalExecutor, future: F) -> F::Output {
+        let _running = Running::enter();
+        executor.run(future)
+    }
+
+    /// Whether a glommio executor is running on the current thread, so tasks can be spawned.
+    /// Returns an error of kind [`Unsupported`](crate::SpawnErrorKind::Unsupported) otherwise. The
+    /// spawn methods return that error too, instead of panicking.
+    ///
+    /// Glommio does not expose this, so only executors started through [`run`](Glommio::run) are
+    /// known. An executor run directly with [`LocalExecutor::run`] counts as not running.
+    pub fn status() -> Result<(), SpawnError> {
+        if RUNNING.with(Cell::get) > 0 {
+            Ok(())
+        } else {
+            Err(SpawnError::unsupported())
+        }
+    }
+}
+
 impl LocalSpawnStatic for Glommio {
     fn spawn_local<Output, Fut>(future: Fut) -> Result<(), SpawnError>
     where
         Fut: Future<Output = Output> + 'static,
         Output: 'static,
     {
-        glommio_crate::Task::local(future).detach();
+        spawn_task(future)?.detach();
         Ok(())
     }
 }
@@ -33,7 +93,7 @@ impl LocalSpawnHandleStatic for Glommio {
         Output: 'static,
     {
         let (remote, handle) = future.remote_handle();
-        Task::local(remote).detach();
+        spawn_task(remote)?.detach();
         Ok(handle.into())
     }
 }
@@ -44,7 +104,7 @@ impl SpawnStatic for Glommio {
         Fut: Future<Output = Output> + Send + 'static,
         Output: Send + 'static,
     {
-        glommio_crate::Task::local(future).detach();
+        spawn_task(future)?.detach();
         Ok(())
     }
 }
@@ -56,7 +116,7 @@ impl SpawnHandleStatic for Glommio {
         Output: 'static + Send,
     {
         let (remote, handle) = future.remote_handle();
-        glommio_crate::Task::local(remote).detach();
+        spawn_task(remote)?.detach();
         Ok(handle.into())
     }
 }
@@ -66,6 +126,14 @@ impl YieldNowStatic for Glommio {
     }
 }
 
+// Glommio timers belong to the executor of the current thread, so they are not `Send`.
+//
+impl LocalTimer for Glommio {
+    fn sleep_local(&self, dur: std::time::Duration) -> LocalBoxFuture<'static, ()> {
+        Box::pin(glommio_crate::timer::sleep(dur))
+    }
+}
+
 impl SpawnBlockingStatic for Glommio {
     fn spawn_blocking<T: Send + 'static>(
         func: impl FnOnce() -> T + Send + 'static,
@@ -97,17 +165,29 @@ macro_rules! to_io_error {
         }
     }};
 }
-fn bind_to_cpu_set(cpuset: CpuSet) -> std::io::Result<()> {
-    let pid = nix::unistd::Pid::this();
+// 0 is the calling thread. Pid::this() would be the main thread of the process.
+//
+pub(crate) fn bind_to_cpu_set(cpuset: CpuSet) -> std::io::Result<()> {
+    let pid = nix::unistd::Pid::from_raw(0);
     to_io_error!(nix::sched::sched_setaffinity(pid, &cpuset))
 }
 
+pub(crate) fn current_cpu_set() -> std::io::Result<CpuSet> {
+    let pid = nix::unistd::Pid::from_raw(0);
+    to_io_error!(nix::sched::sched_getaffinity(pid))
+}
+
+pub(crate) fn single_cpu_set(cpu: usize) -> std::io::Result<CpuSet> {
+    let mut set = CpuSet::new();
+    to_io_error!(set.set(cpu))?;
+    Ok(set)
+}
+
 thread_local! {
     static DEFAULT_CPU_SET: Cell<Option<CpuSet>> = Cell::new(None);
 }
 fn set_default_cpu() -> std::io::Result<()> {
-    let pid = nix::unistd::Pid::this();
-    let set = to_io_error!(nix::sched::sched_getaffinity(pid))?;
+    let set = current_cpu_set()?;
 
     DEFAULT_CPU_SET.with(|x| match x.clone().into_inner() {
         Some(_) => {
@@ -140,3 +220,13 @@ impl Drop for CoreAffinityGuard {
         clean_default_cpu().unwrap();
     }
 }
+
+#[cfg(test)]
+//
+mod tests {
+    use super::*;
+
+    // A zero sized handle to the executor of the current thread.
+    //
+    static_assertions::assert_impl_all!(Glommio: Send, Sync, Copy, Default);
+}
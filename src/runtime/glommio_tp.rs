@@ -75,7 +75,7 @@ impl GlommioTpBuilder {
                 .name(name)
                 .spawn(move || {
                     let guard = CoreAffinityGuard::new().unwrap();
-                    t.make().unwrap().run(async move { e.run().await });
+                    Glommio::run(&t.make().unwrap(), async move { e.run().await });
                     drop(guard);
                 })
                 .unwrap();
@@ -182,6 +182,9 @@ impl<T> From<crossbeam::channel::SendError<T>> for GlommioError {
 }
 
 /// A ThreadPooled Glommio Runtime with work stealing algorithm
+///
+/// `GlommioTp` is `Send` and `Sync`, but not `Clone`, as it owns the worker threads. Put it in an
+/// `Arc` to share it.
 #[derive(Debug)]
 pub struct GlommioTp {
     join_handles: Mutex<Vec<std::thread::JoinHandle<()>>>,
@@ -299,3 +302,14 @@ impl<Out: Send + 'static> LocalSpawnHandle<Out> for GlommioTp {
         Glommio::spawn_handle_local(future)
     }
 }
+
+#[cfg(test)]
+//
+mod tests {
+    use super::*;
+
+    // It owns the worker threads, share it with an Arc.
+    //
+    static_assertions::assert_impl_all!(GlommioTp: Send, Sync);
+    static_assertions::assert_not_impl_any!(GlommioTp: Clone);
+}
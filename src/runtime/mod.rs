@@ -15,12 +15,36 @@ mod tokio_tp_builder;
 pub use tokio_tp::*;
 #[cfg(feature = "tokio_tp")]
 pub use tokio_tp_builder::*;
+#[cfg(feature = "tokio_tp")]
+mod tokio_local_worker;
+#[cfg(feature = "tokio_tp")]
+pub use tokio_local_worker::*;
+#[cfg(feature = "tokio_tp")]
+mod tokio_tiered;
+#[cfg(feature = "tokio_tp")]
+pub use tokio_tiered::*;
+#[cfg(any(feature = "tokio_ct", feature = "tokio_tp"))]
+mod tokio_local_set;
+#[cfg(any(feature = "tokio_ct", feature = "tokio_tp"))]
+pub use tokio_local_set::*;
+#[cfg(feature = "tokio_metrics")]
+mod tokio_tp_metrics;
+#[cfg(feature = "tokio_metrics")]
+pub use tokio_tp_metrics::*;
+#[cfg(feature = "tokio_ct_metrics")]
+mod tokio_ct_metrics;
+#[cfg(feature = "tokio_ct_metrics")]
+pub use tokio_ct_metrics::*;
 
 #[cfg(feature = "tokio")]
 mod tokio_jh;
 #[cfg(feature = "tokio")]
 pub use tokio_jh::*;
 #[cfg(feature = "tokio")]
+mod tokio_handle;
+#[cfg(feature = "tokio")]
+pub use tokio_handle::*;
+#[cfg(feature = "tokio")]
 mod tokio_static;
 #[cfg(feature = "tokio")]
 pub use tokio_static::*;
@@ -29,17 +53,29 @@ pub use tokio_static::*;
 mod async_global;
 #[cfg(feature = "async_global")]
 pub use async_global::*;
+#[cfg(all(feature = "async_global", not(target_os = "unknown")))]
+mod async_global_local;
+#[cfg(all(feature = "async_global", not(target_os = "unknown")))]
+pub use async_global_local::*;
 
 #[cfg(feature = "async_std")]
 mod async_std;
 #[cfg(feature = "async_std")]
 pub use async_std::*;
+#[cfg(all(feature = "async_std", not(target_os = "unknown")))]
+mod async_std_builder;
+#[cfg(all(feature = "async_std", not(target_os = "unknown")))]
+pub use async_std_builder::*;
 
 #[cfg(feature = "glommio")]
 mod glommio_ct;
 #[cfg(feature = "glommio")]
 pub use glommio_ct::*;
 #[cfg(feature = "glommio")]
+mod glommio_ct_builder;
+#[cfg(feature = "glommio")]
+pub use glommio_ct_builder::*;
+#[cfg(feature = "glommio")]
 mod glommio_tp;
 #[cfg(feature = "glommio")]
 pub use glommio_tp::*;
@@ -47,6 +83,13 @@ pub use glommio_tp::*;
 mod glommio_static;
 #[cfg(feature = "glommio")]
 pub use glommio_static::*;
+#[cfg(feature = "glommio")]
+pub mod affinity;
+
+#[cfg(feature = "compio")]
+mod compio;
+#[cfg(feature = "compio")]
+pub use compio::*;
 
 #[cfg(feature = "bindgen")]
 mod bindgen;
@@ -64,3 +107,16 @@ pub use futures_executor::LocalPool;
 pub use futures_executor::LocalSpawner;
 #[cfg(feature = "threadpool")]
 pub use futures_executor::ThreadPool;
+
+#[cfg(all(feature = "block_on", not(target_os = "unknown")))]
+mod current_thread;
+#[cfg(all(feature = "block_on", not(target_os = "unknown")))]
+pub use current_thread::*;
+
+#[cfg(feature = "rayon")]
+mod rayon_pool;
+#[cfg(feature = "rayon")]
+pub use rayon_pool::*;
+
+mod enabled;
+pub use enabled::*;
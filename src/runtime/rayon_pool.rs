@@ -0,0 +1,79 @@
+//! Provides RayonPool to run blocking, CPU bound work on a rayon thread pool.
+//
+use {
+    crate::{JoinHandle, SpawnBlocking, SpawnError},
+    futures_util::FutureExt,
+    rayon_crate::ThreadPool,
+    std::sync::Arc,
+};
+
+/// Runs the closures passed to [`SpawnBlocking`] on a [rayon thread pool](rayon_crate::ThreadPool),
+/// so CPU bound work can use rayon's work stealing while being behind the same interface as the
+/// blocking pools of the async runtimes.
+///
+/// Dropping the returned [`JoinHandle`] before the closure starts prevents it from running.
+/// Once it has started, it runs to completion.
+///
+/// `RayonPool` is `Send`, `Sync` and `Clone`. Clones share the same thread pool.
+//
+#[derive(Debug, Clone)]
+//
+#[cfg_attr(nightly, doc(cfg(feature = "rayon")))]
+//
+pub struct RayonPool {
+    pool: Arc<ThreadPool>,
+}
+
+impl RayonPool {
+    /// Wrap a thread pool you have configured with [`rayon::ThreadPoolBuilder`](rayon_crate::ThreadPoolBuilder).
+    //
+    pub fn new(pool: Arc<ThreadPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Access the wrapped thread pool, eg. to use the parallel iterators of rayon on it.
+    //
+    pub fn pool(&self) -> &Arc<ThreadPool> {
+        &self.pool
+    }
+}
+
+impl From<ThreadPool> for RayonPool {
+    fn from(pool: ThreadPool) -> Self {
+        Self::new(Arc::new(pool))
+    }
+}
+
+impl From<Arc<ThreadPool>> for RayonPool {
+    fn from(pool: Arc<ThreadPool>) -> Self {
+        Self::new(pool)
+    }
+}
+
+impl<T: Send + 'static> SpawnBlocking<T> for RayonPool {
+    fn spawn_blocking_obj(
+        &self,
+        func: Box<dyn FnOnce() -> T + Send>,
+    ) -> Result<JoinHandle<T>, SpawnError> {
+        let (fut, handle) = async move { func() }.remote_handle();
+
+        // The closure is synchronous, so a single poll runs it to completion and sends the output
+        // to the handle. If the handle has been dropped, the remote future returns without polling it.
+        //
+        self.pool.spawn(move || {
+            let _ = fut.now_or_never();
+        });
+
+        Ok(handle.into())
+    }
+}
+
+#[cfg(test)]
+//
+mod tests {
+    use super::*;
+
+    // Clones share the thread pool and can be sent to other threads.
+    //
+    static_assertions::assert_impl_all!(RayonPool: Send, Sync, Clone);
+}
@@ -1,10 +1,26 @@
-use crate::{BlockOn, LocalSpawn, Spawn, SpawnBlocking, SpawnError, TokioJoinHandle};
+use crate::{
+    BlockOn, LocalSpawn, Spawn, SpawnBlocking, SpawnError, Timer, Tokio, TokioJoinHandle,
+    WithRuntime,
+};
+use futures_util::future::{poll_fn, BoxFuture};
+use futures_util::task::AtomicWaker;
+use futures_util::FutureExt;
+use std::time::Duration;
 
 use std::rc::Rc;
 use {
     crate::{JoinHandle, LocalSpawnHandle, SpawnHandle},
     futures_task::{FutureObj, LocalFutureObj},
-    std::future::Future,
+    std::{
+        future::Future,
+        panic::AssertUnwindSafe,
+        pin::Pin,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        task::{Context, Poll},
+    },
     tokio::{runtime::Runtime, task::LocalSet},
 };
 
@@ -61,7 +77,7 @@ use {
 /// nor any destructors called during the unwind can observe data in an inconsistent state.
 ///
 /// Note: the future running from within `block_on` as opposed to `spawn` does not exhibit this behavior and will panic
-/// the current thread.
+/// the current thread. Use [`block_on_catch_unwind`](TokioCt::block_on_catch_unwind) to get the panic as an error instead.
 ///
 /// Note that these are logic errors, not related to the class of problems that cannot happen
 /// in safe rust (memory safety, undefined behavior, unsoundness, data races, ...). See the relevant
@@ -69,6 +85,8 @@ use {
 /// and it's discussion threads for more info as well as the documentation of [std::panic::UnwindSafe]
 /// for more information.
 ///
+/// `TokioCt` can spawn `!Send` futures, so it's neither `Send` nor `Sync`. Clones share the same runtime.
+///
 //
 #[derive(Debug, Clone)]
 //
@@ -77,6 +95,47 @@ use {
 pub struct TokioCt {
     pub(crate) exec: Rc<Runtime>,
     pub(crate) local: Rc<LocalSet>,
+    pub(crate) polls: Arc<AtomicUsize>,
+    pub(crate) local_tasks: Arc<AtomicUsize>,
+    pub(crate) local_drained: Arc<AtomicWaker>,
+    // Must stay the last field, fields are dropped in order and the drain needs to be the last
+    // owner of the runtime and the local set.
+    pub(crate) _drain: Option<Rc<DrainOnDrop>>,
+}
+
+/// Runs the remaining tasks of a [`TokioCt`] when the last clone is dropped.
+/// See [`TokioCtBuilder::drain_on_drop`](crate::TokioCtBuilder::drain_on_drop).
+//
+#[derive(Debug)]
+//
+pub(crate) struct DrainOnDrop {
+    pub(crate) exec: Rc<Runtime>,
+    pub(crate) local: Rc<LocalSet>,
+    pub(crate) timeout: Duration,
+}
+
+impl Drop for DrainOnDrop {
+    fn drop(&mut self) {
+        // block_on would panic in these cases.
+        if std::thread::panicking() || tokio::runtime::Handle::try_current().is_ok() {
+            return;
+        }
+
+        let local = std::mem::replace(&mut self.local, Rc::new(LocalSet::new()));
+
+        // Someone else still has the local set, so the executor is not really gone.
+        let local = match Rc::try_unwrap(local) {
+            Ok(local) => local,
+            Err(_) => return,
+        };
+
+        let timeout = self.timeout;
+
+        // The local set resolves once all the tasks in it have completed.
+        let _ = self
+            .exec
+            .block_on(async move { tokio::time::timeout(timeout, local).await });
+    }
 }
 
 impl TokioCt {
@@ -96,6 +155,156 @@ impl TokioCt {
     pub fn block_on<F: Future>(&self, f: F) -> F::Output {
         self.exec.block_on(self.local.run_until(f))
     }
+
+    /// Like [`block_on`](TokioCt::block_on), but keeps running the executor after `f` resolves, until all
+    /// tasks on the local set have completed too. So this runs everything to completion, where `block_on`
+    /// stops as soon as the top level future is done and leaves the other tasks pending.
+    ///
+    /// The local set is where the tasks spawned through [`Spawn`], [`LocalSpawn`] and [`LocalSpawnHandle`]
+    /// go. Tasks spawned through [`SpawnHandle`] run on the runtime itself and are not waited for, but
+    /// they are polled in the meantime. Note that a task that never completes makes this block forever.
+    ///
+    /// ## Panics
+    ///
+    /// This function will panic if it is called from an async context. It will also panic if the
+    /// provided future panics.
+    pub fn block_on_all<F: Future>(&self, f: F) -> F::Output {
+        let drained = self.local_drained();
+
+        self.block_on(async move {
+            let out = f.await;
+            drained.await;
+            out
+        })
+    }
+
+    /// Like [`block_on`](TokioCt::block_on), but a panic in the top level future is caught and returned
+    /// as an error instead of unwinding the calling thread, so eg. a REPL loop can keep going.
+    ///
+    /// The future is wrapped in [`AssertUnwindSafe`](std::panic::AssertUnwindSafe), so the same caveats
+    /// as for spawned tasks apply, see the section on unwind safety in the docs of [`TokioCt`].
+    ///
+    /// ## Panics
+    ///
+    /// This function will panic if it is called from an async context.
+    pub fn block_on_catch_unwind<F: Future>(&self, f: F) -> std::thread::Result<F::Output> {
+        self.block_on(AssertUnwindSafe(f).catch_unwind())
+    }
+
+    /// Run the tasks spawned on this executor until it is idle, without a specific top level future.
+    ///
+    /// Idle means that during a full round of the scheduler, none of the tasks spawned through
+    /// the traits of this crate got polled. That is, they have all completed, or they are waiting
+    /// on something that wasn't ready when tokio last checked for IO and timers. A task that waits
+    /// on a timer that hasn't expired yet or on a channel fed from another thread does not keep the
+    /// executor busy, so it might still be pending when this returns. Tasks spawned directly
+    /// through tokio are run, but not tracked.
+    ///
+    /// Like with `block_on`, remaining tasks will make progress again the next time the executor runs.
+    ///
+    /// ## Panics
+    ///
+    /// This function will panic if it is called from an async context.
+    pub fn run_until_idle(&self) {
+        self.block_on(self.idle())
+    }
+
+    /// Resolves once the executor is idle, as defined for [`run_until_idle`](TokioCt::run_until_idle).
+    /// Use this to wait for the spawned tasks from within the top level future, eg. before
+    /// asserting on their side effects in a test.
+    ///
+    /// Await it in the future passed to [`block_on`](TokioCt::block_on), not in a task spawned through
+    /// the traits of this crate, as the polls of that task would keep the executor busy.
+    pub fn idle(&self) -> impl Future<Output = ()> + 'static {
+        let polls = self.polls.clone();
+
+        async move {
+            loop {
+                let before = polls.load(Ordering::Relaxed);
+
+                // Tokio defers waking us up until it has run the other ready tasks and polled the drivers.
+                tokio::task::yield_now().await;
+
+                if polls.load(Ordering::Relaxed) == before {
+                    break;
+                }
+            }
+        }
+    }
+
+    // Resolves once there are no more tasks on the local set.
+    //
+    fn local_drained(&self) -> impl Future<Output = ()> + 'static {
+        let count = self.local_tasks.clone();
+        let drained = self.local_drained.clone();
+
+        poll_fn(move |cx| {
+            // Register first, so we don't miss the last task completing in between.
+            drained.register(cx.waker());
+
+            match count.load(Ordering::Relaxed) {
+                0 => Poll::Ready(()),
+                _ => Poll::Pending,
+            }
+        })
+    }
+
+    fn count_polls<F>(&self, future: F) -> CountPolls<F> {
+        CountPolls {
+            future,
+            polls: self.polls.clone(),
+            _local: None,
+        }
+    }
+
+    // Tokio does not include the tasks on the LocalSet in the runtime metrics, so we count them ourselves.
+    //
+    fn count_local<F>(&self, future: F) -> CountPolls<F> {
+        self.local_tasks.fetch_add(1, Ordering::Relaxed);
+
+        let task = LocalTask {
+            count: self.local_tasks.clone(),
+            drained: self.local_drained.clone(),
+        };
+
+        CountPolls {
+            _local: Some(task),
+            ..self.count_polls(future)
+        }
+    }
+}
+
+// Counts how many times the tasks of a TokioCt get polled, so we can detect when it's idle.
+//
+struct CountPolls<F> {
+    future: F,
+    polls: Arc<AtomicUsize>,
+    _local: Option<LocalTask>,
+}
+
+// Decrements the number of alive tasks on the LocalSet when the task is dropped, and wakes up
+// block_on_all when it was the last one.
+//
+struct LocalTask {
+    count: Arc<AtomicUsize>,
+    drained: Arc<AtomicWaker>,
+}
+
+impl Drop for LocalTask {
+    fn drop(&mut self) {
+        if self.count.fetch_sub(1, Ordering::Relaxed) == 1 {
+            self.drained.wake();
+        }
+    }
+}
+
+impl<F: Future + Unpin> Future for CountPolls<F> {
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.polls.fetch_add(1, Ordering::Relaxed);
+        Pin::new(&mut self.future).poll(cx)
+    }
 }
 
 impl BlockOn for TokioCt {
@@ -104,11 +313,19 @@ impl BlockOn for TokioCt {
     }
 }
 
+impl Timer for TokioCt {
+    fn sleep(&self, dur: Duration) -> BoxFuture<'static, ()> {
+        // Enter the runtime so this also works when called outside of block_on.
+        let _guard = self.exec.enter();
+        Box::pin(tokio::time::sleep(dur))
+    }
+}
+
 impl Spawn for TokioCt {
     fn spawn_obj(&self, future: FutureObj<'static, ()>) -> Result<(), SpawnError> {
         // We drop the JoinHandle, so the task becomes detached.
         //
-        let _ = self.local.spawn_local(future);
+        let _ = self.local.spawn_local(self.count_local(future));
 
         Ok(())
     }
@@ -118,7 +335,7 @@ impl LocalSpawn for TokioCt {
     fn spawn_local_obj(&self, future: LocalFutureObj<'static, ()>) -> Result<(), SpawnError> {
         // We drop the JoinHandle, so the task becomes detached.
         //
-        let _ = self.local.spawn_local(future);
+        let _ = self.local.spawn_local(self.count_local(future));
 
         Ok(())
     }
@@ -129,7 +346,28 @@ impl<Out: 'static + Send> SpawnHandle<Out> for TokioCt {
         &self,
         future: FutureObj<'static, Out>,
     ) -> Result<JoinHandle<Out>, SpawnError> {
-        Ok(TokioJoinHandle::new(self.exec.spawn(future)).into())
+        let handle = self.exec.spawn(self.count_polls(future));
+
+        Ok(TokioJoinHandle::new(handle)
+            .on_runtime(self.exec.handle())
+            .into())
+    }
+
+    #[cfg(all(tokio_unstable, feature = "tokio_tracing"))]
+    //
+    fn spawn_handle_obj_named(
+        &self,
+        name: &str,
+        future: FutureObj<'static, Out>,
+    ) -> Result<JoinHandle<Out>, SpawnError> {
+        let handle = tokio::task::Builder::new()
+            .name(name)
+            .spawn_on(self.count_polls(future), self.exec.handle())
+            .map_err(|_| SpawnError::shutdown())?;
+
+        Ok(TokioJoinHandle::new(handle)
+            .on_runtime(self.exec.handle())
+            .into())
     }
 }
 
@@ -138,7 +376,11 @@ impl<Out: 'static> LocalSpawnHandle<Out> for TokioCt {
         &self,
         future: LocalFutureObj<'static, Out>,
     ) -> Result<JoinHandle<Out>, SpawnError> {
-        Ok(TokioJoinHandle::new(self.local.spawn_local(future)).into())
+        let handle = self.local.spawn_local(self.count_local(future));
+
+        Ok(TokioJoinHandle::new(handle)
+            .on_runtime(self.exec.handle())
+            .into())
     }
 }
 impl<T: Send + 'static> SpawnBlocking<T> for TokioCt {
@@ -147,10 +389,19 @@ impl<T: Send + 'static> SpawnBlocking<T> for TokioCt {
         func: Box<dyn FnOnce() -> T + Send>,
     ) -> Result<JoinHandle<T>, SpawnError> {
         let handle = self.exec.spawn_blocking(func);
-        Ok(TokioJoinHandle::new(handle).into())
+        Ok(TokioJoinHandle::new(handle)
+            .on_runtime(self.exec.handle())
+            .into())
     }
 }
 
+/// Use [`Tokio`] to access this runtime through the static traits, eg. `SpawnBlockingStatic`, from
+/// within [`block_on`](TokioCt::block_on).
+//
+impl WithRuntime for TokioCt {
+    type Runtime = Tokio;
+}
+
 #[cfg(test)]
 //
 mod tests {
@@ -159,4 +410,8 @@ mod tests {
     // It's important that this is not Send, as we allow spawning !Send futures on it.
     //
     static_assertions::assert_not_impl_any!(TokioCt: Send, Sync);
+
+    // Clones share the runtime.
+    //
+    static_assertions::assert_impl_all!(TokioCt: Clone);
 }
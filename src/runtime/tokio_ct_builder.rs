@@ -1,7 +1,8 @@
 //! Provides TokioCtBuilder which guarantees at type level that it is single-threaded.
 //
-use std::rc::Rc;
+use std::{rc::Rc, time::Duration};
 use {
+    super::tokio_ct::DrainOnDrop,
     crate::TokioCt,
     tokio::{runtime::Builder, task::LocalSet},
 };
@@ -15,14 +16,25 @@ use {
 //
 pub struct TokioCtBuilder {
     builder: Builder,
+    drain_on_drop: bool,
+    drain_timeout: Duration,
+    #[cfg(tokio_unstable)]
+    unhandled_panic: Option<tokio::runtime::UnhandledPanic>,
 }
 
 impl TokioCtBuilder {
-    /// Constructor.
+    /// Constructor. Enables the tokio timer, which is needed for the [`Timer`](crate::Timer) implementation.
     //
     pub fn new() -> Self {
+        let mut builder = Builder::new_current_thread();
+        builder.enable_time();
+
         Self {
-            builder: Builder::new_current_thread(),
+            builder,
+            drain_on_drop: false,
+            drain_timeout: Duration::from_secs(1),
+            #[cfg(tokio_unstable)]
+            unhandled_panic: None,
         }
     }
 
@@ -33,16 +45,90 @@ impl TokioCtBuilder {
         &mut self.builder
     }
 
+    /// Set the name of the threads tokio starts for this runtime, see [`Builder::thread_name`].
+    ///
+    /// Note that a current thread runtime doesn't start a thread to run the tasks on. They run on
+    /// the thread that calls [`block_on`](TokioCt::block_on), so that thread keeps its own name. The
+    /// name applies to the threads of the blocking pool, eg. for
+    /// [`spawn_blocking`](crate::SpawnBlocking). To name the thread that runs the tasks, call `block_on`
+    /// from a thread created with [`std::thread::Builder::name`].
+    //
+    pub fn thread_name(&mut self, name: impl Into<String>) -> &mut Self {
+        self.builder.thread_name(name);
+        self
+    }
+
+    /// Choose what happens when a spawned task panics, eg. shut down the runtime so the panic doesn't
+    /// go unnoticed when the handle is detached. See [`Builder::unhandled_panic`]. It's applied to the
+    /// runtime and to the `LocalSet` that runs the tasks spawned through this crate.
+    ///
+    /// Only available when compiling with `RUSTFLAGS="--cfg tokio_unstable"`. Tokio only supports
+    /// this for the current thread runtime. For other executors, see [`OnTaskPanic`](crate::OnTaskPanic).
+    //
+    #[cfg(tokio_unstable)]
+    #[cfg_attr(nightly, doc(cfg(tokio_unstable)))]
+    //
+    pub fn unhandled_panic(&mut self, behavior: tokio::runtime::UnhandledPanic) -> &mut Self {
+        self.builder.unhandled_panic(behavior.clone());
+        self.unhandled_panic = Some(behavior);
+        self
+    }
+
+    /// When the last clone of the executor is dropped, run the tasks that are still in it until they
+    /// have all completed, or until the [`drain_timeout`](TokioCtBuilder::drain_timeout) has passed.
+    /// Useful to flush pending writes or logs when a short lived executor goes away. Off by default,
+    /// in which case the remaining tasks are just dropped.
+    ///
+    /// __Warning__: this blocks the thread that drops the executor for up to the timeout. Draining
+    /// is skipped when the executor is dropped from within an async context or during a panic,
+    /// since tokio can't block there.
+    //
+    pub fn drain_on_drop(&mut self, drain: bool) -> &mut Self {
+        self.drain_on_drop = drain;
+        self
+    }
+
+    /// The maximum time to spend running remaining tasks when [`drain_on_drop`](TokioCtBuilder::drain_on_drop)
+    /// is enabled. Defaults to 1 second.
+    //
+    pub fn drain_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.drain_timeout = timeout;
+        self
+    }
+
     /// Create the actual executor.
     ///
     /// The error comes from tokio. From their docs, no idea why it is there or what could go wrong.
     //
     pub fn build(&mut self) -> Result<TokioCt, std::io::Error> {
-        let exec = self.builder.build()?;
+        let exec = Rc::new(self.builder.build()?);
+        #[allow(unused_mut)]
+        //
+        let mut local = LocalSet::new();
+
+        #[cfg(tokio_unstable)]
+        //
+        if let Some(behavior) = self.unhandled_panic.clone() {
+            local.unhandled_panic(behavior);
+        }
+
+        let local = Rc::new(local);
+
+        let drain = self.drain_on_drop.then(|| {
+            Rc::new(DrainOnDrop {
+                exec: exec.clone(),
+                local: local.clone(),
+                timeout: self.drain_timeout,
+            })
+        });
 
         Ok(TokioCt {
-            exec: Rc::new(exec),
-            local: Rc::new(LocalSet::new()),
+            exec,
+            local,
+            polls: Default::default(),
+            local_tasks: Default::default(),
+            local_drained: Default::default(),
+            _drain: drain,
         })
     }
 }
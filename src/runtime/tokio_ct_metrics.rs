@@ -0,0 +1,69 @@
+//! Provides metrics about the runtime behind TokioCt.
+//
+use crate::TokioCt;
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+use tokio::runtime::RuntimeMetrics;
+
+/// A handle on the metrics of a [`TokioCt`] runtime, see [`TokioCt::metrics`]. The methods return
+/// the current values, so you can keep it around and query it again later.
+///
+/// This wraps [tokio::runtime::RuntimeMetrics] so the API stays the same when tokio renames things, and
+/// only exposes what tokio provides without `tokio_unstable`. Tokio does not count the tasks on the
+/// [`LocalSet`](tokio::task::LocalSet), which is where [`TokioCt`] spawns through [`Spawn`](crate::Spawn)
+/// and [`LocalSpawn`](crate::LocalSpawn), so those are counted by this crate.
+///
+/// A nonzero [`active_tasks_count`](TokioCtMetrics::active_tasks_count) after the program should be
+/// done points to a task that is stuck.
+//
+#[derive(Debug, Clone)]
+//
+#[cfg_attr(nightly, doc(cfg(feature = "tokio_ct_metrics")))]
+//
+pub struct TokioCtMetrics {
+    metrics: RuntimeMetrics,
+    local_tasks: Arc<AtomicUsize>,
+}
+
+impl TokioCtMetrics {
+    /// The number of tasks currently alive on the executor, both on the runtime and on the local set.
+    //
+    pub fn active_tasks_count(&self) -> usize {
+        self.metrics.num_alive_tasks() + self.local_tasks_count()
+    }
+
+    /// The number of tasks spawned on the local set of the executor through the traits of this crate
+    /// that are still alive.
+    //
+    pub fn local_tasks_count(&self) -> usize {
+        self.local_tasks.load(Ordering::Relaxed)
+    }
+
+    /// The number of tasks in the scheduler queue of the runtime, waiting to be polled. Tasks on the
+    /// local set are not included.
+    //
+    pub fn global_queue_depth(&self) -> usize {
+        self.metrics.global_queue_depth()
+    }
+
+    /// Access the metrics from tokio directly.
+    //
+    pub fn inner(&self) -> &RuntimeMetrics {
+        &self.metrics
+    }
+}
+
+impl TokioCt {
+    /// Get the metrics of the runtime.
+    //
+    #[cfg_attr(nightly, doc(cfg(feature = "tokio_ct_metrics")))]
+    //
+    pub fn metrics(&self) -> TokioCtMetrics {
+        TokioCtMetrics {
+            metrics: self.exec.metrics(),
+            local_tasks: self.local_tasks.clone(),
+        }
+    }
+}
@@ -0,0 +1,111 @@
+//! Provides TokioHandle, a lightweight executor for when you only have access to a tokio runtime handle.
+//
+use crate::{JoinHandle, Spawn, SpawnBlocking, SpawnError, SpawnHandle, Timer, TokioJoinHandle};
+use {
+    futures_task::FutureObj, futures_util::future::BoxFuture, std::time::Duration,
+    tokio::runtime::Handle,
+};
+
+/// An executor that spawns on the runtime behind a [`tokio::runtime::Handle`].
+///
+/// This is lighter than [`TokioTp`](crate::TokioTp) or [`TokioCt`](crate::TokioCt) and covers the case
+/// where you embed into an existing application and all you have is a handle to it's runtime.
+/// Note that it does not keep the runtime alive. Spawning after the runtime has been dropped will
+/// give you tasks that never run.
+///
+/// ```
+/// use
+/// {
+///    async_executors :: { TokioHandle, SpawnHandleExt } ,
+///    tokio           :: { runtime::Builder            } ,
+/// };
+///
+/// let rt   = Builder::new_current_thread().build().expect( "create tokio runtime" );
+/// let exec = TokioHandle( rt.handle().clone() );
+///
+/// let join_handle = exec.spawn_handle( async { 5u8 } ).expect( "spawn" );
+///
+/// assert_eq!( 5, rt.block_on( join_handle ) );
+/// ```
+///
+/// `TokioHandle` is `Send`, `Sync` and `Clone`, like the [`Handle`] it wraps.
+//
+#[derive(Debug, Clone)]
+//
+#[cfg_attr(nightly, doc(cfg(feature = "tokio")))]
+//
+pub struct TokioHandle(pub Handle);
+
+impl From<Handle> for TokioHandle {
+    fn from(handle: Handle) -> Self {
+        Self(handle)
+    }
+}
+
+/// # Panics
+///
+/// When the runtime behind the handle doesn't have timers enabled.
+//
+impl Timer for TokioHandle {
+    fn sleep(&self, dur: Duration) -> BoxFuture<'static, ()> {
+        let _guard = self.0.enter();
+        Box::pin(tokio::time::sleep(dur))
+    }
+}
+
+impl Spawn for TokioHandle {
+    fn spawn_obj(&self, future: FutureObj<'static, ()>) -> Result<(), SpawnError> {
+        // We drop the JoinHandle, so the task becomes detached.
+        //
+        drop(self.0.spawn(future));
+
+        Ok(())
+    }
+}
+
+impl<Out: 'static + Send> SpawnHandle<Out> for TokioHandle {
+    fn spawn_handle_obj(
+        &self,
+        future: FutureObj<'static, Out>,
+    ) -> Result<JoinHandle<Out>, SpawnError> {
+        Ok(TokioJoinHandle::new(self.0.spawn(future))
+            .on_runtime(&self.0)
+            .into())
+    }
+
+    #[cfg(all(tokio_unstable, feature = "tokio_tracing"))]
+    //
+    fn spawn_handle_obj_named(
+        &self,
+        name: &str,
+        future: FutureObj<'static, Out>,
+    ) -> Result<JoinHandle<Out>, SpawnError> {
+        let handle = tokio::task::Builder::new()
+            .name(name)
+            .spawn_on(future, &self.0)
+            .map_err(|_| SpawnError::shutdown())?;
+
+        Ok(TokioJoinHandle::new(handle).on_runtime(&self.0).into())
+    }
+}
+
+impl<T: Send + 'static> SpawnBlocking<T> for TokioHandle {
+    fn spawn_blocking_obj(
+        &self,
+        func: Box<dyn FnOnce() -> T + Send>,
+    ) -> Result<JoinHandle<T>, SpawnError> {
+        Ok(TokioJoinHandle::new(self.0.spawn_blocking(func))
+            .on_runtime(&self.0)
+            .into())
+    }
+}
+
+#[cfg(test)]
+//
+mod tests {
+    use super::*;
+
+    // Like the tokio Handle it wraps.
+    //
+    static_assertions::assert_impl_all!(TokioHandle: Send, Sync, Clone);
+}
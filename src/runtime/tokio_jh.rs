@@ -1,23 +1,122 @@
 use crate::AsyncJoinHandle;
-use futures_util::ready;
+use futures_util::{future::poll_fn, ready};
 use std::future::Future;
 use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::task::{Context, Poll};
-use tokio::task::JoinHandle;
+use std::{any::Any, fmt};
+use tokio::runtime::Handle;
+use tokio::task::{JoinError, JoinHandle};
 
 #[derive(Debug)]
 pub struct TokioJoinHandle<T> {
     handle: Option<JoinHandle<T>>,
+    runtime: Option<Handle>,
+    aborted: AtomicBool,
 }
 impl<T> TokioJoinHandle<T> {
     pub fn new(handle: JoinHandle<T>) -> Self {
         Self {
-            handle: Some(handle)
+            handle: Some(handle),
+            runtime: Handle::try_current().ok(),
+            aborted: AtomicBool::new(false),
         }
     }
+
+    // For when the task is spawned from outside the runtime, so make_portable knows where to spawn.
+    pub(crate) fn on_runtime(mut self, runtime: &Handle) -> Self {
+        self.runtime = Some(runtime.clone());
+        self
+    }
+
+    /// Wait for the task without panicking when it doesn't complete. Awaiting the handle directly
+    /// panics in that case.
+    ///
+    /// Tokio reports both an aborted task and a task dropped by a runtime that shut down as a
+    /// cancellation. A task cancelled through [`abort`](TokioJoinHandle::abort) is reported as
+    /// [`JoinErrorKind::Cancelled`], any other cancellation as [`JoinErrorKind::RuntimeGone`]. That
+    /// includes aborting the native handle from [`inner`](TokioJoinHandle::inner), which this can't see.
+    ///
+    /// # Panics
+    ///
+    /// When the task has already been joined.
+    pub async fn join(mut self) -> Result<T, JoinErrorKind> {
+        // Poll in place, so dropping this future drops self and with it cancels the task.
+        //
+        poll_fn(|cx| self.poll_join(cx)).await
+    }
+
+    // Like poll, but reports a task that didn't complete instead of panicking.
+    //
+    pub(crate) fn poll_join(&mut self, cx: &mut Context<'_>) -> Poll<Result<T, JoinErrorKind>> {
+        let handle = self
+            .handle
+            .as_mut()
+            .expect("Cannot poll after completion/cancellation");
+
+        let result = ready!(Pin::new(handle).poll(cx));
+
+        self.handle = None;
+        Poll::Ready(result.map_err(|e| self.error_kind(e)))
+    }
+
+    fn error_kind(&self, err: JoinError) -> JoinErrorKind {
+        if err.is_panic() {
+            return JoinErrorKind::Panic(err.into_panic());
+        }
+
+        if self.aborted.load(Ordering::SeqCst) {
+            JoinErrorKind::Cancelled
+        } else {
+            JoinErrorKind::RuntimeGone
+        }
+    }
+
+    /// Cancel the task, while keeping the handle so you can still [`join`](TokioJoinHandle::join) it.
+    /// Does nothing if the task has already completed or has been joined.
+    pub fn abort(&self) {
+        if let Some(handle) = &self.handle {
+            self.aborted.store(true, Ordering::SeqCst);
+            handle.abort();
+        }
+    }
+
+    /// Access the native tokio handle. Returns `None` once the task has been joined.
+    pub fn inner(&self) -> Option<&JoinHandle<T>> {
+        self.handle.as_ref()
+    }
+
+    // The runtime the task was spawned on, if known.
+    pub(crate) fn runtime(&self) -> Option<&Handle> {
+        self.runtime.as_ref()
+    }
 }
 impl<T> Unpin for TokioJoinHandle<T> {}
 
+/// Why a tokio task did not complete, see [`TokioJoinHandle::join`].
+#[derive(Debug)]
+pub enum JoinErrorKind {
+    /// The task was aborted.
+    Cancelled,
+    /// The task panicked. Contains the panic payload, which you can pass to
+    /// [`std::panic::resume_unwind`].
+    Panic(Box<dyn Any + Send + 'static>),
+    /// The runtime shut down before the task completed.
+    RuntimeGone,
+}
+
+impl fmt::Display for JoinErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Cancelled => write!(f, "The task was cancelled"),
+            Self::Panic(_) => write!(f, "The task panicked"),
+            Self::RuntimeGone => write!(f, "The runtime shut down before the task completed"),
+        }
+    }
+}
+
+impl std::error::Error for JoinErrorKind {}
+
 impl<T> Future for TokioJoinHandle<T> {
     type Output = T;
 
@@ -53,6 +152,6 @@ impl<T> Drop for TokioJoinHandle<T> {
 }
 impl<T> Into<crate::JoinHandle<T>> for TokioJoinHandle<T> {
     fn into(self) -> crate::JoinHandle<T> {
-        crate::JoinHandle::TokioJoinHandle(self)
+        crate::JoinHandle::TokioJoinHandle(self).with_default_drop()
     }
-}
\ No newline at end of file
+}
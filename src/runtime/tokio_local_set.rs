@@ -0,0 +1,64 @@
+//! Provides LocalSetSpawn, to spawn on a tokio LocalSet you own.
+//
+use crate::{JoinHandle, LocalSpawn, LocalSpawnHandle, Spawn, SpawnError, TokioJoinHandle};
+use {
+    futures_task::{FutureObj, LocalFutureObj},
+    tokio::task::LocalSet,
+};
+
+/// Spawns `!Send` futures on a borrowed [`tokio::task::LocalSet`].
+///
+/// [`TokioCt`](crate::TokioCt) owns its local set. Use this when you manage your own, eg. one per
+/// shard, and want to spawn on it through the traits of this crate. The tasks only make progress
+/// while the set is being driven, eg. by [`LocalSet::run_until`] or by awaiting it.
+///
+/// ```
+/// use
+/// {
+///    async_executors :: { LocalSetSpawn, LocalSpawnHandleExt } ,
+///    tokio           :: { runtime::Builder, task::LocalSet   } ,
+///    std             :: { rc::Rc                             } ,
+/// };
+///
+/// let rt    = Builder::new_current_thread().build().expect( "create tokio runtime" );
+/// let set   = LocalSet::new();
+/// let exec  = LocalSetSpawn( &set );
+///
+/// let not_send    = Rc::new( 5u8 );
+/// let join_handle = exec.spawn_handle_local( async move { *not_send } ).expect( "spawn" );
+///
+/// assert_eq!( 5, rt.block_on( set.run_until( join_handle ) ) );
+/// ```
+//
+#[derive(Debug, Clone, Copy)]
+//
+#[cfg_attr(nightly, doc(cfg(any(feature = "tokio_ct", feature = "tokio_tp"))))]
+//
+pub struct LocalSetSpawn<'a>(pub &'a LocalSet);
+
+impl LocalSpawn for LocalSetSpawn<'_> {
+    fn spawn_local_obj(&self, future: LocalFutureObj<'static, ()>) -> Result<(), SpawnError> {
+        // We drop the JoinHandle, so the task becomes detached.
+        //
+        drop(self.0.spawn_local(future));
+
+        Ok(())
+    }
+}
+
+// Send futures go on the local set as well, like for TokioCt.
+//
+impl Spawn for LocalSetSpawn<'_> {
+    fn spawn_obj(&self, future: FutureObj<'static, ()>) -> Result<(), SpawnError> {
+        self.spawn_local_obj(future.into())
+    }
+}
+
+impl<Out: 'static> LocalSpawnHandle<Out> for LocalSetSpawn<'_> {
+    fn spawn_handle_local_obj(
+        &self,
+        future: LocalFutureObj<'static, Out>,
+    ) -> Result<JoinHandle<Out>, SpawnError> {
+        Ok(TokioJoinHandle::new(self.0.spawn_local(future)).into())
+    }
+}
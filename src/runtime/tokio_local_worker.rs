@@ -0,0 +1,180 @@
+//! Provides LocalWorkerHandle, a thread with a LocalSet next to a TokioTp.
+//
+use crate::{JoinHandle, Spawn, SpawnError, SpawnHandle, TokioTp};
+use {
+    futures_channel::{mpsc, oneshot},
+    futures_task::FutureObj,
+    futures_util::{
+        future::{select, Either, FutureExt},
+        StreamExt,
+    },
+    std::{future::Future, io},
+    tokio::task::{spawn_local, LocalSet},
+};
+
+// Runs on the worker thread, inside the LocalSet.
+//
+type Job = Box<dyn FnOnce() + Send>;
+
+/// A dedicated thread that runs a [`LocalSet`] on the runtime of a [`TokioTp`], created with
+/// [`TokioTp::local_worker`].
+///
+/// All tasks spawned through a handle run on that one thread, so related tasks can share `!Send`
+/// state and stay on the same core. Tokio can not pin tasks on the pool itself, this gives you that
+/// on top of it. Timers and IO still go through the drivers of the pool, and [`tokio::spawn`] called
+/// from a task on the worker puts `Send` work on the pool as usual.
+///
+/// A `!Send` future can not be moved to the worker, so [`spawn_local`](LocalWorkerHandle::spawn_local)
+/// takes a closure that creates it on the worker thread. `Send` futures can be spawned directly through
+/// [`Spawn`] and [`SpawnHandle`].
+///
+/// Clones spawn on the same worker. The worker stops once all handles are dropped and the tasks spawned
+/// on it have completed or been cancelled through their [`JoinHandle`], so a task that never ends keeps
+/// the thread around. The worker keeps the runtime alive, so [`TokioTp::shutdown_timeout`] fails while
+/// it runs.
+///
+/// ```
+/// use
+/// {
+///    async_executors :: { TokioTp } ,
+///    std             :: { rc::Rc  } ,
+/// };
+///
+/// let exec   = TokioTp::default();
+/// let worker = exec.local_worker().expect( "start local worker" );
+///
+/// let join_handle = worker.spawn_local( ||
+/// {
+///    let not_send = Rc::new( 5u8 );
+///
+///    async move { *not_send }
+///
+/// }).expect( "spawn" );
+///
+/// assert_eq!( 5, exec.block_on( join_handle ) );
+/// ```
+//
+#[derive(Debug, Clone)]
+//
+#[cfg_attr(nightly, doc(cfg(feature = "tokio_tp")))]
+//
+pub struct LocalWorkerHandle {
+    jobs: mpsc::UnboundedSender<Job>,
+}
+
+impl TokioTp {
+    /// Start a dedicated thread that runs a [`LocalSet`] on this runtime. See [`LocalWorkerHandle`].
+    /// Call this several times to get several workers.
+    ///
+    /// # Errors
+    ///
+    /// When the thread can not be spawned, or when the runtime has been shut down.
+    //
+    pub fn local_worker(&self) -> io::Result<LocalWorkerHandle> {
+        let exec = self
+            .exec
+            .clone()
+            .ok_or_else(|| io::Error::other("the runtime has been shut down"))?;
+
+        let (jobs, mut rx) = mpsc::unbounded::<Job>();
+
+        std::thread::Builder::new()
+            .name("tokio-local-worker".to_string())
+            .spawn(move || {
+                let local = LocalSet::new();
+
+                // Take jobs until all handles are dropped, then let the tasks that are still
+                // pending finish, a LocalSet resolves once it has no tasks left.
+                //
+                exec.block_on(async move {
+                    local
+                        .run_until(async move {
+                            while let Some(job) = rx.next().await {
+                                job();
+                            }
+                        })
+                        .await;
+
+                    local.await
+                });
+            })?;
+
+        Ok(LocalWorkerHandle { jobs })
+    }
+}
+
+impl LocalWorkerHandle {
+    /// Spawn a `!Send` future on the worker. `make_fut` is called on the worker thread to create it.
+    /// The output has to be `Send`, since it comes back to the thread that awaits the handle.
+    ///
+    /// Dropping the handle cancels the task, unless you [`detach`](JoinHandle::detach) it.
+    ///
+    /// # Errors
+    ///
+    /// [`SpawnError::shutdown`] when the worker thread is gone, eg. because it panicked.
+    //
+    pub fn spawn_local<F, Fut>(&self, make_fut: F) -> Result<JoinHandle<Fut::Output>, SpawnError>
+    where
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future + 'static,
+        Fut::Output: Send + 'static,
+    {
+        // The task can't be created here, so the handle wraps the receiving end of a channel, which
+        // runs on the worker as well. When the handle gets dropped, the task notices through the channel.
+        //
+        let (mut tx, rx) = oneshot::channel();
+        let (remote, handle) = rx
+            .map(|out| out.expect("local worker stopped before the task completed"))
+            .remote_handle();
+
+        self.run(move || {
+            let fut = make_fut();
+
+            drop(spawn_local(async move {
+                let out = {
+                    futures_util::pin_mut!(fut);
+
+                    match select(fut, tx.cancellation()).await {
+                        Either::Left((out, _)) => Some(out),
+                        Either::Right(_) => None,
+                    }
+                };
+
+                if let Some(out) = out {
+                    let _ = tx.send(out);
+                }
+            }));
+
+            drop(spawn_local(remote));
+        })?;
+
+        Ok(handle.into())
+    }
+
+    // Send a job to the worker thread.
+    //
+    fn run(&self, job: impl FnOnce() + Send + 'static) -> Result<(), SpawnError> {
+        self.jobs
+            .unbounded_send(Box::new(job))
+            .map_err(|_| SpawnError::shutdown())
+    }
+}
+
+impl Spawn for LocalWorkerHandle {
+    fn spawn_obj(&self, future: FutureObj<'static, ()>) -> Result<(), SpawnError> {
+        self.run(move || drop(spawn_local(future)))
+    }
+}
+
+impl<Out: 'static + Send> SpawnHandle<Out> for LocalWorkerHandle {
+    fn spawn_handle_obj(
+        &self,
+        future: FutureObj<'static, Out>,
+    ) -> Result<JoinHandle<Out>, SpawnError> {
+        let (remote, handle) = future.remote_handle();
+
+        self.run(move || drop(spawn_local(remote)))?;
+
+        Ok(handle.into())
+    }
+}
@@ -5,6 +5,15 @@ use crate::{
 use futures_util::future::BoxFuture;
 use std::future::Future;
 
+/// Static access to the tokio runtime the current thread is running in, eg. inside
+/// [`TokioCt::block_on`](crate::TokioCt::block_on) or a task spawned on a [`TokioTp`](crate::TokioTp).
+/// This works for both the current thread and the multi threaded runtime, so generic code with bounds
+/// like `Exec: SpawnBlockingStatic` can use it with either.
+///
+/// The methods panic when called outside of a tokio runtime, like the tokio functions they forward to.
+///
+/// `Tokio` is a zero sized type that is `Send`, `Sync` and `Copy`.
+//
 #[derive(Debug, Copy, Default, Clone)]
 pub struct Tokio;
 
@@ -64,3 +73,13 @@ impl SpawnBlockingStatic for Tokio {
         Ok(TokioJoinHandle::new(handle).into())
     }
 }
+
+#[cfg(test)]
+//
+mod tests {
+    use super::*;
+
+    // A zero sized handle to whatever runtime is current.
+    //
+    static_assertions::assert_impl_all!(Tokio: Send, Sync, Copy, Default);
+}
@@ -0,0 +1,119 @@
+//! Provides TieredTokio, which routes tasks to one of two tokio thread pools by priority.
+//
+use crate::{JoinHandle, Spawn, SpawnError, SpawnExt, SpawnHandle, SpawnHandleExt, TokioTp};
+use futures_task::FutureObj;
+use std::future::Future;
+
+/// Two [`TokioTp`] runtimes, one for latency critical work and one for background work. Since they
+/// have separate worker pools, a flood of background tasks can not delay the high priority ones,
+/// unlike with prioritization within a single queue, eg. [`PrioritizedSpawn`](crate::PrioritizedSpawn).
+///
+/// [`Spawn`] and [`SpawnHandle`] put tasks on the high priority runtime, so code that is generic over
+/// the spawn traits keeps working. Use [`spawn_low`](TieredTokio::spawn_low) for background work.
+///
+/// It's a good idea to give the runtimes fewer worker threads than you have cores in total, and to name
+/// their threads, see [`TokioTpBuilder`](crate::TokioTpBuilder).
+///
+/// ```
+/// use async_executors::{ SpawnHandleExt, TieredTokio, TokioTpBuilder };
+///
+/// let high = TokioTpBuilder::new().worker_threads( 2 ).build().expect( "create high priority runtime" );
+/// let low  = TokioTpBuilder::new().worker_threads( 1 ).build().expect( "create low priority runtime"  );
+///
+/// let exec = TieredTokio::new( high, low );
+///
+/// let urgent     = exec.spawn_handle( async { 1u8 } ).expect( "spawn" );
+/// let background = exec.spawn_handle_low( async { 2u8 } ).expect( "spawn" );
+///
+/// assert_eq!( 1, exec.high().block_on( urgent     ) );
+/// assert_eq!( 2, exec.high().block_on( background ) );
+/// ```
+//
+#[derive(Debug, Clone)]
+//
+#[cfg_attr(nightly, doc(cfg(feature = "tokio_tp")))]
+//
+pub struct TieredTokio {
+    high: TokioTp,
+    low: TokioTp,
+}
+
+impl TieredTokio {
+    /// Create a new executor from the runtime for high priority tasks and the one for low priority tasks.
+    //
+    pub fn new(high: TokioTp, low: TokioTp) -> Self {
+        Self { high, low }
+    }
+
+    /// The runtime for high priority tasks.
+    //
+    pub fn high(&self) -> &TokioTp {
+        &self.high
+    }
+
+    /// The runtime for low priority tasks.
+    //
+    pub fn low(&self) -> &TokioTp {
+        &self.low
+    }
+
+    /// Spawn a future on the high priority runtime. Same as [`SpawnExt::spawn`].
+    //
+    pub fn spawn_high(
+        &self,
+        future: impl Future<Output = ()> + Send + 'static,
+    ) -> Result<(), SpawnError> {
+        self.high.spawn(future)
+    }
+
+    /// Spawn a future on the low priority runtime.
+    //
+    pub fn spawn_low(
+        &self,
+        future: impl Future<Output = ()> + Send + 'static,
+    ) -> Result<(), SpawnError> {
+        self.low.spawn(future)
+    }
+
+    /// Spawn a future on the high priority runtime and get a [`JoinHandle`] to await it's output.
+    /// Same as [`SpawnHandleExt::spawn_handle`].
+    //
+    pub fn spawn_handle_high<Out: 'static + Send>(
+        &self,
+        future: impl Future<Output = Out> + Send + 'static,
+    ) -> Result<JoinHandle<Out>, SpawnError> {
+        self.high.spawn_handle(future)
+    }
+
+    /// Spawn a future on the low priority runtime and get a [`JoinHandle`] to await it's output.
+    //
+    pub fn spawn_handle_low<Out: 'static + Send>(
+        &self,
+        future: impl Future<Output = Out> + Send + 'static,
+    ) -> Result<JoinHandle<Out>, SpawnError> {
+        self.low.spawn_handle(future)
+    }
+}
+
+impl Spawn for TieredTokio {
+    fn spawn_obj(&self, future: FutureObj<'static, ()>) -> Result<(), SpawnError> {
+        self.high.spawn_obj(future)
+    }
+}
+
+impl<Out: 'static + Send> SpawnHandle<Out> for TieredTokio {
+    fn spawn_handle_obj(
+        &self,
+        future: FutureObj<'static, Out>,
+    ) -> Result<JoinHandle<Out>, SpawnError> {
+        self.high.spawn_handle_obj(future)
+    }
+
+    fn spawn_handle_obj_named(
+        &self,
+        name: &str,
+        future: FutureObj<'static, Out>,
+    ) -> Result<JoinHandle<Out>, SpawnError> {
+        self.high.spawn_handle_obj_named(name, future)
+    }
+}
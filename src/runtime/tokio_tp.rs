@@ -1,14 +1,21 @@
 //! Provides TokioTp executor specific functionality.
 //
-use crate::{BlockOn, Spawn, SpawnError, TokioJoinHandle};
 use crate::{
-    LocalSpawnHandleStatic, LocalSpawnStatic, SpawnHandleStatic, SpawnStatic, YieldNowStatic,
+    BlockOn, Spawn, SpawnError, Timer, Tokio, TokioJoinHandle, TokioTpBuilder, WithRuntime,
 };
 use futures_util::future::BoxFuture;
 use {
     crate::{JoinHandle, SpawnHandle},
     futures_task::FutureObj,
-    std::{future::Future, sync::Arc},
+    std::{
+        cell::Cell,
+        convert::TryFrom,
+        error::Error,
+        fmt::{Display, Formatter},
+        future::Future,
+        sync::Arc,
+        time::Duration,
+    },
     tokio::runtime::Runtime,
 };
 
@@ -21,11 +28,8 @@ use {
 /// ```rust
 /// use
 /// {
-///    futures          :: { task::{ Spawn, SpawnExt } } ,
-///    async_executors  :: { TokioTpBuilder            } ,
-///    tokio::runtime   :: { Builder                   } ,
-///    std::convert     :: { TryFrom                   } ,
-///    futures::channel :: { oneshot, oneshot::Sender  } ,
+///    async_executors  :: { Spawn, SpawnExt, TokioTpBuilder } ,
+///    futures::channel :: { oneshot, oneshot::Sender         } ,
 /// };
 ///
 ///
@@ -41,9 +45,9 @@ use {
 ///
 /// fn main()
 /// {
-///    // You must use the builder. This guarantees that TokioTp is always backed up by a threadpool.
-///    // You can set other configurations by calling `tokio_builder()` on TokioTpBuilder, so you get
-///    // access to the `tokio::runtime::Builder`.
+///    // The builder guarantees that TokioTp is always backed up by a threadpool. `TokioTp::default()`
+///    // gives you one with the default configuration. You can set other configurations by calling
+///    // `tokio_builder()` on TokioTpBuilder, so you get access to the `tokio::runtime::Builder`.
 ///    //
 ///    let exec = TokioTpBuilder::new().build().expect( "create tokio threadpool" );
 ///
@@ -79,6 +83,12 @@ use {
 /// in safe rust (memory safety, undefined behavior, unsoundness, data races, ...). See the relevant
 /// [catch_unwind RFC](https://github.com/rust-lang/rfcs/blob/master/text/1236-stabilize-catch-panic.md)
 /// and it's discussion threads for more info as well as the documentation of [std::panic::UnwindSafe].
+///
+/// `TokioTp` is `Send`, `Sync` and `Clone`. Clones share the same runtime.
+///
+/// `TokioTp` does not implement the static spawn traits like `SpawnStatic`, as those require a `Copy` type
+/// and `TokioTp` owns the runtime. Use [`Tokio`] from within the runtime instead, which is also what
+/// [`WithRuntime`] points to.
 //
 #[derive(Debug, Clone)]
 //
@@ -89,10 +99,108 @@ pub struct TokioTp {
 }
 
 impl TokioTp {
-    /// Start the thread pool and run until completion
+    /// Start the thread pool and run until completion. This can be called several times, also
+    /// on different clones of the same executor and from several threads at once.
+    ///
+    /// # Panics
+    ///
+    /// When called from within an async context, eg. from a task running on any tokio runtime. Use
+    /// [`try_block_on`](TokioTp::try_block_on) if you want an error instead. Blocking while a runtime
+    /// has only been entered with `Runtime::enter` is fine.
+    //
     pub fn block_on<F: Future>(&self, f: F) -> F::Output {
-        self.exec.as_ref().unwrap().block_on(f)
+        match self.try_block_on(f) {
+            Ok(out) => out,
+            Err(e) => panic!("TokioTp::block_on: {}", e),
+        }
     }
+
+    /// Like [`block_on`](TokioTp::block_on), but returns an error instead of panicking when the
+    /// future can not be run.
+    ///
+    /// Tokio has no public way to tell whether blocking is allowed, so this returns
+    /// [`TokioTpError::InsideRuntime`] when called from a task on any tokio runtime, including a
+    /// `spawn_blocking` closure, or from the future of another `TokioTp::block_on`. The future
+    /// passed to `block_on` of a plain tokio `Runtime` is not detected, and there tokio panics.
+    //
+    pub fn try_block_on<F: Future>(&self, f: F) -> Result<F::Output, TokioTpError> {
+        let exec = self.exec.as_ref().ok_or(TokioTpError::Shutdown)?;
+
+        if in_async_context() {
+            return Err(TokioTpError::InsideRuntime);
+        }
+
+        let _guard = BlockOnGuard::enter();
+
+        Ok(exec.block_on(f))
+    }
+}
+
+thread_local! {
+    // Whether `TokioTp::block_on` is running on this thread.
+    //
+    static IN_BLOCK_ON: Cell<bool> = const { Cell::new(false) };
+}
+
+// Tokio refuses to block or to shut down a runtime from within an async context, but it has no public
+// way to ask whether we are in one. A current handle is not enough, as `Runtime::enter` sets one without
+// driving tasks, and blocking is fine there. So we look for a task id, which every task on a tokio runtime
+// has, and for our own `block_on`, whose future has none.
+//
+// This errs on the safe side for `spawn_blocking`, whose closures also have a task id, and misses the
+// future passed to `block_on` of a runtime that is not a `TokioTp`, where tokio still panics.
+//
+fn in_async_context() -> bool {
+    tokio::task::try_id().is_some() || IN_BLOCK_ON.with(Cell::get)
+}
+
+// Sets IN_BLOCK_ON for the duration of `block_on`, also when the future panics.
+//
+struct BlockOnGuard {
+    was_set: bool,
+}
+
+impl BlockOnGuard {
+    fn enter() -> Self {
+        Self {
+            was_set: IN_BLOCK_ON.with(|flag| flag.replace(true)),
+        }
+    }
+}
+
+impl Drop for BlockOnGuard {
+    fn drop(&mut self) {
+        IN_BLOCK_ON.with(|flag| flag.set(self.was_set));
+    }
+}
+
+/// Creates a threadpool with the default configuration of [`TokioTpBuilder`].
+///
+/// # Panics
+///
+/// When tokio fails to create the runtime. Use the builder if you want to handle that error.
+//
+impl Default for TokioTp {
+    fn default() -> Self {
+        TokioTpBuilder::new()
+            .build()
+            .expect("create tokio threadpool")
+    }
+}
+
+impl TryFrom<&mut TokioTpBuilder> for TokioTp {
+    type Error = std::io::Error;
+
+    fn try_from(builder: &mut TokioTpBuilder) -> Result<Self, Self::Error> {
+        builder.build()
+    }
+}
+
+/// Use [`Tokio`] to access this runtime through the static traits, eg. `SpawnHandleStatic`, from
+/// within the runtime.
+//
+impl WithRuntime for TokioTp {
+    type Runtime = Tokio;
 }
 
 impl BlockOn for TokioTp {
@@ -102,20 +210,79 @@ impl BlockOn for TokioTp {
 }
 
 impl TokioTp {
+    /// Run `f` to completion, then shut down the runtime with [`shutdown_timeout`](TokioTp::shutdown_timeout),
+    /// waiting up to `grace` for the blocking tasks that are still running to finish. As per tokio's
+    /// semantics, async tasks are dropped the next time they yield, and blocking tasks that are still running
+    /// after `grace` are left running on their threads.
+    ///
+    /// If other clones of this executor are alive, the runtime can not be shut down and keeps running the
+    /// remaining tasks for them. In that case this returns right after `f` completes.
+    ///
+    /// # Panics
+    ///
+    /// When called from within an async context, like [`block_on`](TokioTp::block_on).
+    //
+    pub fn block_on_then_shutdown<F: Future>(self, f: F, grace: Duration) -> F::Output {
+        let out = self.block_on(f);
+
+        let _ = self.shutdown_timeout(grace);
+
+        out
+    }
+
+    /// Run `f` until it completes or the process receives Ctrl-C (SIGINT on unix), whichever comes first,
+    /// the usual entry point of a server. Returns `None` on Ctrl-C, after dropping `f`, so you can go on with
+    /// a graceful shutdown, eg. with [`shutdown_timeout`](TokioTp::shutdown_timeout).
+    ///
+    /// If the signal handler can not be installed, only `f` completing ends the run. To stop on something
+    /// else than Ctrl-C, use [`BlockOn::block_on_with_shutdown`], which works for all executors.
+    ///
+    /// # Panics
+    ///
+    /// When called from within an async context, like [`block_on`](TokioTp::block_on).
+    //
+    #[cfg(feature = "signal")]
+    #[cfg_attr(nightly, doc(cfg(feature = "signal")))]
+    //
+    pub fn run_with_shutdown<F: Future>(&self, f: F) -> Option<F::Output> {
+        let ctrl_c = async {
+            if tokio::signal::ctrl_c().await.is_err() {
+                std::future::pending::<()>().await;
+            }
+        };
+
+        self.block_on_with_shutdown(f, ctrl_c)
+    }
+
     /// See: [tokio::runtime::Runtime::shutdown_timeout]
     ///
     ///  This tries to unwrap the Arc<Runtime> we hold, so that works only if no other clones are around. If this is not the
-    ///  only reference, self will be returned to you as an error. It means you cannot shutdown the runtime because there are
-    ///  other clones of the executor still alive.
+    ///  only reference, you get [`TokioTpError::ClonesAlive`]. Tokio panics when a runtime is shut down from within an
+    ///  async context, so in that case you get [`TokioTpError::CannotShutdownFromWithin`] instead, detected the
+    ///  same way as in [`try_block_on`](TokioTp::try_block_on). Either way, the error gives you the executor back.
     //
-    pub fn shutdown_timeout(mut self, duration: std::time::Duration) -> Result<(), Self> {
-        let arc = self.exec.take().unwrap();
+    pub fn shutdown_timeout(mut self, duration: Duration) -> Result<(), TokioTpShutdownError> {
+        if in_async_context() {
+            return Err(TokioTpShutdownError {
+                exec: self,
+                kind: TokioTpError::CannotShutdownFromWithin,
+            });
+        }
+
+        let arc = match self.exec.take() {
+            Some(arc) => arc,
+            None => return Ok(()),
+        };
 
         let rt = match Arc::try_unwrap(arc) {
             Ok(rt) => rt,
             Err(arc) => {
                 self.exec = Some(arc);
-                return Err(self);
+
+                return Err(TokioTpShutdownError {
+                    exec: self,
+                    kind: TokioTpError::ClonesAlive,
+                });
             }
         };
 
@@ -131,7 +298,10 @@ impl TokioTp {
     ///  other clones of the executor still alive.
     //
     pub fn shutdown_background(mut self) -> Result<(), Self> {
-        let arc = self.exec.take().unwrap();
+        let arc = match self.exec.take() {
+            Some(arc) => arc,
+            None => return Ok(()),
+        };
 
         let rt = match Arc::try_unwrap(arc) {
             Ok(rt) => rt,
@@ -145,25 +315,42 @@ impl TokioTp {
 
         Ok(())
     }
+
+    /// Take the tokio runtime out of the wrapper, eg. to pass it to code that needs the raw runtime.
+    /// Other clones of the executor keep working, as they share the same runtime.
+    ///
+    /// Returns `None` when the runtime has already been shut down.
+    //
+    pub fn into_inner(mut self) -> Option<Arc<Runtime>> {
+        self.exec.take()
+    }
+}
+
+impl Timer for TokioTp {
+    fn sleep(&self, dur: Duration) -> BoxFuture<'static, ()> {
+        match &self.exec {
+            Some(exec) => {
+                // Enter the runtime so this also works when called outside of block_on.
+                let _guard = exec.enter();
+                Box::pin(tokio::time::sleep(dur))
+            }
+
+            // The runtime is gone, the timer would never fire.
+            None => Box::pin(futures_util::future::pending()),
+        }
+    }
 }
 
 impl Spawn for TokioTp {
     fn spawn_obj(&self, future: FutureObj<'static, ()>) -> Result<(), SpawnError> {
         // We drop the JoinHandle, so the task becomes detached.
         //
-        let _ = self.exec.as_ref().unwrap().spawn(future);
-
-        Ok(())
-    }
-}
+        let _ = self
+            .exec
+            .as_ref()
+            .ok_or_else(SpawnError::shutdown)?
+            .spawn(future);
 
-impl SpawnStatic for TokioTp {
-    fn spawn<Output, Fut>(future: Fut) -> Result<(), SpawnError>
-    where
-        Fut: Future<Output = Output> + Send + 'static,
-        Output: Send + 'static,
-    {
-        let _ = tokio::task::spawn(future);
         Ok(())
     }
 }
@@ -173,43 +360,110 @@ impl<Out: 'static + Send> SpawnHandle<Out> for TokioTp {
         &self,
         future: FutureObj<'static, Out>,
     ) -> Result<JoinHandle<Out>, SpawnError> {
-        Ok(TokioJoinHandle::new(self.exec.as_ref().unwrap().spawn(future)).into())
+        let exec = self.exec.as_ref().ok_or_else(SpawnError::shutdown)?;
+
+        Ok(TokioJoinHandle::new(exec.spawn(future))
+            .on_runtime(exec.handle())
+            .into())
     }
-}
 
-impl LocalSpawnStatic for TokioTp {
-    fn spawn_local<Output, Fut>(future: Fut) -> Result<(), SpawnError>
-    where
-        Fut: Future<Output = Output> + 'static,
-        Output: 'static,
-    {
-        let _ = tokio::task::spawn_local(future);
-        Ok(())
+    #[cfg(all(tokio_unstable, feature = "tokio_tracing"))]
+    //
+    fn spawn_handle_obj_named(
+        &self,
+        name: &str,
+        future: FutureObj<'static, Out>,
+    ) -> Result<JoinHandle<Out>, SpawnError> {
+        let exec = self.exec.as_ref().ok_or_else(SpawnError::shutdown)?;
+
+        let handle = tokio::task::Builder::new()
+            .name(name)
+            .spawn_on(future, exec.handle())
+            .map_err(|_| SpawnError::shutdown())?;
+
+        Ok(TokioJoinHandle::new(handle)
+            .on_runtime(exec.handle())
+            .into())
     }
 }
 
-impl SpawnHandleStatic for TokioTp {
-    fn spawn_handle<Output, Fut>(future: Fut) -> Result<JoinHandle<Output>, SpawnError>
-    where
-        Fut: Future<Output = Output> + Send + 'static,
-        Output: 'static + Send,
-    {
-        Ok(TokioJoinHandle::new(tokio::task::spawn(future)).into())
+/// Errors that can happen when calling [`TokioTp::try_block_on`] or [`TokioTp::shutdown_timeout`].
+//
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+//
+#[cfg_attr(nightly, doc(cfg(feature = "tokio_tp")))]
+//
+pub enum TokioTpError {
+    /// The runtime has already been shut down.
+    Shutdown,
+    /// `block_on` was called from within a tokio runtime, which tokio does not allow.
+    InsideRuntime,
+    /// Other clones of the executor are still alive, so the runtime can not be shut down.
+    ClonesAlive,
+    /// `shutdown_timeout` was called from within a tokio runtime, which tokio does not allow.
+    CannotShutdownFromWithin,
+}
+
+impl Display for TokioTpError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TokioTpError::Shutdown => f.write_str("the tokio runtime has been shut down"),
+            TokioTpError::InsideRuntime => {
+                f.write_str("cannot block on a future from within a tokio runtime")
+            }
+            TokioTpError::ClonesAlive => f.write_str(
+                "cannot shut down the runtime while other clones of the executor are alive",
+            ),
+            TokioTpError::CannotShutdownFromWithin => {
+                f.write_str("cannot shut down a tokio runtime from within a tokio runtime")
+            }
+        }
     }
 }
 
-impl LocalSpawnHandleStatic for TokioTp {
-    fn spawn_handle_local<Output, Fut>(future: Fut) -> Result<JoinHandle<Output>, SpawnError>
-    where
-        Fut: Future<Output = Output> + 'static,
-        Output: 'static,
-    {
-        Ok(TokioJoinHandle::new(tokio::task::spawn_local(future)).into())
+impl Error for TokioTpError {}
+
+/// The error returned by [`TokioTp::shutdown_timeout`]. Gives you the executor back, so you can keep
+/// using it or try again later.
+//
+#[derive(Debug)]
+//
+#[cfg_attr(nightly, doc(cfg(feature = "tokio_tp")))]
+//
+pub struct TokioTpShutdownError {
+    exec: TokioTp,
+    kind: TokioTpError,
+}
+
+impl TokioTpShutdownError {
+    /// Why the runtime could not be shut down.
+    //
+    pub fn kind(&self) -> TokioTpError {
+        self.kind
+    }
+
+    /// Get the executor back.
+    //
+    pub fn into_inner(self) -> TokioTp {
+        self.exec
     }
 }
 
-impl YieldNowStatic for TokioTp {
-    fn yield_now() -> BoxFuture<'static, ()> {
-        Box::pin(tokio::task::yield_now())
+impl Display for TokioTpShutdownError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.kind, f)
     }
 }
+
+impl Error for TokioTpShutdownError {}
+
+#[cfg(test)]
+//
+mod tests {
+    use super::*;
+
+    // Clones share the runtime and can be sent to other threads.
+    //
+    static_assertions::assert_impl_all!(TokioTp: Send, Sync, Clone);
+}
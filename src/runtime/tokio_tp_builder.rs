@@ -2,9 +2,9 @@
 //
 use
 {
-	crate          :: { TokioTp   } ,
-	std            :: { sync::Arc } ,
-	tokio::runtime :: { Builder   } ,
+	crate          :: { TokioTp       } ,
+	std            :: { io, sync::Arc } ,
+	tokio::runtime :: { Builder       } ,
 };
 
 
@@ -12,24 +12,38 @@ use
 /// as tokio does not make this information available on it's `Runtime` type.
 ///
 /// Further allows you access to the tokio builder so you can set the other configuration options on it as you see fit.
+///
+/// The options set through the methods of this builder are checked by [`build`](TokioTpBuilder::build), so a
+/// configuration that tokio would reject with a panic, like 0 worker threads, gives you an error instead.
 //
 #[ derive(Debug) ]
 //
 pub struct TokioTpBuilder
 {
-	builder: Builder,
+	builder               : Builder       ,
+	worker_threads        : Option<usize> ,
+	max_blocking_threads  : Option<usize> ,
+	global_queue_interval : Option<u32>   ,
+	event_interval        : Option<u32>   ,
 }
 
 
 impl TokioTpBuilder
 {
-	/// Constructor.
+	/// Constructor. Enables the tokio timer, which is needed for the [`Timer`](crate::Timer) implementation.
 	//
 	pub fn new() -> Self
 	{
+		let mut builder = Builder::new_multi_thread();
+		builder.enable_time();
+
 		Self
 		{
-			builder: Builder::new_multi_thread(),
+			builder                       ,
+			worker_threads        : None  ,
+			max_blocking_threads  : None  ,
+			global_queue_interval : None  ,
+			event_interval        : None  ,
 		}
 	}
 
@@ -42,13 +56,104 @@ impl TokioTpBuilder
 	}
 
 
+	/// Sets the number of worker threads that run the async tasks. Tokio defaults to the number of cores.
+	/// See: [Builder::worker_threads].
+	///
+	/// [`build`](TokioTpBuilder::build) returns an error when `threads` is 0.
+	//
+	pub fn worker_threads( &mut self, threads: usize ) -> &mut Self
+	{
+		self.worker_threads = Some( threads );
+		self
+	}
+
+
+	/// Sets the maximum number of threads tokio spawns for blocking tasks, eg. from
+	/// [`SpawnBlocking`](crate::SpawnBlocking). They are spawned on demand and shut down when idle. When they
+	/// are all busy, further blocking tasks wait in a queue. Tokio defaults to 512. See: [Builder::max_blocking_threads].
+	///
+	/// [`build`](TokioTpBuilder::build) returns an error when `threads` is 0.
+	//
+	pub fn max_blocking_threads( &mut self, threads: usize ) -> &mut Self
+	{
+		self.max_blocking_threads = Some( threads );
+		self
+	}
+
+
+	/// Sets the number of scheduler ticks after which a worker checks the global task queue
+	/// for new tasks. Lower values favor starting new work over continuing with tasks that yielded.
+	///
+	/// By default tokio adjusts this dynamically for the multi-threaded scheduler, based on how long
+	/// tasks take to poll. See: [Builder::global_queue_interval].
+	///
+	/// [`build`](TokioTpBuilder::build) returns an error when `interval` is 0.
+	//
+	pub fn global_queue_interval( &mut self, interval: u32 ) -> &mut Self
+	{
+		self.global_queue_interval = Some( interval );
+		self
+	}
+
+
+	/// Sets the number of scheduler ticks after which a worker polls the IO and timer drivers
+	/// for external events. Lower values reduce the latency of picking up IO events when tasks
+	/// rarely yield.
+	///
+	/// Tokio defaults to 61. See: [Builder::event_interval].
+	///
+	/// [`build`](TokioTpBuilder::build) returns an error when `interval` is 0.
+	//
+	pub fn event_interval( &mut self, interval: u32 ) -> &mut Self
+	{
+		self.event_interval = Some( interval );
+		self
+	}
+
+
+	/// Sets the stack size in bytes of the worker threads, eg. when deeply nested futures overflow the
+	/// default stack. Tokio defaults to 2 MiB. See: [Builder::thread_stack_size].
+	///
+	/// This also applies to the threads tokio spawns for blocking tasks, up to
+	/// [`max_blocking_threads`](TokioTpBuilder::max_blocking_threads) of them, each with its own stack. So raising it
+	/// increases the memory reserved for those too. Note that [`TokioTp::block_on`] runs the future on the calling
+	/// thread, so its stack size is not affected.
+	//
+	pub fn thread_stack_size( &mut self, size: usize ) -> &mut Self
+	{
+		self.builder.thread_stack_size( size );
+		self
+	}
+
+
 	/// Create the actual executor.
 	///
-	/// The error comes from tokio. From their docs, no idea why it is there or what could go wrong.
-	/// Suppose spawning threads could fail...
+	/// Returns an error of kind [`InvalidInput`](io::ErrorKind::InvalidInput) when one of the options set on
+	/// this builder is 0. Otherwise the error comes from tokio. From their docs, no idea why it is there or what
+	/// could go wrong. Suppose spawning threads could fail...
 	//
-	pub fn build( &mut self ) -> Result<TokioTp, std::io::Error>
+	pub fn build( &mut self ) -> Result<TokioTp, io::Error>
 	{
+		if let Some( threads ) = non_zero( "worker_threads", self.worker_threads )?
+		{
+			self.builder.worker_threads( threads );
+		}
+
+		if let Some( threads ) = non_zero( "max_blocking_threads", self.max_blocking_threads )?
+		{
+			self.builder.max_blocking_threads( threads );
+		}
+
+		if let Some( interval ) = non_zero( "global_queue_interval", self.global_queue_interval )?
+		{
+			self.builder.global_queue_interval( interval );
+		}
+
+		if let Some( interval ) = non_zero( "event_interval", self.event_interval )?
+		{
+			self.builder.event_interval( interval );
+		}
+
 		let exec = self.builder.build()?;
 
 		Ok( TokioTp
@@ -59,6 +164,23 @@ impl TokioTpBuilder
 }
 
 
+// Tokio panics when these options are 0, so check them before passing them on.
+//
+fn non_zero<T: Default + PartialEq>( option: &str, value: Option<T> ) -> Result<Option<T>, io::Error>
+{
+	match value
+	{
+		Some( v ) if v == T::default() => Err( io::Error::new
+		(
+			io::ErrorKind::InvalidInput,
+			format!( "TokioTpBuilder: {} must be greater than 0", option ),
+		)),
+
+		_ => Ok( value ),
+	}
+}
+
+
 impl Default for TokioTpBuilder
 {
 	fn default() -> Self
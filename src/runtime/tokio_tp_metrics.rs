@@ -0,0 +1,87 @@
+//! Provides metrics about the runtime behind TokioTp.
+//
+use crate::TokioTp;
+use tokio::runtime::RuntimeMetrics;
+
+/// A snapshot handle on the metrics of a [`TokioTp`] runtime, see [`TokioTp::metrics`].
+///
+/// This wraps [tokio::runtime::RuntimeMetrics] so the API stays the same when tokio renames things.
+/// The metrics about the blocking pool are unstable in tokio and only available when compiling with
+/// `RUSTFLAGS="--cfg tokio_unstable"`.
+//
+#[derive(Debug, Clone)]
+//
+#[cfg_attr(nightly, doc(cfg(feature = "tokio_metrics")))]
+//
+pub struct TokioTpMetrics {
+    metrics: RuntimeMetrics,
+}
+
+impl TokioTpMetrics {
+    /// The number of worker threads used by the runtime.
+    //
+    pub fn num_workers(&self) -> usize {
+        self.metrics.num_workers()
+    }
+
+    /// The number of tasks currently alive on the runtime.
+    //
+    pub fn num_alive_tasks(&self) -> usize {
+        self.metrics.num_alive_tasks()
+    }
+
+    /// The number of tasks in the global queue, waiting to be picked up by a worker.
+    //
+    pub fn global_queue_depth(&self) -> usize {
+        self.metrics.global_queue_depth()
+    }
+
+    /// The number of threads in the blocking pool, including idle ones.
+    //
+    #[cfg(tokio_unstable)]
+    //
+    pub fn num_blocking_threads(&self) -> usize {
+        self.metrics.num_blocking_threads()
+    }
+
+    /// The number of idle threads in the blocking pool.
+    //
+    #[cfg(tokio_unstable)]
+    //
+    pub fn num_idle_blocking_threads(&self) -> usize {
+        self.metrics.num_idle_blocking_threads()
+    }
+
+    /// The number of blocking tasks waiting for a thread to become available.
+    /// When this keeps growing, the blocking pool is saturated.
+    //
+    #[cfg(tokio_unstable)]
+    //
+    pub fn blocking_queue_depth(&self) -> usize {
+        self.metrics.blocking_queue_depth()
+    }
+
+    /// The number of active tasks on the runtime. Same as [`num_alive_tasks`](TokioTpMetrics::num_alive_tasks).
+    //
+    pub fn active_tasks_count(&self) -> usize {
+        self.metrics.num_alive_tasks()
+    }
+
+    /// Access the metrics from tokio directly.
+    //
+    pub fn inner(&self) -> &RuntimeMetrics {
+        &self.metrics
+    }
+}
+
+impl TokioTp {
+    /// Get the metrics of the runtime. Returns `None` if the runtime has been shut down.
+    //
+    #[cfg_attr(nightly, doc(cfg(feature = "tokio_metrics")))]
+    //
+    pub fn metrics(&self) -> Option<TokioTpMetrics> {
+        self.exec.as_ref().map(|rt| TokioTpMetrics {
+            metrics: rt.metrics(),
+        })
+    }
+}
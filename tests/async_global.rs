@@ -13,6 +13,15 @@
 //
 // ✔ Joinhandle::detach allows task to keep running.
 // ✔ Joinhandle::drop aborts the task.
+// ✔ Joinhandle::as_async_global gives access to the native handle.
+// ✔ Joinhandle::from_async_global wraps a task spawned directly on async-global-executor.
+//
+// ✔ local tasks spawned outside of block_on run once block_on is called on that thread.
+// ✔ block_on drives !Send local tasks on the current thread.
+// ✔ run_local drives !Send tasks on a local executor of its own.
+// ✔ run_local drops the tasks that are still running when it returns, and spawning fails after.
+//
+// ✔ use AsyncGlobal through the static spawn traits.
 //
 mod common;
 
@@ -168,6 +177,7 @@ impl Drop for DropNotify
 // Make sure that a task that is currently waiting for it's waker to be woken up
 // get's dropped when JoinHandle is dropped.
 //
+#[ cfg(not( feature = "detach_on_drop" )) ]
 #[ test ]
 //
 fn join_handle_abort()
@@ -374,3 +384,155 @@ fn spawn_handle_local_os()
 
 	assert_eq!( 5u8, result );
 }
+
+
+
+// Joinhandle::as_async_global gives access to the native handle.
+//
+#[ test ]
+//
+fn join_handle_as_async_global()
+{
+	let exec        = AsyncGlobal::default();
+	let join_handle = exec.spawn_handle( async { 5u8 } ).expect( "spawn task" );
+
+	assert!( join_handle.as_async_global().is_some() );
+	assert_eq!( AsyncGlobal::block_on( join_handle ), 5 );
+}
+
+
+
+// Joinhandle::from_async_global wraps a task spawned directly on async-global-executor.
+//
+#[ test ]
+//
+fn join_handle_from_async_global()
+{
+	let join_handle = JoinHandle::from_async_global( async_global_executor::spawn( async { 5u8 } ) );
+
+	assert!( join_handle.as_async_global().is_some() );
+	assert_eq!( AsyncGlobal::block_on( join_handle ), 5 );
+}
+
+
+
+// local tasks spawned outside of block_on run once block_on is called on that thread.
+//
+#[ test ]
+//
+fn spawn_local_outside_block_on()
+{
+	let handle = AsyncGlobal.spawn_handle_local( async { 5u8 } ).expect( "spawn" );
+
+	assert_eq!( 5u8, AsyncGlobal::block_on( handle ) );
+}
+
+
+
+// block_on drives !Send local tasks on the current thread.
+//
+#[ test ]
+//
+fn block_on_local()
+{
+	let res = AsyncGlobal::block_on( async
+	{
+		let rc     = std::rc::Rc::new( 5u8 );
+		let handle = AsyncGlobal.spawn_handle_local( async move { *rc } ).expect( "spawn" );
+
+		handle.await
+	});
+
+	assert_eq!( 5, res );
+}
+
+
+
+// run_local drives !Send tasks on a local executor of its own.
+//
+#[ test ]
+//
+fn run_local()
+{
+	let res = AsyncGlobal::run_local( |local| async move
+	{
+		let rc     = std::rc::Rc::new( 5u8 );
+		let inner  = local.clone();
+
+		// Tasks can hold the handle to spawn more tasks.
+		//
+		let handle = local.spawn_handle_local( async move
+		{
+			inner.spawn_handle_local( async move { *rc } ).expect( "spawn" ).await
+
+		}).expect( "spawn" );
+
+		handle.await
+	});
+
+	assert_eq!( 5, res );
+}
+
+
+
+// run_local drops the tasks that are still running when it returns, and spawning fails after.
+//
+#[ test ]
+//
+fn run_local_drops_tasks()
+{
+	let (tx, mut rx)             = mpsc::channel::<u8>( 1 );
+	let (started_tx, started_rx) = futures::channel::oneshot::channel();
+
+	let local = AsyncGlobal::run_local( |local| async move
+	{
+		local.spawn_local( async move
+		{
+			started_tx.send(()).expect( "send started" );
+			futures::future::pending::<()>().await;
+			drop( tx );
+
+		}).expect( "spawn" );
+
+		started_rx.await.expect( "task started" );
+		local
+	});
+
+	// The sender is dropped with the task, without it ever sending.
+	//
+	assert_eq!( None, AsyncGlobal::block_on( rx.next() ) );
+
+	assert!( local.status_local().is_err() );
+	assert!( local.spawn_local( async {} ).expect_err( "executor is gone" ).is_shutdown() );
+}
+
+
+
+// use AsyncGlobal through the static spawn traits.
+//
+#[ test ]
+//
+fn spawn_static()
+{
+	AsyncGlobal::block_on( async
+	{
+		assert_eq!( 4, spawn_static_all::<AsyncGlobal>().await );
+	});
+}
+
+
+async fn spawn_static_all<E>() -> u8
+
+	where E: SpawnStatic + SpawnHandleStatic + LocalSpawnStatic + LocalSpawnHandleStatic
+{
+	let (tx , rx ) = oneshot::channel();
+	let (ltx, lrx) = oneshot::channel();
+
+	E::spawn      ( async move { tx .send( 1u8          ).expect( "send" ); } ).expect( "spawn"       );
+	E::spawn_local( async move { ltx.send( Rc::new(1u8) ).expect( "send" ); } ).expect( "spawn local" );
+
+	let handle = E::spawn_handle      ( async { 1u8          } ).expect( "spawn handle"       ).await;
+	let local  = E::spawn_handle_local( async { Rc::new(1u8) } ).expect( "spawn handle local" ).await;
+
+	rx.await.expect( "recv" ) + *lrx.await.expect( "recv" ) + handle + *local
+}
@@ -10,9 +10,21 @@
 // ✔ pass a     AsyncStd  to a function that takes exec: `impl SpawnHandle`
 // ✔ pass a Arc<AsyncStd> to a function that takes exec: `impl SpawnHandle`
 // ✔ pass a    &AsyncStd  to a function that takes exec: `&dyn SpawnHandle`
+// ✔ pass a     AsyncStd  to a function that takes exec: `impl Executor`
 //
 // ✔ Joinhandle::detach allows task to keep running.
 // ✔ Joinhandle::drop aborts the task.
+// ✔ Joinhandle::as_async_std gives access to the native handle.
+// ✔ Joinhandle::from_async_std wraps a task spawned directly on async-std.
+// ✔ Joinhandle::from_async_std_abortable wraps an abortable task, dropping it aborts the task.
+// ✔ Joinhandle::abort reports that a task from from_async_std can not be aborted.
+//
+// ✔ spawning local tasks outside of an async-std task returns an error of kind Unsupported.
+//
+// ✔ the yield_now free function lets other tasks run.
+//
+// ✔ use AsyncStd through the static spawn traits.
+// ✔ make_portable gives a handle that can be awaited from another executor.
 //
 mod common;
 
@@ -22,6 +34,7 @@ use
 	futures :: { channel::mpsc, StreamExt } ,
 	std             :: { time::Duration           } ,
 	futures_timer   :: { Delay                    } ,
+	futures::future::abortable,
 	async_std_crate as async_std,
 };
 
@@ -151,6 +164,19 @@ fn spawn_handle_os()
 }
 
 
+// pass a AsyncStd to a function that takes exec: `impl Executor`
+//
+#[ test ]
+//
+fn spawn_executor()
+{
+	let exec   = AsyncStd::default();
+	let result = AsyncStd::block_on( increment_executor( 4, exec ) );
+
+	assert_eq!( 5u8, result );
+}
+
+
 struct DropNotify
 {
 	tx: Option<oneshot::Sender<()>>,
@@ -169,6 +195,7 @@ impl Drop for DropNotify
 // Make sure that a task that is currently waiting for it's waker to be woken up
 // get's dropped when JoinHandle is dropped.
 //
+#[ cfg(not( feature = "detach_on_drop" )) ]
 #[ async_std::test ]
 //
 async fn join_handle_abort()
@@ -369,3 +396,177 @@ fn spawn_handle_local_os()
 
 	assert_eq!( 5u8, result );
 }
+
+
+
+// Joinhandle::as_async_std gives access to the native handle.
+//
+#[ test ]
+//
+fn join_handle_as_async_std()
+{
+	let exec        = AsyncStd::default();
+	let join_handle = exec.spawn_handle( async { 5u8 } ).expect( "spawn task" );
+
+	assert!( join_handle.as_async_std().is_some() );
+	assert_eq!( AsyncStd::block_on( join_handle ), 5 );
+}
+
+
+
+// Joinhandle::from_async_std wraps a task spawned directly on async-std.
+//
+#[ test ]
+//
+fn join_handle_from_async_std()
+{
+	let join_handle = JoinHandle::from_async_std( async_std::task::spawn( async { 5u8 } ) );
+
+	assert!( join_handle.as_async_std().is_none() );
+	assert_eq!( AsyncStd::block_on( join_handle ), 5 );
+}
+
+
+
+// Joinhandle::from_async_std_abortable wraps an abortable task, dropping it aborts the task.
+//
+#[ cfg(not( feature = "detach_on_drop" )) ]
+#[ test ]
+//
+fn join_handle_from_async_std_abortable()
+{
+	AsyncStd::block_on( async
+	{
+		let (mut tx, mut rx) = mpsc::channel::<u8>( 1 );
+
+		let task = async move
+		{
+			Delay::new( Duration::from_millis( 50 ) ).await;
+			let _ = tx.try_send( 5 );
+		};
+
+		let (fut, a_handle) = abortable( task );
+		let join_handle     = JoinHandle::from_async_std_abortable( async_std::task::spawn( fut ), a_handle );
+
+		assert!( join_handle.as_async_std().is_some() );
+
+		drop( join_handle );
+
+		// The sender is dropped with the aborted task, so the stream ends without a value.
+		//
+		assert_eq!( None, rx.next().await );
+	});
+}
+
+
+
+// Joinhandle::abort reports that a task from from_async_std can not be aborted.
+//
+#[ test ]
+//
+fn join_handle_abort_plain()
+{
+	let plain     = JoinHandle::from_async_std( async_std::task::spawn( async { 5u8 } ) );
+	let abortable = AsyncStd.spawn_handle( async { 5u8 } ).expect( "spawn" );
+
+	assert!( !plain.is_abortable() );
+	assert!( !plain.detach_on_drop().abort() );
+
+	assert!( abortable.is_abortable() );
+	assert!( abortable.abort() );
+}
+
+
+
+// spawning local tasks outside of an async-std task returns an error.
+//
+#[ test ]
+//
+fn spawn_local_outside_task()
+{
+	assert!( AsyncStd.status_local().is_err() );
+	assert!( AsyncStd.spawn_local( async {} ).is_err() );
+	assert!( AsyncStd.spawn_handle_local( async {} ).is_err() );
+
+	let err = AsyncStd.spawn_local( async {} ).expect_err( "outside task" );
+
+	assert_eq!( SpawnErrorKind::Unsupported, err.kind() );
+
+	AsyncStd::block_on( async
+	{
+		assert!( AsyncStd.status_local().is_ok() );
+		assert_eq!( 5, AsyncStd.spawn_handle_local( async { 5 } ).expect( "spawn" ).await );
+	});
+}
+
+
+
+// the yield_now free function lets other tasks run. It's only there when async-std is the only runtime enabled.
+//
+#[ cfg(not(any( feature = "tokio", feature = "async_global", feature = "glommio", feature = "compio", feature = "bindgen" ))) ]
+#[ test ]
+//
+fn yield_now_free_fn()
+{
+	AsyncStd::block_on( async
+	{
+		let flag  = Rc::new( std::cell::Cell::new( false ) );
+		let flag2 = flag.clone();
+
+		AsyncStd.spawn_local( async move { flag2.set( true ); } ).expect( "spawn" );
+
+		for _ in 0..10
+		{
+			if flag.get() { break; }
+
+			async_executors::yield_now().await;
+		}
+
+		assert!( flag.get() );
+	});
+}
+
+
+
+// use AsyncStd through the static spawn traits.
+//
+#[ test ]
+//
+fn spawn_static()
+{
+	AsyncStd::block_on( async
+	{
+		assert_eq!( 4, spawn_static_all::<AsyncStd>().await );
+	});
+}
+
+
+async fn spawn_static_all<E>() -> u8
+
+	where E: SpawnStatic + SpawnHandleStatic + LocalSpawnStatic + LocalSpawnHandleStatic
+{
+	let (tx , rx ) = oneshot::channel();
+	let (ltx, lrx) = oneshot::channel();
+
+	E::spawn      ( async move { tx .send( 1u8          ).expect( "send" ); } ).expect( "spawn"       );
+	E::spawn_local( async move { ltx.send( Rc::new(1u8) ).expect( "send" ); } ).expect( "spawn local" );
+
+	let handle = E::spawn_handle      ( async { 1u8          } ).expect( "spawn handle"       ).await;
+	let local  = E::spawn_handle_local( async { Rc::new(1u8) } ).expect( "spawn handle local" ).await;
+
+	rx.await.expect( "recv" ) + *lrx.await.expect( "recv" ) + handle + *local
+}
+
+
+
+// make_portable gives a handle that can be awaited from another executor.
+//
+#[ test ]
+//
+fn make_portable()
+{
+	let handle = AsyncStd.spawn_handle( async { async_std::task::yield_now().await; 5u8 } ).expect( "spawn" ).make_portable();
+
+	assert!( handle.as_async_std().is_none() );
+	assert_eq!( 5, futures::executor::block_on( handle ) );
+}
@@ -0,0 +1,36 @@
+#![ cfg(all( feature = "async_std", not(target_os = "unknown") )) ]
+
+// The threadpool is global, so these tests need their own process.
+//
+// Tested:
+//
+// ✔ the builder configures the threadpool.
+// ✔ building fails when the threadpool is already running.
+//
+mod common;
+
+use
+{
+	common  :: { *        } ,
+	std     :: { io       } ,
+};
+
+
+#[ test ]
+//
+fn builder()
+{
+	let exec = AsyncStdBuilder::new().threads( 2 ).name( "builder_test" ).build().expect( "build AsyncStd" );
+
+	let name = AsyncStd::block_on( exec.spawn_handle( async
+	{
+		std::thread::current().name().expect( "some name" ).to_string()
+
+	}).expect( "spawn" ));
+
+	assert_eq!( "builder_test", name );
+
+	let err = AsyncStdBuilder::new().threads( 3 ).build().expect_err( "already running" );
+
+	assert_eq!( io::ErrorKind::AlreadyExists, err.kind() );
+}
@@ -21,6 +21,8 @@
 // ✔ pass a  Rc<AsyncStd> to a function that takes exec:  `impl LocalSpawnHandle`
 // ✔ pass a    &AsyncStd  to a function that takes exec:  `&dyn LocalSpawnHandle`
 //
+// ✔ local spawning works outside of an async-std task.
+//
 mod common;
 
 use
@@ -347,3 +349,22 @@ fn spawn_handle_os_local()
 	AsyncStd.spawn_local( fut ).expect( "spawn future" );
 }
 
+
+
+
+// local spawning works outside of an async-std task.
+//
+#[ wasm_bindgen_test ]
+//
+fn spawn_local_outside_task()
+{
+	assert!( AsyncStd.status_local().is_ok() );
+
+	let handle = AsyncStd.spawn_handle_local( async { 5u8 } ).expect( "spawn" );
+
+	AsyncStd.spawn_local( async move
+	{
+		assert_eq!( 5u8, handle.await );
+
+	}).expect( "spawn future" );
+}
@@ -20,13 +20,23 @@
 // ✔ pass a  Rc<Bindgen> to a function that takes exec:  `impl LocalSpawnHandle`
 // ✔ pass a    &Bindgen  to a function that takes exec:  `&dyn LocalSpawnHandle`
 //
+// ✔ Joinhandle::drop aborts the task.
+// ✔ Joinhandle::drop aborts a local task.
+//
+// ✔ Timer::sleep resolves after the delay, and can be dropped before it fires.
+//
+// ✔ spawn !Send futures through LocalSpawnStatic and LocalSpawnHandleStatic.
+// ✔ spawn through SpawnStatic and SpawnHandleStatic.
+//
 mod common;
 
 use
 {
-	common            :: { *                        } ,
-	futures           :: { channel::mpsc, StreamExt } ,
-	wasm_bindgen_test :: { *                        } ,
+	common            :: { *                                             } ,
+	futures           :: { channel::mpsc, future::{ select, Either }, StreamExt } ,
+	futures_timer     :: { Delay                                         } ,
+	std               :: { cell::Cell, rc::Rc, time::Duration, sync::atomic::{ AtomicUsize, Ordering::SeqCst } } ,
+	wasm_bindgen_test :: { *                                             } ,
 };
 
 wasm_bindgen_test_configure!( run_in_browser );
@@ -374,3 +384,148 @@ fn spawn_handle_os_local()
 	exec.spawn_local( fut ).expect( "spawn future" );
 }
 
+
+
+// Joinhandle::drop aborts the task.
+// Make sure the task is no longer polled by the browser event loop once the handle is dropped.
+//
+#[ wasm_bindgen_test ]
+//
+async fn join_handle_abort()
+{
+	let exec    = Bindgen::default();
+	let counter = Arc::new( AtomicUsize::new( 0 ) );
+	let cnt2    = counter.clone();
+
+	let join_handle = exec.spawn_handle( async move
+	{
+		loop
+		{
+			cnt2.fetch_add( 1, SeqCst );
+			Delay::new( Duration::from_millis(5) ).await;
+		}
+
+	}).expect( "spawn task" );
+
+	// Let the task run for a bit.
+	//
+	Delay::new( Duration::from_millis(30) ).await;
+
+	drop( join_handle );
+
+	let stopped_at = counter.load( SeqCst );
+	assert!( stopped_at > 0 );
+
+	Delay::new( Duration::from_millis(30) ).await;
+
+	assert_eq!( stopped_at, counter.load( SeqCst ) );
+}
+
+
+
+// Joinhandle::drop aborts a local task.
+// Make sure the task is no longer polled by the browser event loop once the handle is dropped.
+//
+#[ wasm_bindgen_test ]
+//
+async fn join_handle_abort_local()
+{
+	let exec    = Bindgen::default();
+	let counter = Rc::new( Cell::new( 0usize ) );
+	let cnt2    = counter.clone();
+
+	let join_handle = exec.spawn_handle_local( async move
+	{
+		loop
+		{
+			cnt2.set( cnt2.get() + 1 );
+			Delay::new( Duration::from_millis(5) ).await;
+		}
+
+	}).expect( "spawn task" );
+
+	// Let the task run for a bit.
+	//
+	Delay::new( Duration::from_millis(30) ).await;
+
+	drop( join_handle );
+
+	let stopped_at = counter.get();
+	assert!( stopped_at > 0 );
+
+	Delay::new( Duration::from_millis(30) ).await;
+
+	assert_eq!( stopped_at, counter.get() );
+}
+
+
+
+// Timer::sleep resolves after the delay, and can be dropped before it fires.
+//
+#[ wasm_bindgen_test ]
+//
+async fn timer_sleep()
+{
+	let exec = Bindgen::default();
+
+	// A shorter delay wins the race.
+	//
+	let early = select( exec.sleep( Duration::from_millis(50) ), Delay::new( Duration::from_millis(5) ) ).await;
+	assert!( matches!( early, Either::Right(_) ) );
+
+	// Dropping the first timer cleared it, so this one doesn't see a spurious wake up.
+	//
+	let late = select( exec.sleep( Duration::from_millis(5) ), Delay::new( Duration::from_millis(200) ) ).await;
+	assert!( matches!( late, Either::Left(_) ) );
+}
+
+
+
+
+// spawn !Send futures through LocalSpawnStatic and LocalSpawnHandleStatic.
+//
+#[ wasm_bindgen_test ]
+//
+async fn spawn_local_static()
+{
+	let (tx, mut rx) = mpsc::channel( 1 );
+	let not_send     = Rc::new( 5u8 );
+
+	<Bindgen as LocalSpawnStatic>::spawn_local( async move
+	{
+		let mut tx = tx;
+		tx.send( *not_send ).await.expect( "send" );
+
+	}).expect( "spawn_local" );
+
+	assert_eq!( Some(5), rx.next().await );
+
+	let not_send = Rc::new( 6u8 );
+	let handle   = <Bindgen as LocalSpawnHandleStatic>::spawn_handle_local( async move { *not_send } ).expect( "spawn_handle_local" );
+
+	assert_eq!( 6, handle.await );
+}
+
+
+
+// spawn through SpawnStatic and SpawnHandleStatic.
+//
+#[ wasm_bindgen_test ]
+//
+async fn spawn_static()
+{
+	let (tx, mut rx) = mpsc::channel( 1 );
+
+	<Bindgen as SpawnStatic>::spawn( async move
+	{
+		let mut tx = tx;
+		tx.send( 5u8 ).await.expect( "send" );
+
+	}).expect( "spawn" );
+
+	assert_eq!( Some(5), rx.next().await );
+
+	let handle = <Bindgen as SpawnHandleStatic>::spawn_handle( async { 6u8 } ).expect( "spawn_handle" );
+
+	assert_eq!( 6, handle.await );
+}
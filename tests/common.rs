@@ -116,6 +116,18 @@ pub async fn increment_spawn_handle_local(a: u8, exec: impl LocalSpawnHandle<Rc<
         .await
 }
 
+// A function that takes a generic executor bundling the common bounds and spawns a task.
+//
+#[allow(dead_code)]
+//
+pub async fn increment_executor(a: u8, exec: impl Executor<u8>) -> u8 {
+    let exec2 = exec.clone();
+
+    exec.spawn_handle(async move { increment_spawn_handle(a, exec2).await })
+        .expect("spawn handle")
+        .await
+}
+
 // A function that takes a trait object and spawns a task.
 //
 #[allow(dead_code)]
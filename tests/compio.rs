@@ -0,0 +1,234 @@
+#![cfg(feature = "compio")]
+
+// Tested:
+//
+// ✔ pass a     Compio  to a function that takes exec: `impl Spawn`
+// ✔ pass a    &Compio  to a function that takes exec: `&impl Spawn`
+// ✔ pass a     Compio  to a function that takes exec: `impl SpawnHandle`
+// ✔ pass a    &Compio  to a function that takes exec: `&dyn SpawnHandle`
+//
+// ✔ pass a    Compio  to a function that takes exec: `impl LocalSpawn`
+// ✔ pass a Rc<Compio> to a function that takes exec: `impl LocalSpawn`
+// ✔ pass a    Compio  to a function that takes exec: `impl LocalSpawnHandle`
+// ✔ pass a   &Compio  to a function that takes exec: `&dyn LocalSpawnHandle`
+//
+// ✔ Joinhandle::detach allows task to keep running.
+// ✔ Joinhandle::drop cancels the task.
+// ✔ yield_now lets other tasks run.
+// ✔ pass a builder with the queue depth set.
+//
+mod common;
+
+use {
+    common::*,
+    futures::{channel::mpsc, StreamExt},
+    std::{cell::Cell, rc::Rc},
+};
+
+// pass a Compio to a function that takes exec: `impl Spawn`
+//
+#[test]
+//
+fn spawn() {
+    let (tx, mut rx) = mpsc::channel(1);
+    let exec = Compio::new().expect("create compio runtime");
+    let ex2 = exec.clone();
+
+    let res = exec.block_on(async {
+        increment(4, ex2, tx);
+
+        rx.next().await.expect("Some")
+    });
+
+    assert_eq!(5u8, res);
+}
+
+// pass a &Compio to a function that takes exec: `&impl Spawn`
+//
+#[test]
+//
+fn spawn_ref() {
+    let (tx, mut rx) = mpsc::channel(1);
+    let exec = Compio::new().expect("create compio runtime");
+
+    let res = exec.block_on(async {
+        increment_ref(4, &exec, tx);
+
+        rx.next().await.expect("Some")
+    });
+
+    assert_eq!(5u8, res);
+}
+
+// pass a Compio to a function that takes exec: `impl SpawnHandle`
+//
+#[test]
+//
+fn spawn_handle() {
+    let exec = Compio::new().expect("create compio runtime");
+
+    let res = exec.block_on(increment_spawn_handle(4, exec.clone()));
+
+    assert_eq!(5u8, res);
+}
+
+// pass a &Compio to a function that takes exec: `&dyn SpawnHandle`
+//
+#[test]
+//
+fn spawn_handle_os() {
+    let exec = Compio::new().expect("create compio runtime");
+
+    let res = exec.block_on(increment_spawn_handle_os(4, &exec));
+
+    assert_eq!(5u8, res);
+}
+
+// pass a Compio to a function that takes exec: `impl LocalSpawn`
+//
+#[test]
+//
+fn spawn_local() {
+    let (tx, mut rx) = mpsc::channel(1);
+    let exec = Compio::new().expect("create compio runtime");
+
+    let res = exec.block_on(async {
+        increment_local(4, exec.clone(), tx);
+
+        rx.next().await.expect("Some")
+    });
+
+    assert_eq!(5u8, res);
+}
+
+// pass a Rc<Compio> to a function that takes exec: `impl LocalSpawn`
+//
+#[test]
+//
+fn spawn_clone_with_rc_local() {
+    let (tx, mut rx) = mpsc::channel(1);
+    let exec = Compio::new().expect("create compio runtime");
+
+    let res = exec.block_on(async {
+        increment_clone_local(4, Rc::new(exec.clone()), tx);
+
+        rx.next().await.expect("Some")
+    });
+
+    assert_eq!(5u8, res);
+}
+
+// pass a Compio to a function that takes exec: `impl LocalSpawnHandle`
+//
+#[test]
+//
+fn spawn_handle_local() {
+    let exec = Compio::new().expect("create compio runtime");
+
+    let res = exec.block_on(increment_spawn_handle_local(4, exec.clone()));
+
+    assert_eq!(5u8, *res);
+}
+
+// pass a &Compio to a function that takes exec: `&dyn LocalSpawnHandle`
+//
+#[test]
+//
+fn spawn_handle_local_os() {
+    let exec = Compio::new().expect("create compio runtime");
+
+    let res = exec.block_on(increment_spawn_handle_local_os(4, &exec));
+
+    assert_eq!(5u8, *res);
+}
+
+// Joinhandle::detach allows task to keep running.
+//
+#[test]
+//
+fn join_handle_detach() {
+    let exec = &Compio::new().expect("create compio runtime");
+
+    let (in_tx, in_rx) = oneshot::channel();
+    let (out_tx, out_rx) = oneshot::channel();
+
+    exec.block_on(async move {
+        let in_join_handle = exec
+            .spawn_handle(async move {
+                let content = in_rx.await.expect("receive on in");
+
+                out_tx.send(content).expect("send on out");
+            })
+            .expect("spawn task");
+
+        in_join_handle.detach();
+        in_tx.send(5u8).expect("send on in");
+
+        assert_eq!(out_rx.await, Ok(5));
+    });
+}
+
+// Joinhandle::drop cancels the task.
+//
+#[cfg(not(feature = "detach_on_drop"))]
+#[test]
+//
+fn join_handle_drop() {
+    let exec = &Compio::new().expect("create compio runtime");
+
+    let (in_tx, in_rx) = oneshot::channel::<()>();
+    let (out_tx, out_rx) = oneshot::channel::<()>();
+
+    exec.block_on(async move {
+        let join_handle = exec
+            .spawn_handle_local(async move {
+                let _out_tx = out_tx;
+                let _ = in_rx.await;
+            })
+            .expect("spawn task");
+
+        exec.yield_now().await;
+        drop(join_handle);
+
+        // The sender gets dropped with the task, without in_tx ever being used.
+        //
+        assert!(out_rx.await.is_err());
+        drop(in_tx);
+    });
+}
+
+// yield_now lets other tasks run.
+//
+#[test]
+//
+fn yield_now() {
+    let exec = &Compio::new().expect("create compio runtime");
+    let flag = Rc::new(Cell::new(false));
+    let flag2 = flag.clone();
+
+    exec.block_on(async move {
+        exec.spawn_local(async move { flag2.set(true) })
+            .expect("spawn task");
+
+        assert!(!flag.get());
+
+        exec.yield_now().await;
+
+        assert!(flag.get());
+    });
+}
+
+// pass a builder with the queue depth set.
+//
+#[test]
+//
+fn build_capacity() {
+    let exec = CompioBuilder::new()
+        .capacity(64)
+        .build()
+        .expect("create compio runtime");
+
+    let res = exec.block_on(increment_spawn_handle(4, exec.clone()));
+
+    assert_eq!(5u8, res);
+}
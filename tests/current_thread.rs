@@ -0,0 +1,160 @@
+#![ cfg(all( feature = "block_on", not(target_os = "unknown") )) ]
+
+// Tested:
+//
+// ✔ pass a CurrentThread to a function that takes exec: `impl BlockOn`
+// ✔ the block_on function drives a future woken up from another thread.
+//
+// ✔ pass a CurrentThread to a function that takes exec: `impl Spawn`
+// ✔ pass a CurrentThread to a function that takes exec: `impl LocalSpawn`
+// ✔ pass a CurrentThread to a function that takes exec: `impl SpawnHandle`
+// ✔ pass a CurrentThread to a function that takes exec: `impl LocalSpawnHandle`
+// ✔ YieldNow lets the other tasks run.
+// ✔ run_until_stalled runs the spawned tasks without a top level future.
+//
+mod common;
+
+use
+{
+	common  :: { *                                } ,
+	futures :: { channel::mpsc, StreamExt         } ,
+	std     :: { cell::RefCell                    } ,
+};
+
+
+fn run( exec: impl BlockOn ) -> u8
+{
+	exec.block_on( async { 5u8 } )
+}
+
+
+// pass a CurrentThread to a function that takes exec: `impl BlockOn`
+//
+#[ test ]
+//
+fn current_thread()
+{
+	assert_eq!( 5, run( CurrentThread::new() ) );
+}
+
+
+// the block_on function drives a future woken up from another thread.
+//
+#[ test ]
+//
+fn block_on_fn()
+{
+	let (tx, rx) = oneshot::channel();
+
+	let thread = std::thread::spawn( move || tx.send( 3u8 ).expect( "send" ) );
+
+	assert_eq!( Ok(3), async_executors::block_on( rx ) );
+
+	thread.join().expect( "join thread" );
+}
+
+
+// pass a CurrentThread to a function that takes exec: `impl Spawn`
+//
+#[ test ]
+//
+fn spawn()
+{
+	let (tx, mut rx) = mpsc::channel( 1 );
+	let exec         = CurrentThread::new();
+
+	increment( 4, exec.clone(), tx );
+
+	let result = exec.block_on( rx.next() ).expect( "Some" );
+
+		assert_eq!( 5u8, result );
+}
+
+
+// pass a CurrentThread to a function that takes exec: `impl LocalSpawn`
+//
+#[ test ]
+//
+fn spawn_local()
+{
+	let (tx, mut rx) = mpsc::channel( 1 );
+	let exec         = CurrentThread::new();
+
+	increment_local( 4, exec.clone(), tx );
+
+	let result = exec.block_on( rx.next() ).expect( "Some" );
+
+		assert_eq!( 5u8, result );
+}
+
+
+// pass a CurrentThread to a function that takes exec: `impl SpawnHandle`
+//
+#[ test ]
+//
+fn spawn_handle()
+{
+	let exec   = CurrentThread::new();
+	let result = exec.block_on( increment_spawn_handle( 4, exec.clone() ) );
+
+		assert_eq!( 5u8, result );
+}
+
+
+// pass a CurrentThread to a function that takes exec: `impl LocalSpawnHandle`
+//
+#[ test ]
+//
+fn spawn_handle_local()
+{
+	let exec   = CurrentThread::new();
+	let result = exec.block_on( increment_spawn_handle_local( 4, exec.clone() ) );
+
+		assert_eq!( 5u8, *result );
+}
+
+
+// YieldNow lets the other tasks run.
+//
+#[ test ]
+//
+fn yield_now()
+{
+	let exec  = CurrentThread::new();
+	let order = Rc::new( RefCell::new( Vec::new() ) );
+
+	for i in 0..2
+	{
+		let exec  = exec.clone();
+		let order = order.clone();
+
+		exec.clone().spawn_local( async move
+		{
+			order.borrow_mut().push( i );
+			exec.yield_now().await;
+			order.borrow_mut().push( i + 10 );
+
+		}).expect( "spawn" );
+	}
+
+	exec.run_until_stalled();
+
+	assert_eq!( vec![ 0, 1, 10, 11 ], *order.borrow() );
+}
+
+
+// run_until_stalled runs the spawned tasks without a top level future.
+//
+#[ test ]
+//
+fn run_until_stalled()
+{
+	let exec         = CurrentThread::new();
+	let (tx, mut rx) = mpsc::channel( 1 );
+
+	increment( 4, &exec, tx );
+
+	exec.run_until_stalled();
+
+	assert_eq!( Ok(5), rx.try_recv() );
+}
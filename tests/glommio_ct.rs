@@ -21,8 +21,24 @@
 // ✔ pass a Rc<GlommioCt> to a function that takes exec: `impl LocalSpawnHandle`
 // ✔ pass a   &GlommioCt  to a function that takes exec: `&dyn LocalSpawnHandle`
 //
-// x we can spawn without being in a future running on block_on.
+// ✔ spawning outside of block_on returns an error instead of panicking, status reports it.
+// ✔ Glommio spawns on an executor that wasn't started through Glommio::run.
 // ✔ Joinhandle::detach allows task to keep running.
+// ✔ spawn_local_detached runs the task to completion after the handle is dropped.
+//
+// ✔ build with a custom preempt timer and io memory.
+// ✔ spawn the executor on a named thread with a custom stack size.
+// ✔ GlommioCtBuilder::block_on reuses the executor, so tasks survive between calls.
+// ✔ GlommioCtBuilder spawns local tasks on its executor, or errors when there is none yet.
+// ✔ GlommioCtBuilder spawns Send futures with spawn_send, status_local tells whether there is an executor.
+//
+// ✔ sleep, sleep_until and LocalTimer::sleep_local wait for the given duration.
+// ✔ spawn_handle_into: a task on a queue with more shares makes more progress.
+//
+// ✔ shutdown reports the pending tasks and allows creating a new executor on the thread.
+// ✔ shutdown gives back the executor when other clones are alive.
+//
+// ✔ affinity: pin a thread to a cpu and read back the set, other threads keep theirs.
 //
 mod common;
 
@@ -307,23 +323,57 @@ fn spawn_handle_local_os() {
     assert_eq!(5u8, result);
 }
 
-// make sure we can spawn without being in a future running on block_on.
+// spawning outside of block_on returns an error instead of panicking, status reports it.
 //
 #[test]
 //
 fn spawn_outside_block_on() {
-    let (mut tx, mut rx) = mpsc::channel(1);
-
     let exec = GlommioCt::new("unnamed", None);
 
-    exec.spawn(async move {
-        tx.send("hello").await.expect("send hello");
-    })
-    .expect("spawn");
+    let err = exec.spawn(async {}).expect_err("no running executor");
 
-    let result = exec.block_on(async move { rx.next().await.expect("receive hello") });
+    assert_eq!(SpawnErrorKind::Unsupported, err.kind());
+    assert!(exec.status().is_err());
+    assert!(exec.status_local().is_err());
+    assert!(exec.spawn_handle_local(async {}).is_err());
+    assert!(Glommio::status().is_err());
 
-    assert_eq!("hello", result);
+    exec.block_on(async {
+        assert!(exec.status().is_ok());
+        assert!(Glommio::status().is_ok());
+        exec.spawn_handle(async { 5u8 }).expect("spawn").await
+    });
+
+    // The executor is not running anymore.
+    //
+    assert!(exec.spawn(async {}).is_err());
+}
+
+// Executors run by hand are only known to Glommio when run through Glommio::run.
+//
+#[test]
+//
+fn spawn_on_own_executor() {
+    let executor = glommio_crate::LocalExecutorBuilder::new()
+        .make()
+        .expect("create glommio executor");
+
+    executor.run(async {
+        assert_eq!(
+            SpawnErrorKind::Unsupported,
+            Glommio::status().unwrap_err().kind()
+        );
+    });
+
+    let out = Glommio::run(&executor, async {
+        assert!(Glommio::status().is_ok());
+
+        Glommio::spawn_handle_local(async { 5u8 })
+            .expect("spawn")
+            .await
+    });
+
+    assert_eq!(5u8, out);
 }
 
 // Joinhandle::detach allows task to keep running.
@@ -351,3 +401,290 @@ fn join_handle_detach() {
         assert_eq!(out_rx.await, Ok(5));
     });
 }
+
+// spawn_local_detached runs the task to completion after the handle is dropped.
+//
+#[test]
+//
+fn spawn_local_detached() {
+    let exec = GlommioCt::new("unnamed", None);
+
+    let (in_tx, in_rx) = oneshot::channel();
+    let (out_tx, out_rx) = oneshot::channel();
+
+    exec.block_on(async {
+        let handle = exec
+            .spawn_local_detached(async move {
+                let content: Rc<u8> = Rc::new(in_rx.await.expect("receive on in"));
+
+                out_tx.send(*content).expect("send on out");
+            })
+            .expect("spawn task");
+
+        drop(handle);
+        in_tx.send(5u8).expect("send on in");
+
+        assert_eq!(out_rx.await, Ok(5));
+
+        // The handle can still be awaited for the output.
+        //
+        let handle = exec
+            .spawn_local_detached(async { Rc::new(3u8) })
+            .expect("spawn task");
+
+        assert_eq!(*handle.await, 3);
+    });
+}
+
+// build with a custom preempt timer and io memory.
+//
+#[test]
+//
+fn builder_preempt_timer() {
+    let exec = GlommioCtBuilder::new()
+        .name("preempt")
+        .preempt_timer(std::time::Duration::from_millis(10))
+        .io_memory(1 << 20)
+        .build()
+        .expect("build glommio executor");
+
+    assert_eq!(5, exec.block_on(async { 5 }));
+}
+
+// spawn the executor on a named thread with a custom stack size.
+//
+#[test]
+//
+fn builder_spawn_thread_name() {
+    let thread = GlommioCtBuilder::new()
+        .name("glommio_named")
+        .spawn_stack_size(4 * 1024 * 1024)
+        .spawn(|exec| {
+            let ex2 = exec.clone();
+
+            exec.block_on(async move {
+                ex2.spawn_handle_local(async {
+                    std::thread::current()
+                        .name()
+                        .expect("some name")
+                        .to_string()
+                })
+                .expect("spawn")
+                .await
+            })
+        })
+        .expect("spawn thread");
+
+    let name = thread
+        .join()
+        .expect("join thread")
+        .expect("build glommio executor");
+
+    assert_eq!("glommio_named", name);
+}
+
+// GlommioCtBuilder::block_on reuses the executor, so tasks survive between calls.
+//
+#[test]
+//
+fn builder_block_on_reuse() {
+    let builder = GlommioCtBuilder::new();
+
+    let (in_tx, in_rx) = oneshot::channel();
+    let (out_tx, out_rx) = oneshot::channel();
+
+    builder.block_on(async {
+        Glommio::spawn_local(async move {
+            let content: u8 = in_rx.await.expect("receive on in");
+
+            out_tx.send(content).expect("send on out");
+        })
+        .expect("spawn task");
+    });
+
+    let result = builder.block_on(async move {
+        in_tx.send(5u8).expect("send on in");
+        out_rx.await
+    });
+
+    assert_eq!(result, Ok(5));
+}
+
+// GlommioCtBuilder spawns local tasks on its executor, or errors when there is none yet.
+//
+#[test]
+//
+fn builder_spawn_handle_local() {
+    let builder = GlommioCtBuilder::new();
+
+    let err = builder
+        .spawn_handle_local(async { 5u8 })
+        .expect_err("no executor yet");
+
+    assert_eq!(SpawnErrorKind::Unsupported, err.kind());
+
+    let result = builder.block_on(async {
+        let not_send = Rc::new(5u8);
+
+        builder
+            .spawn_handle_local(async move { *not_send })
+            .expect("spawn")
+            .await
+    });
+
+    assert_eq!(result, 5);
+}
+
+// GlommioCtBuilder spawns Send futures with spawn_send, status_local tells whether there is an executor.
+//
+#[test]
+//
+fn builder_spawn_send() {
+    let builder = GlommioCtBuilder::new();
+    let (mut tx, mut rx) = mpsc::channel(1);
+
+    assert!(builder.status_local().is_err());
+
+    let out = builder.block_on(async {
+        assert!(builder.status_local().is_ok());
+
+        builder
+            .spawn_send(async move { tx.send(5u8).await.expect("send") })
+            .expect("spawn");
+
+        rx.next().await.expect("receive")
+    });
+
+    assert_eq!(5, out);
+}
+
+// sleep, sleep_until and LocalTimer::sleep_local wait for the given duration.
+//
+#[test]
+//
+fn timer_sleep() {
+    use std::time::{Duration, Instant};
+
+    let exec = GlommioCt::new("unnamed", None);
+    let dur = Duration::from_millis(20);
+
+    exec.block_on(async {
+        let start = Instant::now();
+        exec.sleep(dur).await;
+        assert!(start.elapsed() >= dur);
+
+        let start = Instant::now();
+        exec.sleep_until(start + dur).await;
+        assert!(start.elapsed() >= dur);
+
+        let start = Instant::now();
+        exec.sleep_local(dur).await;
+        assert!(start.elapsed() >= dur);
+    });
+}
+
+// spawn_handle_into: a task on a queue with more shares makes more progress.
+//
+#[test]
+//
+fn spawn_handle_into() {
+    use glommio_crate::{Latency, Shares, Task};
+    use std::{cell::Cell, time::Duration};
+
+    let exec = GlommioCtBuilder::new()
+        .preempt_timer(Duration::from_millis(5))
+        .build()
+        .expect("create glommio executor");
+
+    let (low, high) = exec.block_on(async {
+        let low_queue = exec.create_task_queue(Shares::Static(100), Latency::NotImportant, "low");
+        let high_queue =
+            exec.create_task_queue(Shares::Static(1000), Latency::NotImportant, "high");
+
+        let stop = Rc::new(Cell::new(false));
+
+        let count = |stop: Rc<Cell<bool>>| async move {
+            let mut count = 0u64;
+
+            while !stop.get() {
+                count += 1;
+                std::thread::sleep(Duration::from_micros(50));
+                Task::<()>::yield_if_needed().await;
+            }
+
+            count
+        };
+
+        let low = exec
+            .spawn_handle_into(low_queue, count(stop.clone()))
+            .expect("spawn low");
+
+        let high = exec
+            .spawn_handle_into(high_queue, count(stop.clone()))
+            .expect("spawn high");
+
+        exec.sleep(Duration::from_millis(200)).await;
+        stop.set(true);
+
+        (low.await, high.await)
+    });
+
+    assert!(high > low, "high: {}, low: {}", high, low);
+}
+
+// shutdown reports the pending tasks and allows creating a new executor on the thread.
+//
+#[test]
+//
+fn shutdown() {
+    for shard in 0..3 {
+        let exec = GlommioCt::new(&format!("shard {}", shard), None);
+
+        exec.block_on(async {
+            exec.spawn_local(futures::future::pending()).expect("spawn");
+            exec.spawn_handle_local(async move { Rc::new(shard) })
+                .expect("spawn")
+                .await;
+        });
+
+        assert_eq!(Ok(1), exec.shutdown().map_err(drop));
+    }
+}
+
+// shutdown gives back the executor when other clones are alive.
+//
+#[test]
+//
+fn shutdown_with_clones() {
+    let exec = GlommioCt::new("unnamed", None);
+    let clone = exec.clone();
+
+    let exec = exec.shutdown().expect_err("other clone is alive");
+
+    drop(clone);
+
+    assert_eq!(Ok(0), exec.shutdown().map_err(drop));
+}
+
+// affinity: pin a thread to a cpu and read back the set, other threads keep theirs.
+//
+#[test]
+//
+fn affinity() {
+    use async_executors::affinity::{current_cpu_set, pin_current_thread};
+
+    let before = current_cpu_set().expect("get affinity");
+    let cpu = *before.last().expect("at least one cpu");
+
+    let pinned = std::thread::spawn(move || {
+        pin_current_thread(cpu).expect("set affinity");
+        current_cpu_set().expect("get affinity")
+    })
+    .join()
+    .expect("join thread");
+
+    assert_eq!(vec![cpu], pinned);
+    assert_eq!(before, current_cpu_set().expect("get affinity"));
+
+    assert!(pin_current_thread(usize::MAX).is_err());
+}
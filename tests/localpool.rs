@@ -10,13 +10,27 @@
 // ✔ pass a Rc<LocalSpawner> to a function that takes exec: `impl LocalSpawnHandle`
 // ✔ pass a   &LocalSpawner  to a function that takes exec: `&dyn LocalSpawnHandle`
 //
+// ✔ WithHandles gives join handles for an executor that only implements Spawn.
+//
+// ✔ StepExecutor runs spawned tasks and returns Pending when nothing can make progress.
+//
+// ✔ TimerWheel fires the sleep of a task when the wheel is advanced past the deadline.
+// ✔ TimerWheel::block_on drives the timers and parks the thread until they are due.
+// ✔ TimerWheel forgets the timer of a sleep that was dropped.
+// ✔ TimerWheel doesn't overflow on huge durations, a sleep that can't be reached never fires.
+//
+// ✔ JoinHandle made from a RemoteHandle cancels the task on drop.
+// ✔ try_into_remote_handle gives back the RemoteHandle, which cancels on drop unless forgotten.
+//
 mod common;
 
 use
 {
 	common           :: * ,
-	futures_executor :: { LocalPool                    } ,
-	std              :: { rc::Rc                       } ,
+	futures_executor :: { LocalPool, LocalSpawner       } ,
+	futures::task    :: { FutureObj                    } ,
+	std              :: { rc::Rc, pin::Pin, task::{ Context, Poll } } ,
+	futures::task    :: { noop_waker_ref, LocalSpawnExt as _ } ,
 };
 
 
@@ -115,3 +129,213 @@ fn spawn_handle_local_os()
 	assert_eq!( 5u8, result );
 }
 
+
+
+
+
+// An executor that only implements Spawn.
+//
+struct SpawnOnly( LocalSpawner );
+
+impl Spawn for SpawnOnly
+{
+	fn spawn_obj( &self, future: FutureObj<'static, ()> ) -> Result<(), SpawnError>
+	{
+		futures::task::Spawn::spawn_obj( &self.0, future ).map_err( Into::into )
+	}
+}
+
+
+// WithHandles gives join handles for an executor that only implements Spawn.
+//
+#[ test ]
+//
+fn with_handles()
+{
+	let mut exec    = LocalPool::new();
+	let     spawner = SpawnOnly( exec.spawner() ).with_handles();
+
+	let res = exec.run_until( increment_spawn_handle( 4, &spawner ) );
+
+	assert_eq!( 5u8, res );
+}
+
+
+
+// StepExecutor runs spawned tasks and returns Pending when nothing can make progress.
+//
+#[ test ]
+//
+fn step_executor()
+{
+	let mut exec    = LocalPool::new();
+	let     spawner = exec.spawner();
+	let mut cx      = Context::from_waker( noop_waker_ref() );
+
+	let (tx , mut ready) = oneshot::channel::<u8>();
+	let (_tx, mut never) = oneshot::channel::<u8>();
+
+	spawner.spawn_local( async move { tx.send( 5 ).expect( "send" ); } ).expect( "spawn" );
+
+	assert_eq!( Poll::Ready( Ok(5) ), exec.step( Pin::new( &mut ready ), &mut cx ) );
+	assert!( exec.step( Pin::new( &mut never ), &mut cx ).is_pending() );
+}
+
+
+
+// JoinHandle made from a RemoteHandle cancels the task on drop.
+//
+#[ cfg(not( feature = "detach_on_drop" )) ]
+#[ test ]
+//
+fn join_handle_from_remote_handle()
+{
+	let mut exec     = LocalPool::new();
+	let     spawner  = exec.spawner();
+	let (tx, rx)     = oneshot::channel::<()>();
+
+	let (fut, remote) = async move { tx.send(()).expect( "send" ); }.remote_handle();
+	spawner.spawn_local( fut ).expect( "spawn" );
+
+	let handle: JoinHandle<()> = remote.into();
+	drop( handle );
+
+	exec.run_until_stalled();
+
+	assert!( exec.run_until( rx ).is_err() );
+}
+
+
+
+// try_into_remote_handle gives back the RemoteHandle, which cancels on drop unless forgotten.
+//
+#[ test ]
+//
+fn try_into_remote_handle()
+{
+	let mut exec    = LocalPool::new();
+	let     spawner = exec.spawner();
+
+	let (tx, rx) = oneshot::channel::<()>();
+	let handle   = spawner.spawn_handle( async move { tx.send(()).expect( "send" ); } ).expect( "spawn" );
+
+	drop( handle.try_into_remote_handle().expect( "RemoteHandle" ) );
+	exec.run_until_stalled();
+	assert!( exec.run_until( rx ).is_err() );
+
+	let (tx, rx) = oneshot::channel::<()>();
+	let handle   = spawner.spawn_handle( async move { tx.send(()).expect( "send" ); } ).expect( "spawn" );
+
+	handle.try_into_remote_handle().expect( "RemoteHandle" ).forget();
+	assert!( exec.run_until( rx ).is_ok() );
+
+	// Other variants are returned unchanged.
+	//
+	let (handle, _abort) = spawner.spawn_handle_with_abort( async { 5u8 } ).expect( "spawn" );
+	let handle           = handle.try_into_remote_handle().expect_err( "not a RemoteHandle" );
+
+	assert_eq!( 5, exec.run_until( handle ) );
+}
+
+
+
+// TimerWheel fires the sleep of a task when the wheel is advanced past the deadline.
+//
+#[ test ]
+//
+fn timer_wheel_advance()
+{
+	use std::{ cell::Cell, time::Duration };
+
+	let mut exec    = LocalPool::new();
+	let     spawner = exec.spawner();
+	let     wheel   = TimerWheel::new( Duration::from_millis( 10 ) );
+	let     done    = Rc::new( Cell::new( false ) );
+
+	let sleep = wheel.sleep( Duration::from_secs( 3600 ) );
+	let done2 = done.clone();
+
+	spawner.spawn_local( async move { sleep.await; done2.set( true ); } ).expect( "spawn" );
+
+	exec.run_until_stalled();
+	assert!( !done.get() );
+	assert!( wheel.next_timer().expect( "pending timer" ) > Duration::from_secs( 3590 ) );
+
+	assert_eq!( 0, wheel.advance( Duration::from_secs( 1800 ) ) );
+	exec.run_until_stalled();
+	assert!( !done.get() );
+
+	// Deadlines are rounded up to the resolution.
+	//
+	assert_eq!( 1, wheel.advance( Duration::from_secs( 1800 ) + wheel.resolution() ) );
+	exec.run_until_stalled();
+	assert!( done.get() );
+	assert_eq!( None, wheel.next_timer() );
+}
+
+
+
+// TimerWheel::block_on drives the timers and parks the thread until they are due.
+//
+#[ test ]
+//
+fn timer_wheel_block_on()
+{
+	use std::time::{ Duration, Instant };
+
+	let mut exec    = LocalPool::new();
+	let     spawner = exec.spawner();
+	let     wheel   = TimerWheel::new( Duration::from_millis( 5 ) );
+	let     dur     = Duration::from_millis( 20 );
+
+	let child = spawner.spawn_handle_local( wheel.sleep( dur ) ).expect( "spawn" );
+	let start = Instant::now();
+
+	wheel.block_on( &mut exec, async
+	{
+		wheel.sleep( dur ).await;
+		child.await;
+	});
+
+	assert!( start.elapsed() >= dur );
+}
+
+
+
+// TimerWheel forgets the timer of a sleep that was dropped.
+//
+#[ test ]
+//
+fn timer_wheel_drop_sleep()
+{
+	use std::time::Duration;
+
+	let wheel = TimerWheel::new( Duration::from_millis( 10 ) );
+	let sleep = wheel.sleep( Duration::from_secs( 3600 ) );
+
+	assert!( wheel.next_timer().is_some() );
+
+	drop( sleep );
+
+	assert_eq!( None, wheel.next_timer() );
+	assert_eq!( 0, wheel.advance( Duration::from_secs( 7200 ) ) );
+}
+
+
+
+// TimerWheel doesn't overflow on huge durations, a sleep that can't be reached never fires.
+//
+#[ test ]
+//
+fn timer_wheel_huge_duration()
+{
+	use std::time::Duration;
+
+	let wheel = TimerWheel::new( Duration::from_millis( 1 ) );
+	let mut sleep = wheel.sleep( Duration::MAX );
+
+	assert_eq!( 0, wheel.advance( Duration::MAX ) );
+	assert_eq!( 0, wheel.advance( Duration::MAX ) );
+	assert!( futures::FutureExt::now_or_never( &mut sleep ).is_none() );
+	assert_eq!( None, wheel.next_timer() );
+}
@@ -0,0 +1,181 @@
+#![ cfg( not( target_os = "unknown" ) ) ]
+
+// Tested:
+//
+// ✔ cooperating tasks make progress over several calls to run_pending.
+// ✔ spawning returns an error of kind AtCapacity when the executor is full.
+// ✔ tasks can spawn on the executor, and the new task runs on the next call.
+// ✔ dropping the executor drops the pending tasks, also when they hold a spawner.
+// ✔ a panicking task is removed and the other tasks still run.
+//
+mod common;
+
+use
+{
+	common  :: { *                                           } ,
+	futures :: { channel::mpsc, FutureExt, SinkExt, StreamExt } ,
+	std     :: { cell::Cell, rc::Rc                           } ,
+};
+
+
+
+// cooperating tasks make progress over several calls to run_pending.
+//
+#[ test ]
+//
+fn run_pending()
+{
+	let exec = ManualExecutor::new( 4 );
+
+	let (mut ping_tx, mut ping_rx) = mpsc::channel::<u8>( 0 );
+	let (mut pong_tx, mut pong_rx) = mpsc::channel::<u8>( 0 );
+
+	let mut pinger = exec.spawn_handle_local( async move
+	{
+		let mut sum = 0;
+
+		for i in 1..=3
+		{
+			ping_tx.send( i ).await.expect( "send ping" );
+			sum += pong_rx.next().await.expect( "receive pong" );
+		}
+
+		sum
+
+	}).expect( "spawn" );
+
+	exec.spawn_local( async move
+	{
+		while let Some( i ) = ping_rx.next().await
+		{
+			pong_tx.send( i * 2 ).await.expect( "send pong" );
+		}
+
+	}).expect( "spawn" );
+
+	// Both get polled once, after that they wake each other up.
+	//
+	assert_eq!( 2, exec.run_pending() );
+
+	let mut runs = 1;
+
+	while exec.run_pending() > 0 { runs += 1 }
+
+	assert!( runs > 3 );
+	assert!( exec.is_empty() );
+	assert_eq!( Some( 12 ), (&mut pinger).now_or_never() );
+}
+
+
+
+// spawning returns an error of kind AtCapacity when the executor is full.
+//
+#[ test ]
+//
+fn at_capacity()
+{
+	let exec         = ManualExecutor::new( 1 );
+	let (tx, mut rx) = mpsc::channel::<()>( 1 );
+
+	exec.spawn( async move { rx.next().await; } ).expect( "spawn" );
+
+	let err = exec.spawn( async {} ).expect_err( "full" );
+
+	assert_eq!( SpawnErrorKind::AtCapacity, err.kind() );
+	assert!( exec.status().is_err() );
+
+	// Once the task completes, there is room again.
+	//
+	drop( tx );
+	exec.run_pending();
+
+	assert!( exec.is_empty() );
+	assert!( exec.spawn( async {} ).is_ok() );
+}
+
+
+
+// tasks can spawn on the executor, and the new task runs on the next call.
+//
+#[ test ]
+//
+fn spawn_from_task()
+{
+	let exec  = ManualExecutor::new( 2 );
+	let ran   = Rc::new( Cell::new( false ) );
+	let ran2  = ran.clone();
+	let exec2 = exec.spawner();
+
+	exec.spawn_local( async move
+	{
+		exec2.spawn_local( async move { ran2.set( true ) } ).expect( "spawn" );
+
+	}).expect( "spawn" );
+
+	assert_eq!( 1, exec.run_pending() );
+	assert!( !ran.get() );
+
+	assert_eq!( 1, exec.run_pending() );
+	assert!( ran.get() );
+	assert_eq!( 0, exec.run_pending() );
+}
+
+
+
+// dropping the executor drops the pending tasks, also when they hold a spawner.
+//
+#[ test ]
+//
+fn drop_executor()
+{
+	let exec      = ManualExecutor::new( 2 );
+	let spawner   = exec.spawner();
+	let spawner2  = exec.spawner();
+	let alive     = Rc::new( () );
+	let alive2    = alive.clone();
+
+	exec.spawn_local( async move
+	{
+		let _alive   = alive2;
+		let _spawner = spawner2;
+
+		futures::future::pending::<()>().await;
+
+	}).expect( "spawn" );
+
+	assert_eq!( 1, exec.run_pending() );
+	assert_eq!( 2, Rc::strong_count( &alive ) );
+
+	drop( exec );
+
+	assert_eq!( 1, Rc::strong_count( &alive ) );
+
+	let err = spawner.spawn_local( async {} ).unwrap_err();
+
+	assert_eq!( SpawnErrorKind::Shutdown, err.kind() );
+}
+
+
+
+// a panicking task is removed and the other tasks still run.
+//
+#[ test ]
+//
+fn task_panics()
+{
+	let exec = ManualExecutor::new( 2 );
+	let ran  = Rc::new( Cell::new( false ) );
+	let ran2 = ran.clone();
+
+	exec.spawn_local( async { panic!( "oops" ) } ).expect( "spawn" );
+	exec.spawn_local( async move { ran2.set( true ) } ).expect( "spawn" );
+
+	let result = std::panic::catch_unwind( std::panic::AssertUnwindSafe( || exec.run_pending() ) );
+
+	assert!( result.is_err() );
+	assert_eq!( 1, exec.len() );
+
+	assert_eq!( 1, exec.run_pending() );
+	assert!( ran.get() );
+	assert!( exec.is_empty() );
+}
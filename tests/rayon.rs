@@ -0,0 +1,68 @@
+#![ cfg( feature = "rayon" ) ]
+
+// Tested:
+//
+// ✔ pass a RayonPool to a function that takes exec: `impl SpawnBlocking`
+// ✔ sum a large slice in parallel on the pool and join the result.
+//
+mod common;
+
+use
+{
+	common      :: { *                                   } ,
+	rayon_crate :: { prelude::*, ThreadPoolBuilder       } ,
+};
+
+
+fn pool() -> RayonPool
+{
+	ThreadPoolBuilder::new()
+
+		.num_threads( 2 )
+		.thread_name( |i| format!( "rayon_test_{}", i ) )
+		.build()
+		.expect( "build rayon pool" )
+		.into()
+}
+
+
+fn square( exec: impl SpawnBlocking<u64>, x: u64 ) -> JoinHandle<u64>
+{
+	exec.spawn_blocking( move || x * x ).expect( "spawn blocking" )
+}
+
+
+// pass a RayonPool to a function that takes exec: `impl SpawnBlocking`
+//
+#[ test ]
+//
+fn spawn_blocking()
+{
+	let exec = pool();
+
+	assert_eq!( 16, block_on( square( exec.clone(), 4 ) ) );
+
+	let name = block_on( exec.spawn_blocking( ||
+	{
+		std::thread::current().name().expect( "some name" ).to_string()
+
+	}).expect( "spawn blocking" ));
+
+	assert!( name.starts_with( "rayon_test_" ) );
+}
+
+
+// sum a large slice in parallel on the pool and join the result.
+//
+#[ test ]
+//
+fn parallel_sum()
+{
+	let exec  = pool();
+	let data  = Arc::new( (1..=1_000_000u64).collect::<Vec<_>>() );
+	let data2 = data.clone();
+
+	let handle = exec.spawn_blocking( move || data2.par_iter().sum::<u64>() ).expect( "spawn blocking" );
+
+	assert_eq!( data.iter().sum::<u64>(), block_on( handle ) );
+}
@@ -0,0 +1,75 @@
+#![ cfg( feature = "tokio_ct" ) ]
+
+// The hook is process wide, so this lives in it's own test binary.
+//
+// Tested:
+//
+// ✔ the global spawn hook sees every task spawned through the extension methods, with it's name and executor.
+// ✔ the hook can only be set once.
+// ✔ methods built on the extension methods call the hook, internal plumbing tasks don't.
+//
+mod common;
+
+use
+{
+	common :: { *                                             } ,
+	std    :: { sync::{ atomic::{ AtomicUsize, Ordering }, Mutex } } ,
+};
+
+
+static SPAWNED: AtomicUsize        = AtomicUsize::new( 0 );
+static NAMES  : Mutex<Vec<String>> = Mutex::new( Vec::new() );
+static EXECS  : Mutex<Vec<String>> = Mutex::new( Vec::new() );
+
+
+// the global spawn hook sees every task spawned through the extension methods, with it's name and executor.
+//
+#[ test ]
+//
+fn spawn_hook()
+{
+	let set = set_global_spawn_hook( Box::new( |info|
+	{
+		SPAWNED.fetch_add( 1, Ordering::SeqCst );
+
+		if let Some( name ) = info.name()
+		{
+			NAMES.lock().expect( "lock names" ).push( name.to_string() );
+		}
+
+		EXECS.lock().expect( "lock executors" ).push( info.executor().to_string() );
+
+	}));
+
+	assert!( set.is_ok() );
+
+	assert!( set_global_spawn_hook( Box::new( |_| {} ) ).is_err() );
+
+	let exec = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+
+	exec.spawn      ( async {}          ).expect( "spawn"       );
+	exec.spawn_local( async {}          ).expect( "spawn_local" );
+
+	let a = exec.spawn_handle      (          async { 1u8 } ).expect( "spawn_handle"       );
+	let b = exec.spawn_handle_named( "named", async { 2u8 } ).expect( "spawn_handle_named" );
+	let c = exec.spawn_handle_local(          async { 3u8 } ).expect( "spawn_handle_local" );
+
+	assert_eq!( 6, exec.block_on( async { a.await + b.await + c.await } ) );
+
+	// Built on spawn_handle and spawn_handle_local.
+	//
+	let d = exec.spawn_until( async { 4u8 }, CancellationToken::new() ).expect( "spawn_until" );
+	let e = exec.spawn_handle_local_catch_unwind( async { 5u8 } ).expect( "spawn_handle_local_catch_unwind" );
+
+	assert_eq!( Some( 4 ), exec.block_on( d ) );
+	assert_eq!( 5, exec.block_on( e ).expect( "no panic" ) );
+
+	// The drivers are not user tasks.
+	//
+	let _prio = PrioritizedSpawn::new( exec.clone(), 2 ).expect( "create PrioritizedSpawn" );
+
+	assert_eq!( 7                    , SPAWNED.load( Ordering::SeqCst )       );
+	assert_eq!( vec![ "named"      ] , *NAMES.lock().expect( "lock names" )   );
+
+	assert!( EXECS.lock().expect( "lock executors" ).iter().all( |e| e.contains( "TokioCt" ) ) );
+}
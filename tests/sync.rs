@@ -0,0 +1,140 @@
+#![ cfg(all( feature = "sync", feature = "tokio_tp" )) ]
+
+// Tested:
+//
+// ✔ Mutex: concurrent tasks don't lose updates when they yield while holding the lock.
+// ✔ RwLock: readers never see a half finished write.
+// ✔ Semaphore: no more tasks than permits hold one at the same time.
+//
+mod common;
+
+use
+{
+	common          :: { *                                             } ,
+	async_executors :: { sync::{ Mutex, RwLock, Semaphore }            } ,
+	futures         :: { future::join_all                              } ,
+	std             :: { sync::atomic::{ AtomicUsize, Ordering::SeqCst } } ,
+};
+
+
+// Spawn `n` tasks through the generic interface and wait for them all.
+//
+async fn spawn_n<F, Fut>( exec: &impl SpawnHandle<()>, n: usize, task: F )
+where
+	F  : Fn() -> Fut,
+	Fut: std::future::Future<Output = ()> + Send + 'static,
+{
+	let handles: Vec<_> = ( 0..n ).map( |_| exec.spawn_handle( task() ).expect( "spawn" ) ).collect();
+
+	join_all( handles ).await;
+}
+
+
+
+// Mutex: concurrent tasks don't lose updates when they yield while holding the lock.
+//
+#[ test ]
+//
+fn mutex_contention()
+{
+	let exec    = TokioTp::default();
+	let counter = Arc::new( Mutex::new( 0usize ) );
+
+	exec.block_on( spawn_n( &exec, 50, ||
+	{
+		let counter = counter.clone();
+
+		async move
+		{
+			let mut guard = counter.lock().await;
+			let value     = *guard;
+
+			Tokio.yield_now().await;
+
+			*guard = value + 1;
+		}
+	}));
+
+	assert_eq!( 50, *exec.block_on( counter.lock() ) );
+}
+
+
+
+// RwLock: readers never see a half finished write.
+//
+#[ test ]
+//
+fn rwlock_contention()
+{
+	let exec  = TokioTp::default();
+	let lock  = Arc::new( RwLock::new( (0usize, 0usize) ) );
+	let reads = Arc::new( AtomicUsize::new( 0 ) );
+
+	exec.block_on( spawn_n( &exec, 40, ||
+	{
+		let lock  = lock.clone();
+		let reads = reads.clone();
+
+		async move
+		{
+			// Every other task writes, the rest read.
+			//
+			match reads.fetch_add( 1, SeqCst ) % 2
+			{
+				0 =>
+				{
+					let mut guard = lock.write().await;
+
+					guard.0 += 1;
+					Tokio.yield_now().await;
+					guard.1 += 1;
+				}
+
+				_ =>
+				{
+					let guard = lock.read().await;
+
+					assert_eq!( guard.0, guard.1 );
+					Tokio.yield_now().await;
+					assert_eq!( guard.0, guard.1 );
+				}
+			}
+		}
+	}));
+
+	assert_eq!( (20, 20), *exec.block_on( lock.read() ) );
+}
+
+
+
+// Semaphore: no more tasks than permits hold one at the same time.
+//
+#[ test ]
+//
+fn semaphore_contention()
+{
+	let exec    = TokioTp::default();
+	let sem     = Arc::new( Semaphore::new( 3 ) );
+	let current = Arc::new( AtomicUsize::new( 0 ) );
+	let max     = Arc::new( AtomicUsize::new( 0 ) );
+
+	exec.block_on( spawn_n( &exec, 30, ||
+	{
+		let sem     = sem    .clone();
+		let current = current.clone();
+		let max     = max    .clone();
+
+		async move
+		{
+			let _permit = sem.acquire().await;
+			let now     = current.fetch_add( 1, SeqCst ) + 1;
+
+			max.fetch_max( now, SeqCst );
+			Tokio.yield_now().await;
+			current.fetch_sub( 1, SeqCst );
+		}
+	}));
+
+	assert!( max.load( SeqCst ) <= 3 );
+	assert_eq!( 3, [ sem.try_acquire(), sem.try_acquire(), sem.try_acquire() ].iter().flatten().count() );
+}
@@ -6,12 +6,20 @@
 //
 // ✔ Joinhandle::detach allows task to keep running.
 //
+// ✔ spawn_stream yields all outputs.
+// ✔ spawn_stream never runs more than max_concurrent tasks at once.
+//
+// ✔ spawn_handle_detached_on_drop: the task keeps running when the handle is dropped.
+// ✔ spawn_handle_detached_on_drop: the handle can still be awaited.
+//
 mod common;
 
 use
 {
 	common           :: { *          } ,
-	futures_executor :: { ThreadPool } ,
+	futures_executor :: { ThreadPool                                      } ,
+	futures          :: { StreamExt                                       } ,
+	std              :: { sync::atomic::{ AtomicUsize, Ordering::SeqCst } } ,
 };
 
 // pass a ThreadPool to a function that takes exec: `impl SpawnHandle`
@@ -84,3 +92,99 @@ fn join_handle_detach()
 		assert_eq!( out_rx.await, Ok(5) );
 	});
 }
+
+
+
+// spawn_stream yields all outputs.
+//
+#[ test ]
+//
+fn spawn_stream()
+{
+	let exec = ThreadPool::new().expect( "create threadpool" );
+	let futs = (0..20u8).map( |i| async move { i } );
+
+	let mut result: Vec<u8> = block_on( exec.spawn_stream( futs, 4 ).map( |r| r.expect( "spawn" ) ).collect() );
+
+	result.sort_unstable();
+
+	assert_eq!( (0..20).collect::<Vec<u8>>(), result );
+}
+
+
+// spawn_stream never runs more than max_concurrent tasks at once.
+//
+#[ test ]
+//
+fn spawn_stream_max_concurrent()
+{
+	let exec    = ThreadPool::new().expect( "create threadpool" );
+	let current = Arc::new( AtomicUsize::new(0) );
+	let max     = Arc::new( AtomicUsize::new(0) );
+
+	let futs = (0..20).map( |_|
+	{
+		let current = current.clone();
+		let max     = max    .clone();
+
+		async move
+		{
+			let now = current.fetch_add( 1, SeqCst ) + 1;
+			max.fetch_max( now, SeqCst );
+
+			std::thread::sleep( std::time::Duration::from_millis(2) );
+
+			current.fetch_sub( 1, SeqCst );
+		}
+	});
+
+	let count = block_on( exec.spawn_stream( futs, 3 ).count() );
+
+	assert_eq!( 20, count );
+	assert!( max.load( SeqCst ) <= 3 );
+}
+
+
+
+// spawn_handle_detached_on_drop: the task keeps running when the handle is dropped.
+//
+#[ test ]
+//
+fn spawn_handle_detached_on_drop()
+{
+	let exec = ThreadPool::new().expect( "create threadpool" );
+
+	let (in_tx , in_rx ) = oneshot::channel();
+	let (out_tx, out_rx) = oneshot::channel();
+
+	let handle = exec.spawn_handle_detached_on_drop( async move
+	{
+		let content = in_rx.await.expect( "receive on in" );
+
+		out_tx.send( content ).expect( "send on out" );
+
+	}).expect( "spawn task" );
+
+	drop( handle );
+
+	block_on( async move
+	{
+		in_tx.send( 5u8 ).expect( "send on in" );
+
+		assert_eq!( out_rx.await, Ok(5) );
+	});
+}
+
+
+// spawn_handle_detached_on_drop: the handle can still be awaited.
+//
+#[ test ]
+//
+fn spawn_handle_detached_on_drop_await()
+{
+	let exec = ThreadPool::new().expect( "create threadpool" );
+
+	let handle = exec.spawn_handle_detached_on_drop( async { 5u8 } ).expect( "spawn task" );
+
+	assert_eq!( 5, block_on( handle ) );
+}
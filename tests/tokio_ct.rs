@@ -22,14 +22,127 @@
 //
 // ✔ we can spawn without being in a future running on block_on.
 // ✔ Joinhandle::detach allows task to keep running.
+// ✔ Joinhandle::as_tokio gives access to the native handle.
+//
+// ✔ spawn_blocking_cancellable: dropping the handle sets the flag.
+// ✔ spawn_blocking_cancellable: cancel sets the flag and we can still await the output.
+//
+// ✔ spawn_handle_detached_on_drop: the task keeps running when the handle is dropped.
+// ✔ spawn_handle_detached_on_drop: the handle can still be awaited.
+//
+// ✔ Timer::sleep waits for the given duration.
+// ✔ spawn_with_retry: retries while status reports that the executor is at capacity.
+// ✔ spawn_with_retry: gives up after max_attempts.
+// ✔ spawn_with_retry: returns other errors right away.
+//
+// ✔ run_until_idle runs spawned tasks to completion.
+//
+// ✔ PrioritizedSpawn polls high priority tasks before low priority ones.
+// ✔ PrioritizedSpawn gives tasks that keep yielding turns according to their weight.
+//
+// ✔ AbortOnDrop aborts the task when dropped, even if the handle detaches on drop.
+// ✔ AbortOnDrop::disarm lets the task keep running.
+//
+// ✔ SpawnBlockingStatic through the WithRuntime of TokioCt from within block_on.
+//
+// ✔ JoinHandle::boxed can be awaited and dropping it still cancels the task.
+// ✔ JoinHandle::boxed_local can be awaited and dropping it still cancels the task.
+//
+// ✔ race_with_timeout returns the first output and cancels the other tasks.
+// ✔ race_with_timeout returns Elapsed and cancels all tasks when the deadline passes.
+//
+// ✔ spawn_handle_with_abort: aborting cancels the task and awaiting the handle panics.
+// ✔ spawn_handle_with_abort: the handle gives the output when not aborted.
+//
+// ✔ CancellationToken wakes up all tasks waiting on it.
+// ✔ JoinSet yields outputs in completion order and abort_all cancels the rest.
+// ✔ Dropping a JoinSet cancels its tasks, also when their handles detach on drop.
+// ✔ Dropping a SpawnStream cancels the tasks in flight, also with the detach_on_drop feature.
+// ✔ Shutdown: tasks stop when the token is cancelled and wait returns Ok.
+// ✔ Shutdown: stragglers get aborted after the grace period.
+//
+// ✔ drain_on_drop: remaining tasks complete when the executor is dropped.
+//
+// ✔ spawn_handle_named returns a working JoinHandle.
+//
+// ✔ BlockingPool never runs more closures at the same time than the limit.
+//
+// ✔ CoopBudget asks to yield every interval iterations.
+//
+// ✔ enabled_runtimes lists tokio_ct.
+//
+// ✔ spawn_into_sender sends the outputs into one channel and drops them when the receiver is gone.
+//
+// ✔ spawn_blocking_catch_unwind resolves to Err when the closure panics.
+//
+// ✔ ContextBlockingPool initializes the context once per worker and survives panics.
+//
+// ✔ TokioJoinHandle::join tells a panic, an abort and a runtime shutdown apart.
+// ✔ Dropping the future of TokioJoinHandle::join cancels the task, like dropping the handle.
+//
+// ✔ block_on_catch_unwind returns the panic of the top level future and the executor stays usable.
+//
+// ✔ join_timeout returns the output when the task completes in time.
+// ✔ join_timeout aborts the task and returns Elapsed when the deadline passes.
+//
+// ✔ dropping a JoinHandle cancels the task, or detaches it with the detach_on_drop feature.
+// ✔ JoinHandle::abort cancels the task with or without the detach_on_drop feature.
+//
+// ✔ MapSpawn applies the middleware to futures spawned with spawn and spawn_handle.
+//
+// ✔ JoinHandle::into_stream yields the output once and can be merged with other streams.
+//
+// ✔ TokioCt::idle resolves once the spawned tasks stopped making progress.
+// ✔ MeteredExecutor::idle resolves once all tasks spawned through it have completed or were dropped.
+//
+// ✔ thread_name names the blocking threads, block_on runs on the calling thread.
+//
+// ✔ OnTaskPanic calls the callback for panics in tasks with and without a handle.
+//
+// ✔ TimedSpawn reports the duration of completed tasks, None for cancelled ones and nothing when spawning fails.
+//
+// ✔ spawn_all returns the handles in input order.
+// ✔ spawn_all aborts the tasks it already spawned when a spawn fails.
+//
+// ✔ metrics count pending tasks on the runtime and on the local set.
+//
+// ✔ spawn_handle_ttl resolves to Some when the task completes in time.
+// ✔ spawn_handle_ttl cancels the task when the ttl elapses, also when the handle is detached.
+//
+// ✔ blocking_iter_stream yields the items of the iterator and stops pulling when dropped.
+// ✔ blocking_iter_stream yields the error when spawning fails and ends after it.
+//
+// ✔ spawn_handle_with_ready signals that the task started before it produces its output.
+//
+// ✔ block_on_all runs spawned tasks that outlive the top level future to completion, block_on does not.
+//
+// ✔ spawn_send spawns a Send future through `impl LocalSpawn`, Spawn puts it on the same local set.
+//
+// ✔ spawn_blocking_stream yields results in completion order, or in input order with one job in flight.
+// ✔ spawn_blocking_stream yields the error when spawning fails and stops taking jobs.
+//
+// ✔ spawn_handle_local_catch_unwind resolves to Err with the payload, spawn_handle_local unwinds the awaiting thread.
+//
+// ✔ flatten_result gives the output, the error of the task as Failed and a panic as Panicked.
+// ✔ flatten_result gives Cancelled for an aborted task, without unwinding.
+//
+// ✔ try_scope returns the outputs in spawn order when all tasks succeed.
+// ✔ try_scope returns the first error and aborts the siblings right away.
+//
+// ✔ spawn_until resolves to Some when the task completes before the token is cancelled.
+// ✔ spawn_until drops the future when the token is cancelled, also when the handle is detached.
+//
+// ✔ par_map returns the outputs in input order, whatever order the tasks complete in.
+// ✔ par_map never runs more than concurrency tasks at the same time.
+// ✔ dropping the par_map future cancels the tasks in flight.
 //
 mod common;
 
 use
 {
 	common          :: * ,
-	futures         :: { channel::{ mpsc }, StreamExt } ,
-	std             :: { rc::Rc                       } ,
+	futures         :: { channel::{ mpsc }, future::BoxFuture, StreamExt } ,
+	std             :: { rc::Rc, sync::Mutex, time::Duration          } ,
 };
 
 
@@ -412,3 +525,2199 @@ fn join_handle_detach()
 		assert_eq!( out_rx.await, Ok(5) );
 	});
 }
+
+
+
+// Joinhandle::as_tokio gives access to the native handle.
+//
+#[ test ]
+//
+fn join_handle_as_tokio()
+{
+	let exec = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+
+	let join_handle = exec.spawn_handle( async { 5u8 } ).expect( "spawn task" );
+
+	assert!( join_handle.as_tokio().is_some() );
+
+	exec.block_on( async move
+	{
+		assert_eq!( join_handle.await, 5 );
+	});
+}
+
+
+
+// spawn_blocking_cancellable: dropping the handle sets the flag.
+//
+#[ test ]
+//
+fn spawn_blocking_cancellable_drop()
+{
+	let exec     = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+	let (tx, rx) = std::sync::mpsc::channel();
+
+	let handle = exec.spawn_blocking_cancellable( move |flag|
+	{
+		tx.send( "started" ).expect( "send" );
+
+		while !flag.load( std::sync::atomic::Ordering::SeqCst )
+		{
+			std::thread::yield_now();
+		}
+
+		tx.send( "cancelled" ).expect( "send" );
+
+	}).expect( "spawn" );
+
+	// Make sure the closure is running, otherwise tokio doesn't run it at all.
+	//
+	assert_eq!( "started", rx.recv().expect( "recv" ) );
+
+	drop( handle );
+
+	assert_eq!( "cancelled", rx.recv().expect( "recv" ) );
+}
+
+
+// spawn_blocking_cancellable: cancel sets the flag and we can still await the output.
+//
+#[ test ]
+//
+fn spawn_blocking_cancellable_cancel()
+{
+	let exec     = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+	let (tx, rx) = std::sync::mpsc::channel();
+
+	let handle = exec.spawn_blocking_cancellable( move |flag|
+	{
+		tx.send( "started" ).expect( "send" );
+
+		while !flag.load( std::sync::atomic::Ordering::SeqCst )
+		{
+			std::thread::yield_now();
+		}
+
+		"cancelled"
+
+	}).expect( "spawn" );
+
+	assert_eq!( "started", rx.recv().expect( "recv" ) );
+
+	handle.cancel();
+
+	assert_eq!( "cancelled", exec.block_on( handle ) );
+}
+
+
+
+// spawn_handle_detached_on_drop: the task keeps running when the handle is dropped.
+//
+#[ test ]
+//
+fn spawn_handle_detached_on_drop()
+{
+	let exec = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+
+	let (in_tx , in_rx ) = oneshot::channel();
+	let (out_tx, out_rx) = oneshot::channel();
+
+	let handle = exec.spawn_handle_detached_on_drop( async move
+	{
+		let content = in_rx.await.expect( "receive on in" );
+
+		out_tx.send( content ).expect( "send on out" );
+
+	}).expect( "spawn task" );
+
+	drop( handle );
+
+	exec.block_on( async move
+	{
+		in_tx.send( 5u8 ).expect( "send on in" );
+
+		assert_eq!( out_rx.await, Ok(5) );
+	});
+}
+
+
+// spawn_handle_detached_on_drop: the handle can still be awaited.
+//
+#[ test ]
+//
+fn spawn_handle_detached_on_drop_await()
+{
+	let exec = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+
+	let handle = exec.spawn_handle_detached_on_drop( async { 5u8 } ).expect( "spawn task" );
+
+	assert_eq!( 5, exec.block_on( handle ) );
+}
+
+
+
+// Timer::sleep waits for the given duration.
+//
+#[ test ]
+//
+fn timer_sleep()
+{
+	let exec  = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+	let start = std::time::Instant::now();
+
+	exec.block_on( exec.sleep( std::time::Duration::from_millis(20) ) );
+
+	assert!( start.elapsed() >= std::time::Duration::from_millis(20) );
+}
+
+
+// An executor whose status reports an error of `kind` for the first `fails` calls.
+//
+struct Flaky
+{
+	exec : TokioCt                   ,
+	fails: std::cell::Cell<usize>    ,
+	kind : SpawnErrorKind            ,
+}
+
+impl Spawn for Flaky
+{
+	fn spawn_obj( &self, future: futures::task::FutureObj<'static, ()> ) -> Result<(), SpawnError>
+	{
+		self.exec.spawn_obj( future )
+	}
+
+	fn status( &self ) -> Result<(), SpawnError>
+	{
+		match self.fails.get()
+		{
+			0 => Ok(()),
+			n => { self.fails.set( n - 1 ); Err( self.kind.into() ) }
+		}
+	}
+}
+
+impl Timer for Flaky
+{
+	fn sleep( &self, dur: std::time::Duration ) -> futures::future::BoxFuture<'static, ()>
+	{
+		self.exec.sleep( dur )
+	}
+}
+
+
+// spawn_with_retry: retries while status reports that the executor is at capacity.
+//
+#[ test ]
+//
+fn spawn_with_retry()
+{
+	let exec     = Flaky { exec: TokioCtBuilder::new().build().expect( "create tokio current thread" ), fails: 2.into(), kind: SpawnErrorKind::AtCapacity };
+	let (tx, rx) = oneshot::channel();
+
+	exec.exec.block_on( async
+	{
+		exec.spawn_with_retry( async move { tx.send( 5u8 ).expect( "send" ); }, 3, std::time::Duration::from_millis(1) ).await.expect( "spawn" );
+
+		assert_eq!( Ok(5), rx.await );
+	});
+}
+
+
+// spawn_with_retry: gives up after max_attempts.
+//
+#[ test ]
+//
+fn spawn_with_retry_give_up()
+{
+	let exec = Flaky { exec: TokioCtBuilder::new().build().expect( "create tokio current thread" ), fails: 3.into(), kind: SpawnErrorKind::AtCapacity };
+
+	let result = exec.exec.block_on( exec.spawn_with_retry( async {}, 3, std::time::Duration::from_millis(1) ) );
+
+	assert_eq!( SpawnErrorKind::AtCapacity, result.expect_err( "give up" ).kind() );
+	assert_eq!( 0, exec.fails.get() );
+}
+
+
+// spawn_with_retry: returns other errors right away.
+//
+#[ test ]
+//
+fn spawn_with_retry_shutdown()
+{
+	let exec = Flaky { exec: TokioCtBuilder::new().build().expect( "create tokio current thread" ), fails: 3.into(), kind: SpawnErrorKind::Shutdown };
+
+	let result = exec.exec.block_on( exec.spawn_with_retry( async {}, 3, std::time::Duration::from_millis(1) ) );
+
+	assert!( result.expect_err( "shutdown" ).is_shutdown() );
+	assert_eq!( 2, exec.fails.get() );
+}
+
+
+
+// run_until_idle runs spawned tasks to completion.
+//
+#[ test ]
+//
+fn run_until_idle()
+{
+	let exec      = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+	let (tx, rx)  = oneshot::channel();
+	let done      = Rc::new( std::cell::Cell::new( 0 ) );
+	let done2     = done.clone();
+	let done3     = done.clone();
+
+	exec.spawn_local( async move
+	{
+		for _ in 0..10 { tokio::task::yield_now().await; }
+
+		tx.send( 5u8 ).expect( "send" );
+		done2.set( done2.get() + 1 );
+
+	}).expect( "spawn" );
+
+	exec.spawn_local( async move
+	{
+		assert_eq!( Ok(5), rx.await );
+		done3.set( done3.get() + 1 );
+
+	}).expect( "spawn" );
+
+	exec.run_until_idle();
+
+	assert_eq!( 2, done.get() );
+}
+
+
+
+// PrioritizedSpawn polls high priority tasks before low priority ones.
+//
+#[ test ]
+//
+fn prioritized_spawn()
+{
+	let exec  = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+	let prio  = PrioritizedSpawn::new( exec.clone(), 1 ).expect( "create PrioritizedSpawn" );
+	let order = Arc::new( Mutex::new( Vec::new() ) );
+
+	let (tx, rx) = mpsc::unbounded();
+
+	for priority in [ Priority::Low, Priority::Low, Priority::Low, Priority::Low, Priority::High, Priority::High, Priority::High, Priority::High ]
+	{
+		let order = order.clone();
+		let tx    = tx.clone();
+
+		prio.spawn_with_priority( priority, async move
+		{
+			order.lock().expect( "lock" ).push( priority );
+			tx.unbounded_send(()).expect( "send" );
+
+		}).expect( "spawn" );
+	}
+
+	drop( tx );
+
+	exec.block_on( rx.collect::<Vec<()>>() );
+
+	let order = order.lock().expect( "lock" );
+
+	assert_eq!( 8, order.len() );
+	assert!( order[..4].iter().all( |p| *p == Priority::High ) );
+}
+
+
+
+// PrioritizedSpawn gives tasks that keep yielding turns according to their weight.
+//
+#[ test ]
+//
+fn prioritized_spawn_weights()
+{
+	let exec  = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+	let prio  = PrioritizedSpawn::new( exec.clone(), 1 ).expect( "create PrioritizedSpawn" );
+	let order = Arc::new( Mutex::new( Vec::new() ) );
+
+	let task = |priority, turns|
+	{
+		let order = order.clone();
+
+		prio.spawn_handle_with_priority( priority, async move
+		{
+			for _ in 0..turns
+			{
+				order.lock().expect( "lock" ).push( priority );
+
+				// Yield by waking ourselves right away, so the task is ready again immediately.
+				//
+				let mut yielded = false;
+
+				futures::future::poll_fn( |cx|
+				{
+					if yielded { return std::task::Poll::Ready(()) }
+
+					yielded = true;
+					cx.waker().wake_by_ref();
+					std::task::Poll::Pending
+
+				}).await;
+			}
+
+		}).expect( "spawn" )
+	};
+
+	let low  = task( Priority::Low , 2 );
+	let high = task( Priority::High, 8 );
+
+	exec.block_on( futures::future::join( low, high ) );
+
+	use Priority::{ High as H, Low as L };
+
+	assert_eq!( vec![ H, H, H, H, L, H, H, H, H, L ], *order.lock().expect( "lock" ) );
+}
+
+
+
+// AbortOnDrop aborts the task when dropped, even if the handle detaches on drop.
+//
+#[ test ]
+//
+fn abort_on_drop_armed()
+{
+	let exec     = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+	let (tx, rx) = oneshot::channel::<u8>();
+	let (started_tx, started_rx) = oneshot::channel();
+
+	exec.block_on( async
+	{
+		let handle = exec.spawn_handle( async move
+		{
+			started_tx.send(()).expect( "send started" );
+			futures::future::pending::<()>().await;
+			drop( tx );
+
+		}).expect( "spawn" ).detach_on_drop();
+
+		let guard = AbortOnDrop::new( handle );
+
+		started_rx.await.expect( "task started" );
+		drop( guard );
+
+		// The sender is dropped with the task, without it ever sending.
+		//
+		assert!( rx.await.is_err() );
+	});
+}
+
+
+// AbortOnDrop::disarm lets the task keep running.
+//
+#[ test ]
+//
+fn abort_on_drop_disarmed()
+{
+	let exec     = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+	let (tx, rx) = oneshot::channel();
+
+	exec.block_on( async
+	{
+		let guard = AbortOnDrop::new( exec.spawn_handle( async move
+		{
+			tokio::task::yield_now().await;
+			tx.send( 5u8 ).expect( "send" );
+
+		}).expect( "spawn" ) );
+
+		guard.disarm().detach();
+
+		assert_eq!( Ok(5), rx.await );
+
+		// The guard can be awaited directly.
+		//
+		let guard = AbortOnDrop::new( exec.spawn_handle( async { 3u8 } ).expect( "spawn" ) );
+
+		assert_eq!( 3, guard.await );
+	});
+}
+
+
+
+// SpawnBlockingStatic through the WithRuntime of TokioCt from within block_on.
+//
+fn blocking_static<Exec: WithRuntime>( _exec: &Exec ) -> JoinHandle<u8>
+
+	where Exec::Runtime: SpawnBlockingStatic
+{
+	Exec::Runtime::spawn_blocking( || 5u8 ).expect( "spawn blocking" )
+}
+
+
+#[ test ]
+//
+fn spawn_blocking_static()
+{
+	let exec = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+
+	assert_eq!( 5, exec.block_on( async { blocking_static( &exec ).await } ) );
+}
+
+
+
+// JoinHandle::boxed can be awaited and dropping it still cancels the task.
+//
+#[ cfg(not( feature = "detach_on_drop" )) ]
+#[ test ]
+//
+fn join_handle_boxed()
+{
+	let exec     = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+	let (tx, rx) = oneshot::channel::<u8>();
+	let (started_tx, started_rx) = oneshot::channel();
+
+	exec.block_on( async
+	{
+		let handles: Vec< BoxFuture<'static, u8> > = vec!
+		[
+			exec.spawn_handle( async { 1u8 } ).expect( "spawn" ).boxed(),
+			exec.spawn_handle( async { 2u8 } ).expect( "spawn" ).boxed(),
+		];
+
+		assert_eq!( vec![ 1, 2 ], futures::future::join_all( handles ).await );
+
+		let boxed = exec.spawn_handle( async move
+		{
+			started_tx.send(()).expect( "send started" );
+			futures::future::pending::<()>().await;
+			drop( tx );
+
+		}).expect( "spawn" ).boxed();
+
+		started_rx.await.expect( "task started" );
+		drop( boxed );
+
+		assert!( rx.await.is_err() );
+	});
+}
+
+
+// JoinHandle::boxed_local can be awaited and dropping it still cancels the task.
+//
+#[ cfg(not( feature = "detach_on_drop" )) ]
+#[ test ]
+//
+fn join_handle_boxed_local()
+{
+	let exec     = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+	let (tx, rx) = oneshot::channel::<u8>();
+	let (started_tx, started_rx) = oneshot::channel();
+
+	exec.block_on( async
+	{
+		let boxed = exec.spawn_handle_local( async { Rc::new( 5u8 ) } ).expect( "spawn" ).boxed_local();
+
+		assert_eq!( 5, *boxed.await );
+
+		let boxed = exec.spawn_handle_local( async move
+		{
+			started_tx.send(()).expect( "send started" );
+			futures::future::pending::<()>().await;
+			drop( tx );
+
+		}).expect( "spawn" ).boxed_local();
+
+		started_rx.await.expect( "task started" );
+		drop( boxed );
+
+		assert!( rx.await.is_err() );
+	});
+}
+
+
+
+// race_with_timeout returns the first output and cancels the other tasks.
+//
+#[ test ]
+//
+fn race_with_timeout_first()
+{
+	let exec     = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+	let (tx, rx) = oneshot::channel::<u8>();
+
+	exec.block_on( async
+	{
+		let handles = vec!
+		[
+			exec.spawn_handle( async move { futures::future::pending::<()>().await; drop( tx ); 1u8 } ).expect( "spawn" ).detach_on_drop(),
+			exec.spawn_handle( async { 2u8 } ).expect( "spawn" ),
+		];
+
+		let result = race_with_timeout( &exec, handles, Duration::from_secs( 10 ) ).await;
+
+		assert_eq!( Ok(( 2, 1 )), result );
+		assert!( rx.await.is_err() );
+	});
+}
+
+
+// race_with_timeout returns Elapsed and cancels all tasks when the deadline passes.
+//
+#[ test ]
+//
+fn race_with_timeout_elapsed()
+{
+	let exec     = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+	let (tx, rx) = oneshot::channel::<u8>();
+
+	exec.block_on( async
+	{
+		let handles = vec!
+		[
+			exec.spawn_handle( async move { futures::future::pending::<()>().await; drop( tx ); 1u8 } ).expect( "spawn" ),
+		];
+
+		let result = race_with_timeout( &exec, handles, Duration::from_millis( 10 ) ).await;
+
+		assert_eq!( Err( Elapsed ), result );
+		assert!( rx.await.is_err() );
+
+		assert_eq!( Err( Elapsed ), race_with_timeout::<u8, _>( &exec, Vec::new(), Duration::from_millis( 1 ) ).await );
+	});
+}
+
+
+
+// spawn_handle_with_abort: aborting cancels the task and awaiting the handle panics.
+//
+#[ test ]
+//
+fn spawn_handle_with_abort()
+{
+	let exec     = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+	let (tx, rx) = oneshot::channel::<u8>();
+	let (started_tx, started_rx) = oneshot::channel();
+
+	let (handle, abort) = exec.spawn_handle_with_abort( async move
+	{
+		started_tx.send(()).expect( "send started" );
+		futures::future::pending::<()>().await;
+		drop( tx );
+
+	}).expect( "spawn" );
+
+	// Handles of tasks with different outputs can be stored together.
+	//
+	let (other, other_abort) = exec.spawn_handle_with_abort( async { 5u8 } ).expect( "spawn" );
+	let registry: Vec<TaskAbortHandle> = vec![ abort.clone(), other_abort ];
+
+	exec.block_on( async
+	{
+		started_rx.await.expect( "task started" );
+		registry[0].abort();
+
+		assert!( rx.await.is_err() );
+		assert_eq!( 5, other.await );
+	});
+
+	assert!( abort.is_aborted() );
+	assert!( !registry[1].is_aborted() );
+
+	let result = std::panic::catch_unwind( std::panic::AssertUnwindSafe( || exec.block_on( handle ) ) );
+
+	assert!( result.is_err() );
+}
+
+
+// spawn_handle_with_abort: the handle gives the output when not aborted.
+//
+#[ test ]
+//
+fn spawn_handle_with_abort_output()
+{
+	let exec = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+
+	let (handle, abort) = exec.spawn_handle_with_abort( async { 5u8 } ).expect( "spawn" );
+
+	assert_eq!( 5, exec.block_on( handle ) );
+
+	// Aborting a completed task does nothing.
+	//
+	abort.abort();
+	assert!( !abort.is_aborted() );
+}
+
+
+
+// CancellationToken wakes up all tasks waiting on it.
+//
+#[ test ]
+//
+fn cancellation_token()
+{
+	let exec  = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+	let token = CancellationToken::new();
+
+	let handles: Vec<_> = (0..3u8).map( |i|
+	{
+		let token = token.clone();
+
+		exec.spawn_handle( async move { token.cancelled().await; i } ).expect( "spawn" )
+
+	}).collect();
+
+	exec.block_on( async
+	{
+		tokio::task::yield_now().await;
+		assert!( !token.is_cancelled() );
+
+		token.cancel();
+
+		assert_eq!( vec![ 0, 1, 2 ], futures::future::join_all( handles ).await );
+		assert!( token.is_cancelled() );
+
+		// Resolves right away once cancelled.
+		//
+		token.cancelled().await;
+	});
+}
+
+
+// JoinSet yields outputs in completion order and abort_all cancels the rest.
+//
+#[ test ]
+//
+fn join_set()
+{
+	let exec     = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+	let mut set  = JoinSet::new();
+	let (tx, rx) = oneshot::channel::<u8>();
+
+	exec.block_on( async
+	{
+		set.spawn( &exec, async { tokio::time::sleep( Duration::from_millis( 20 ) ).await; 1u8 } ).expect( "spawn" );
+		set.spawn( &exec, async { 2u8 } ).expect( "spawn" );
+
+		assert_eq!( 2, set.len() );
+		assert_eq!( Some(2), set.join_next().await );
+		assert_eq!( Some(1), set.join_next().await );
+		assert_eq!( None   , set.join_next().await );
+
+		set.spawn( &exec, async move { futures::future::pending::<()>().await; drop( tx ); 3u8 } ).expect( "spawn" );
+		tokio::task::yield_now().await;
+
+		set.abort_all();
+
+		assert!( set.is_empty() );
+		assert!( rx.await.is_err() );
+	});
+}
+
+
+// Dropping a JoinSet cancels its tasks, also when their handles detach on drop.
+//
+#[ test ]
+//
+fn join_set_drop()
+{
+	let exec     = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+	let mut set  = JoinSet::new();
+	let (tx, rx) = oneshot::channel::<u8>();
+
+	exec.block_on( async
+	{
+		let handle = exec.spawn_handle( async move { futures::future::pending::<()>().await; drop( tx ); 3u8 } ).expect( "spawn" );
+
+		set.push( handle.detach_on_drop() );
+		tokio::task::yield_now().await;
+
+		drop( set );
+
+		assert!( rx.await.is_err() );
+	});
+}
+
+
+// Dropping a SpawnStream cancels the tasks in flight, also with the detach_on_drop feature.
+//
+#[ test ]
+//
+fn spawn_stream_drop()
+{
+	let exec     = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+	let (tx, rx) = oneshot::channel::<u8>();
+
+	exec.block_on( async
+	{
+		let task       = async move { futures::future::pending::<()>().await; drop( tx ); 3u8 };
+		let mut stream = exec.spawn_stream( vec![ task ], 1 );
+
+		// Spawns the task.
+		//
+		assert!( futures::poll!( stream.next() ).is_pending() );
+		tokio::task::yield_now().await;
+
+		drop( stream );
+
+		assert!( rx.await.is_err() );
+	});
+}
+
+
+// Shutdown: tasks stop when the token is cancelled and wait returns Ok.
+//
+#[ test ]
+//
+fn shutdown_graceful()
+{
+	let exec     = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+	let shutdown = Shutdown::new();
+	let (tx, rx) = oneshot::channel();
+
+	shutdown.spawn( &exec, |token| async move
+	{
+		token.cancelled().await;
+		tx.send( 5u8 ).expect( "send" );
+
+	}).expect( "spawn" );
+
+	exec.block_on( async
+	{
+		shutdown.initiate();
+
+		assert_eq!( Ok(()), shutdown.wait( &exec, Duration::from_secs( 10 ) ).await );
+		assert_eq!( Ok(5), rx.await );
+	});
+
+	// No new tasks once the shutdown has been initiated.
+	//
+	assert!( shutdown.spawn( &exec, |_| async {} ).is_err() );
+}
+
+
+// Shutdown: stragglers get aborted after the grace period.
+//
+#[ test ]
+//
+fn shutdown_stragglers()
+{
+	let exec     = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+	let shutdown = Shutdown::new();
+	let (tx, rx) = oneshot::channel::<u8>();
+
+	// Ignores the token.
+	//
+	shutdown.spawn( &exec, |_token| async move
+	{
+		futures::future::pending::<()>().await;
+		drop( tx );
+
+	}).expect( "spawn" );
+
+	exec.block_on( async
+	{
+		shutdown.initiate();
+
+		assert_eq!( Err( Elapsed ), shutdown.wait( &exec, Duration::from_millis( 10 ) ).await );
+		assert!( rx.await.is_err() );
+	});
+}
+
+
+
+// drain_on_drop: remaining tasks complete when the executor is dropped.
+//
+#[ test ]
+//
+fn drain_on_drop()
+{
+	let exec = TokioCtBuilder::new().drain_on_drop( true ).build().expect( "create tokio current thread" );
+	let done = Rc::new( std::cell::Cell::new( false ) );
+	let flag = done.clone();
+
+	exec.spawn_local( async move
+	{
+		tokio::time::sleep( Duration::from_millis( 10 ) ).await;
+		flag.set( true );
+
+	}).expect( "spawn" );
+
+	let clone = exec.clone();
+
+	drop( exec );
+	assert!( !done.get() );
+
+	drop( clone );
+	assert!( done.get() );
+}
+
+
+
+// spawn_handle_named returns a working JoinHandle.
+//
+#[ test ]
+//
+fn spawn_handle_named()
+{
+	let exec = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+
+	let handle = exec.spawn_handle_named( "answer", async { 42u8 } ).expect( "spawn" );
+	let boxed  = Box::new( exec.clone() ) as Box< dyn SpawnHandle<u8> >;
+	let dyn_handle = boxed.spawn_handle_named( "answer_dyn", async { 43u8 } ).expect( "spawn" );
+
+	assert_eq!( 42, exec.block_on( handle     ) );
+	assert_eq!( 43, exec.block_on( dyn_handle ) );
+}
+
+
+
+// BlockingPool never runs more closures at the same time than the limit.
+//
+#[ test ]
+//
+fn blocking_pool()
+{
+	use std::sync::atomic::{ AtomicUsize, Ordering::SeqCst };
+
+	let exec    = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+	let pool    = BlockingPool::new( exec.clone(), 2 );
+	let current = Arc::new( AtomicUsize::new(0) );
+	let max     = Arc::new( AtomicUsize::new(0) );
+
+	let handles: Vec<_> = (0..8u8).map( |i|
+	{
+		let current = current.clone();
+		let max     = max.clone();
+
+		pool.spawn( move ||
+		{
+			let now = current.fetch_add( 1, SeqCst ) + 1;
+			max.fetch_max( now, SeqCst );
+
+			std::thread::sleep( Duration::from_millis( 10 ) );
+
+			current.fetch_sub( 1, SeqCst );
+			i
+
+		}).expect( "spawn blocking" )
+
+	}).collect();
+
+	let outputs = exec.block_on( futures::future::join_all( handles ) );
+
+	assert_eq!( outputs, (0..8).collect::<Vec<_>>() );
+	assert!( max.load( SeqCst ) <= 2 );
+}
+
+
+
+// CoopBudget asks to yield every interval iterations.
+//
+#[ test ]
+//
+fn coop_budget()
+{
+	let exec = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+
+	exec.block_on( async
+	{
+		let mut coop   = CoopBudget::new( 3 );
+		let mut yields = Vec::new();
+
+		for i in 1..=9
+		{
+			if coop.should_yield()
+			{
+				yields.push( i );
+				Tokio.yield_now().await;
+			}
+		}
+
+		assert_eq!( yields, vec![ 3, 6, 9 ] );
+
+		coop.should_yield();
+		coop.reset();
+
+		assert!( !coop.should_yield() );
+		assert!( !coop.should_yield() );
+		assert!(  coop.should_yield() );
+	});
+}
+
+
+
+// enabled_runtimes lists tokio_ct.
+//
+#[ test ]
+//
+fn enabled_runtimes()
+{
+	const RUNTIMES: &[&str] = async_executors::enabled_runtimes();
+
+	assert!( RUNTIMES.contains( &"tokio_ct" ) );
+	assert!( RUNTIMES.windows(2).all( |w| w[0] < w[1] ) );
+}
+
+
+
+// spawn_into_sender sends the outputs into one channel and drops them when the receiver is gone.
+//
+#[ cfg( feature = "channel" ) ]
+#[ test ]
+//
+fn spawn_into_sender()
+{
+	let exec       = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+	let (tx, rx)   = mpsc::channel( 1 );
+	let (dtx, drx) = mpsc::channel( 1 );
+
+	let handles: Vec<_> = (1..=3u8)
+
+		.map( |i| exec.spawn_into_sender( async move { i }, tx.clone() ).expect( "spawn" ) )
+		.collect()
+	;
+
+	drop( tx  );
+	drop( drx );
+
+	let dropped = exec.spawn_into_sender( async { 4u8 }, dtx ).expect( "spawn" );
+
+	let mut outputs = exec.block_on( async
+	{
+		let outputs: Vec<u8> = rx.collect().await;
+
+		futures::future::join_all( handles ).await;
+		dropped.await;
+
+		outputs
+	});
+
+	outputs.sort_unstable();
+
+	assert_eq!( outputs, vec![ 1, 2, 3 ] );
+}
+
+
+
+// spawn_blocking_catch_unwind resolves to Err when the closure panics.
+//
+#[ test ]
+//
+fn spawn_blocking_catch_unwind()
+{
+	let exec = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+
+	let ok    = exec.spawn_blocking_catch_unwind( ||          5u8           ).expect( "spawn blocking" );
+	let panic = exec.spawn_blocking_catch_unwind( || -> u8 { panic!("oops") } ).expect( "spawn blocking" );
+
+	assert_eq!( 5, exec.block_on( ok ).expect( "no panic" ) );
+
+	let payload = exec.block_on( panic ).expect_err( "panic" );
+
+	assert_eq!( Some( &"oops" ), payload.downcast_ref::<&str>() );
+}
+
+
+
+// ContextBlockingPool initializes the context once per worker and survives panics.
+//
+#[ cfg( feature = "channel" ) ]
+#[ test ]
+//
+fn context_blocking_pool()
+{
+	use std::sync::atomic::{ AtomicUsize, Ordering::SeqCst };
+
+	let exec   = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+	let inits  = Arc::new( AtomicUsize::new(0) );
+	let inits2 = inits.clone();
+
+	// The context is !Send and !Clone, it never leaves the worker.
+	//
+	struct Counter( Rc<std::cell::Cell<u32>> );
+
+	let pool = ContextBlockingPool::new( 2, move ||
+	{
+		inits2.fetch_add( 1, SeqCst );
+		Counter( Rc::new( std::cell::Cell::new( 0u32 ) ) )
+
+	}).expect( "create pool" );
+
+	// Clones share the workers.
+	//
+	let clone = pool.clone();
+
+	let panic = pool.spawn( |_ctx| -> u32 { panic!( "oops" ) } ).expect( "spawn" );
+
+	let handles: Vec<_> = (0..10)
+
+		.map( |i| if i % 2 == 0 { &pool } else { &clone } )
+		.map( |p| p.spawn( |ctx| { ctx.0.set( ctx.0.get() + 1 ); ctx.0.get() } ).expect( "spawn" ) )
+		.collect()
+	;
+
+	let outputs  = exec.block_on( futures::future::join_all( handles ) );
+	let panicked = exec.block_on( std::panic::AssertUnwindSafe( panic ).catch_unwind() );
+
+	assert!( panicked.is_err() );
+	assert_eq!( 2, inits.load( SeqCst ) );
+
+	// The context lives as long as the worker, so one of them has counted at least half of the closures.
+	//
+	assert!( outputs.iter().max().expect( "outputs" ) >= &5 );
+}
+
+
+
+// TokioJoinHandle::join tells a panic, an abort and a runtime shutdown apart.
+//
+#[ test ]
+//
+fn tokio_join_handle_join()
+{
+	fn tokio_handle<T>( handle: JoinHandle<T> ) -> TokioJoinHandle<T>
+	{
+		match handle
+		{
+			JoinHandle::TokioJoinHandle( handle ) => handle,
+			JoinHandle::DetachOnDrop   ( handle ) => tokio_handle( handle.into_inner().expect( "not joined" ) ),
+			_                                     => unreachable!(),
+		}
+	}
+
+	let exec = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+
+	let ok      = tokio_handle( exec.spawn_handle( async { 5u8 }                  ).expect( "spawn" ) );
+	let panics  = tokio_handle( exec.spawn_handle( async { panic!( "oops" ) }     ).expect( "spawn" ) );
+	let aborted = tokio_handle( exec.spawn_handle( futures::future::pending::<u8>() ).expect( "spawn" ) );
+	let gone    = tokio_handle( exec.spawn_handle( futures::future::pending::<u8>() ).expect( "spawn" ) );
+
+	aborted.abort();
+
+	exec.block_on( async
+	{
+		assert_eq!( 5, ok.join().await.expect( "join" ) );
+
+		match panics.join().await
+		{
+			Err( JoinErrorKind::Panic( payload ) ) => assert_eq!( Some( &"oops" ), payload.downcast_ref::<&str>() ),
+			other => panic!( "expected a panic, got: {:?}", other ),
+		}
+
+		assert!( matches!( aborted.join().await, Err( JoinErrorKind::Cancelled ) ) );
+	});
+
+	drop( exec );
+
+	assert!( matches!( block_on( gone.join() ), Err( JoinErrorKind::RuntimeGone ) ) );
+}
+
+
+
+// Dropping the future of TokioJoinHandle::join cancels the task, like dropping the handle.
+//
+#[ test ]
+//
+fn tokio_join_handle_join_dropped()
+{
+	let exec     = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+	let (tx, rx) = oneshot::channel::<u8>();
+
+	let handle = exec.spawn_handle( async move { futures::future::pending::<()>().await; drop( tx ); } ).expect( "spawn" );
+
+	fn tokio_handle<T>( handle: JoinHandle<T> ) -> TokioJoinHandle<T>
+	{
+		match handle
+		{
+			JoinHandle::TokioJoinHandle( handle ) => handle,
+			JoinHandle::DetachOnDrop   ( handle ) => tokio_handle( handle.into_inner().expect( "not joined" ) ),
+			_                                     => unreachable!(),
+		}
+	}
+
+	exec.block_on( async
+	{
+		let join = Box::pin( tokio_handle( handle ).join() );
+
+		// Poll the join future once, then drop it.
+		//
+		assert!( futures::poll!( join ).is_pending() );
+		assert!( rx.await.is_err() );
+	});
+}
+
+
+
+// block_on_catch_unwind returns the panic of the top level future and the executor stays usable.
+//
+#[ test ]
+//
+fn block_on_catch_unwind()
+{
+	let exec = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+
+	let payload = exec.block_on_catch_unwind( async { panic!( "oops" ) } ).expect_err( "catch panic" );
+
+	assert_eq!( Some( &"oops" ), payload.downcast_ref::<&str>() );
+	assert_eq!( 5, exec.block_on_catch_unwind( async { 5u8 } ).expect( "no panic" ) );
+	assert_eq!( 6, exec.block_on( async { 6u8 } ) );
+}
+
+
+
+// join_timeout returns the output when the task completes in time.
+//
+#[ test ]
+//
+fn join_timeout_in_time()
+{
+	let exec = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+
+	exec.block_on( async
+	{
+		let handle = exec.spawn_handle( async { 5u8 } ).expect( "spawn" );
+
+		assert_eq!( Ok(5), handle.join_timeout( Duration::from_secs( 5 ), &exec ).await );
+	});
+}
+
+
+
+// join_timeout aborts the task and returns Elapsed when the deadline passes.
+//
+#[ test ]
+//
+fn join_timeout_elapsed()
+{
+	let exec     = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+	let (tx, rx) = oneshot::channel::<()>();
+
+	exec.block_on( async
+	{
+		// The task holds the sender until it gets dropped. It detaches on drop, so only an abort frees it.
+		//
+		let handle = exec.spawn_handle( async move
+		{
+			let _tx = tx;
+			futures::future::pending::<u8>().await
+
+		}).expect( "spawn" ).detach_on_drop();
+
+		assert_eq!( Err(Elapsed), handle.join_timeout( Duration::from_millis( 10 ), &exec ).await );
+		assert!( rx.await.is_err() );
+	});
+}
+
+
+
+// dropping a JoinHandle cancels the task, or detaches it with the detach_on_drop feature.
+//
+#[ test ]
+//
+fn default_drop_policy()
+{
+	let exec             = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+	let (in_tx , in_rx ) = oneshot::channel::<u8>();
+	let (out_tx, out_rx) = oneshot::channel::<u8>();
+
+	exec.block_on( async
+	{
+		let handle = exec.spawn_handle( async move
+		{
+			let x = in_rx.await.expect( "receive" );
+			out_tx.send( x ).expect( "send" );
+
+		}).expect( "spawn" );
+
+		assert!( handle.as_tokio().is_some() );
+
+		drop( handle );
+
+		// Tokio drops an aborted task asynchronously, so the receiver might still be around.
+		//
+		let _ = in_tx.send( 5 );
+
+		#[ cfg( feature = "detach_on_drop" ) ]
+		//
+		assert_eq!( Ok(5), out_rx.await );
+
+		#[ cfg(not( feature = "detach_on_drop" )) ]
+		//
+		assert!( out_rx.await.is_err() );
+	});
+}
+
+
+
+// JoinHandle::abort cancels the task with or without the detach_on_drop feature.
+//
+#[ test ]
+//
+fn join_handle_abort_any_policy()
+{
+	let exec     = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+	let (tx, rx) = oneshot::channel::<()>();
+
+	exec.block_on( async
+	{
+		let handle = exec.spawn_handle( async move
+		{
+			let _tx = tx;
+			futures::future::pending::<()>().await;
+
+		}).expect( "spawn" );
+
+		handle.abort();
+
+		assert!( rx.await.is_err() );
+	});
+}
+
+
+
+// MapSpawn applies the middleware to futures spawned with spawn and spawn_handle.
+//
+#[ test ]
+//
+fn map_spawn()
+{
+	use std::sync::atomic::{ AtomicUsize, Ordering::SeqCst };
+
+	let exec    = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+	let started = Arc::new( AtomicUsize::new( 0 ) );
+	let done    = Arc::new( AtomicUsize::new( 0 ) );
+
+	let (s, d) = ( started.clone(), done.clone() );
+
+	let mapped = exec.clone().map_spawn( move |fut|
+	{
+		let (s, d) = ( s.clone(), d.clone() );
+
+		async move
+		{
+			s.fetch_add( 1, SeqCst );
+			fut.await;
+			d.fetch_add( 1, SeqCst );
+
+		}.boxed()
+	});
+
+	exec.block_on( async
+	{
+		let (tx, rx) = oneshot::channel();
+
+		mapped.spawn( async move { tx.send( 4u8 ).expect( "send" ); } ).expect( "spawn" );
+
+		assert_eq!( 4, rx.await.expect( "receive" ) );
+		assert_eq!( 5, mapped.spawn_handle( async { 5u8 } ).expect( "spawn" ).await );
+	});
+
+	assert_eq!( 2, started.load( SeqCst ) );
+	assert_eq!( 2, done   .load( SeqCst ) );
+}
+
+
+
+// JoinHandle::into_stream yields the output once and can be merged with other streams.
+//
+#[ test ]
+//
+fn join_handle_into_stream()
+{
+	let exec = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+
+	exec.block_on( async
+	{
+		let mut single = exec.spawn_handle( async { 5u8 } ).expect( "spawn" ).into_stream().boxed();
+
+		assert_eq!( Some(5), single.next().await );
+		assert_eq!( None   , single.next().await );
+
+		let task  = exec.spawn_handle( async { 3u8 } ).expect( "spawn" ).into_stream().boxed();
+		let other = futures::stream::iter( vec![ 1u8, 2 ] ).boxed();
+
+		let mut all: Vec<u8> = futures::stream::select_all( vec![ task, other ] ).collect().await;
+		all.sort_unstable();
+
+		assert_eq!( vec![ 1, 2, 3 ], all );
+	});
+}
+
+
+
+// TokioCt::idle resolves once the spawned tasks stopped making progress.
+//
+#[ test ]
+//
+fn tokio_ct_idle()
+{
+	let exec  = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+	let count = Arc::new( Mutex::new( 0 ) );
+
+	exec.block_on( async
+	{
+		for _ in 0..3
+		{
+			let count = count.clone();
+
+			exec.spawn( async move
+			{
+				Tokio.yield_now().await;
+				*count.lock().expect( "lock" ) += 1;
+
+			}).expect( "spawn" );
+		}
+
+		exec.idle().await;
+
+		assert_eq!( 3, *count.lock().expect( "lock" ) );
+	});
+}
+
+
+
+// MeteredExecutor::idle resolves once all tasks spawned through it have completed or were dropped.
+//
+#[ test ]
+//
+fn metered_executor_idle()
+{
+	let exec    = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+	let metered = MeteredExecutor::new( exec.clone() );
+
+	exec.block_on( async
+	{
+		// Resolves right away when nothing was spawned.
+		//
+		metered.idle().await;
+
+		let (tx, rx) = oneshot::channel::<()>();
+
+		metered.spawn_local( async move { rx.await.expect( "receive" ); } ).expect( "spawn" );
+		let pending = metered.spawn_handle( futures::future::pending::<()>() ).expect( "spawn" );
+
+		Tokio.yield_now().await;
+		assert_eq!( 2, metered.outstanding() );
+
+		let idle = metered.idle();
+
+		tx.send(()).expect( "send" );
+		pending.abort();
+
+		idle.await;
+		assert_eq!( 0, metered.outstanding() );
+	});
+}
+
+
+
+// thread_name names the blocking threads, block_on runs on the calling thread.
+//
+#[ test ]
+//
+fn thread_name()
+{
+	let handle = std::thread::Builder::new().name( "ct_main".into() ).spawn( ||
+	{
+		let exec = TokioCtBuilder::new().thread_name( "ct_worker" ).build().expect( "create tokio current thread" );
+
+		exec.block_on( async
+		{
+			let current = || std::thread::current().name().map( String::from );
+
+			let blocking = exec.spawn_blocking( current ).expect( "spawn blocking" ).await;
+
+			( current(), blocking )
+		})
+
+	}).expect( "spawn thread" );
+
+	let (main, blocking) = handle.join().expect( "join thread" );
+
+	assert_eq!( Some( "ct_main"   ), main    .as_deref() );
+	assert_eq!( Some( "ct_worker" ), blocking.as_deref() );
+}
+
+
+
+// OnTaskPanic calls the callback for panics in tasks with and without a handle.
+//
+#[ test ]
+//
+fn on_task_panic()
+{
+	let exec   = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+	let panics = Arc::new( Mutex::new( Vec::new() ) );
+	let p2     = panics.clone();
+
+	let watched = exec.clone().on_task_panic( move |payload|
+	{
+		let msg = payload.downcast_ref::<&str>().copied().unwrap_or( "unknown" );
+		p2.lock().expect( "lock" ).push( msg.to_string() );
+	});
+
+	exec.block_on( async
+	{
+		watched.spawn( async { panic!( "detached" ) } ).expect( "spawn" );
+
+		let handle = watched.spawn_handle( async { panic!( "joined" ) } ).expect( "spawn" );
+
+		assert!( std::panic::AssertUnwindSafe( handle ).catch_unwind().await.is_err() );
+		assert_eq!( 5, watched.spawn_handle( async { 5u8 } ).expect( "spawn" ).await );
+
+		exec.idle().await;
+	});
+
+	let mut panics = panics.lock().expect( "lock" ).clone();
+	panics.sort();
+
+	assert_eq!( vec![ "detached", "joined" ], panics );
+}
+
+
+
+// TimedSpawn reports the duration of completed tasks, None for cancelled ones and nothing when spawning fails.
+//
+#[ test ]
+//
+fn timed_spawn()
+{
+	let exec    = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+	let timings = Arc::new( Mutex::new( Vec::new() ) );
+	let t2      = timings.clone();
+	let t3      = timings.clone();
+
+	let timed = TimedSpawn::new( exec.clone(), move |time| t2.lock().expect( "lock" ).push( time ) );
+	let full  = TimedSpawn::new( ManualExecutor::new( 0 ), move |time| t3.lock().expect( "lock" ).push( time ) );
+
+	assert!( full.spawn( async {} ).is_err() );
+
+	exec.block_on( async
+	{
+		let sleep = exec.sleep( Duration::from_millis( 20 ) );
+
+		timed.spawn_handle( sleep ).expect( "spawn" ).await;
+
+		let pending = timed.spawn_handle( futures::future::pending::<()>() ).expect( "spawn" );
+
+		Tokio.yield_now().await;
+		pending.abort();
+		exec.idle().await;
+	});
+
+	let timings = timings.lock().expect( "lock" );
+
+	assert_eq!( 2, timings.len() );
+	assert!( timings[0].expect( "completed" ) >= Duration::from_millis( 20 ) );
+	assert_eq!( None, timings[1] );
+}
+
+
+
+// spawn_all returns the handles in input order.
+//
+#[ test ]
+//
+fn spawn_all()
+{
+	let exec = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+
+	exec.block_on( async
+	{
+		let futs    = (0..5u8).rev().map( |i| async move { Tokio.yield_now().await; i } );
+		let handles = exec.spawn_all( futs ).expect( "spawn" );
+
+		assert_eq!( vec![ 4, 3, 2, 1, 0 ], futures::future::join_all( handles ).await );
+	});
+}
+
+
+
+// spawn_all aborts the tasks it already spawned when a spawn fails.
+//
+#[ test ]
+//
+fn spawn_all_partial_failure()
+{
+	// Accepts a limited number of tasks, then reports that it's at capacity.
+	//
+	struct AtCapacity
+	{
+		exec: TokioCt,
+		left: std::cell::Cell<usize>,
+	}
+
+	impl SpawnHandle<()> for AtCapacity
+	{
+		fn spawn_handle_obj( &self, future: futures::task::FutureObj<'static, ()> ) -> Result<JoinHandle<()>, SpawnError>
+		{
+			if self.left.get() == 0
+			{
+				return Err( SpawnError::at_capacity() );
+			}
+
+			self.left.set( self.left.get() - 1 );
+			self.exec.spawn_handle_obj( future )
+		}
+	}
+
+	let exec    = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+	let limited = AtCapacity { exec: exec.clone(), left: std::cell::Cell::new( 2 ) };
+
+	let (tx1, rx1) = oneshot::channel::<()>();
+	let (tx2, rx2) = oneshot::channel::<()>();
+
+	let holds = |tx: oneshot::Sender<()>| async move
+	{
+		let _tx = tx;
+		futures::future::pending::<()>().await;
+	};
+
+	exec.block_on( async
+	{
+		let futs = vec![ holds( tx1 ).boxed(), holds( tx2 ).boxed(), async {}.boxed() ];
+
+		let err = limited.spawn_all( futs ).expect_err( "third spawn fails" );
+
+		assert_eq!( SpawnErrorKind::AtCapacity, err.kind() );
+
+		// The senders are dropped with the aborted tasks.
+		//
+		assert!( rx1.await.is_err() );
+		assert!( rx2.await.is_err() );
+	});
+}
+
+
+
+// metrics count pending tasks on the runtime and on the local set.
+//
+#[ cfg( feature = "tokio_ct_metrics" ) ]
+#[ test ]
+//
+fn metrics()
+{
+	let exec    = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+	let metrics = exec.metrics();
+
+	assert_eq!( 0, metrics.active_tasks_count() );
+
+	exec.spawn_local( futures::future::pending() ).expect( "spawn" );
+
+	assert_eq!( 1, metrics.local_tasks_count() );
+	assert_eq!( 1, metrics.active_tasks_count() );
+
+	let handle = exec.spawn_handle( futures::future::pending::<()>() ).expect( "spawn" );
+
+	exec.block_on( Tokio.yield_now() );
+
+	assert_eq!( 2, metrics.active_tasks_count() );
+
+	// Completed tasks are no longer counted.
+	//
+	exec.block_on( async { exec.spawn_handle_local( async {} ).expect( "spawn" ).await } );
+
+	assert_eq!( 1, metrics.local_tasks_count() );
+
+	handle.detach();
+}
+
+
+
+// spawn_handle_ttl resolves to Some when the task completes in time.
+//
+#[ test ]
+//
+fn spawn_handle_ttl_in_time()
+{
+	let exec = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+
+	let handle = exec.spawn_handle_ttl( async { 5u8 }, Duration::from_secs( 10 ), &exec ).expect( "spawn" );
+
+	assert_eq!( Some( 5 ), exec.block_on( handle ) );
+}
+
+
+
+// spawn_handle_ttl cancels the task when the ttl elapses, also when the handle is detached.
+//
+#[ test ]
+//
+fn spawn_handle_ttl_elapsed()
+{
+	let exec = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+
+	let handle = exec.spawn_handle_ttl( futures::future::pending::<u8>(), Duration::from_millis( 10 ), &exec ).expect( "spawn" );
+
+	assert_eq!( None, exec.block_on( handle ) );
+
+
+	let (tx, rx) = oneshot::channel::<()>();
+
+	let task = async move
+	{
+		let _tx = tx;
+		futures::future::pending::<()>().await;
+	};
+
+	exec.spawn_handle_ttl( task, Duration::from_millis( 10 ), &exec ).expect( "spawn" ).detach();
+
+	// The sender is dropped with the cancelled task.
+	//
+	assert!( exec.block_on( rx ).is_err() );
+}
+
+
+
+// blocking_iter_stream yields the items of the iterator and stops pulling when dropped.
+//
+#[ test ]
+//
+fn blocking_iter_stream()
+{
+	use futures::TryStreamExt;
+	use std::sync::atomic::{ AtomicUsize, Ordering::SeqCst };
+
+	let exec = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+
+	let items: Result<Vec<u32>, _> = exec.block_on( async_executors::blocking_iter_stream( &exec, 0..5 ).try_collect() );
+
+	assert_eq!( vec![ 0, 1, 2, 3, 4 ], items.expect( "spawn" ) );
+
+
+	let pulled  = Arc::new( AtomicUsize::new( 0 ) );
+	let pulled2 = pulled.clone();
+
+	let counted = (0..100).inspect( move |_| { pulled2.fetch_add( 1, SeqCst ); } );
+
+	let items: Result<Vec<u32>, _> = exec.block_on( async_executors::blocking_iter_stream( &exec, counted ).take( 2 ).try_collect() );
+
+	assert_eq!( vec![ 0, 1 ], items.expect( "spawn" ) );
+	assert_eq!( 2, pulled.load( SeqCst ) );
+}
+
+
+
+// blocking_iter_stream yields the error when spawning fails and ends after it.
+//
+#[ test ]
+//
+fn blocking_iter_stream_error()
+{
+	// Runs the first call to next on a new thread and refuses the others.
+	//
+	#[ derive( Default ) ]
+	//
+	struct Once( std::sync::atomic::AtomicBool );
+
+	impl SpawnBlocking<Option<u8>> for Once
+	{
+		fn spawn_blocking_obj( &self, func: Box<dyn FnOnce() -> Option<u8> + Send> ) -> Result<JoinHandle<Option<u8>>, SpawnError>
+		{
+			if self.0.swap( true, std::sync::atomic::Ordering::SeqCst )
+			{
+				return Err( SpawnError::at_capacity() );
+			}
+
+			let (remote, handle) = async { func() }.remote_handle();
+
+			std::thread::spawn( move || futures::executor::block_on( remote ) );
+
+			Ok( handle.into() )
+		}
+	}
+
+	let exec    = Once::default();
+	let results = futures::executor::block_on( async_executors::blocking_iter_stream( &exec, 1..=3u8 ).collect::<Vec<_>>() );
+
+	assert_eq!( 2, results.len() );
+	assert_eq!( 1, *results[0].as_ref().expect( "first item" ) );
+	assert_eq!( SpawnErrorKind::AtCapacity, results[1].as_ref().expect_err( "at capacity" ).kind() );
+}
+
+
+
+// spawn_handle_with_ready signals that the task started before it produces its output.
+//
+#[ cfg( feature = "channel" ) ]
+#[ test ]
+//
+fn spawn_handle_with_ready()
+{
+	let exec                = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+	let (gate_tx, gate_rx)  = futures::channel::oneshot::channel::<()>();
+	let (mut handle, ready) = exec.spawn_handle_with_ready( async move
+	{
+		gate_rx.await.expect( "open gate" );
+		5u8
+
+	}).expect( "spawn" );
+
+	exec.block_on( async
+	{
+		ready.await.expect( "ready signal" );
+
+		// The task has started, but it can't have produced output yet.
+		//
+		assert!( futures::poll!( &mut handle ).is_pending() );
+
+		gate_tx.send(()).expect( "send" );
+
+		assert_eq!( 5, handle.await );
+	});
+}
+
+
+
+// block_on_all runs spawned tasks that outlive the top level future to completion, block_on does not.
+//
+#[ test ]
+//
+fn block_on_all()
+{
+	let exec = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+
+	let spawn_sleeper = |exec: &TokioCt|
+	{
+		let done  = Rc::new( std::cell::Cell::new( false ) );
+		let done2 = done.clone();
+		let exec2 = exec.clone();
+
+		exec.spawn_local( async move
+		{
+			exec2.sleep( Duration::from_millis( 20 ) ).await;
+			done2.set( true );
+
+		}).expect( "spawn" );
+
+		done
+	};
+
+	let done = exec.block_on( async { spawn_sleeper( &exec ) } );
+	assert!( !done.get() );
+
+	// The task from the first call is still there, so it completes now as well.
+	//
+	let done2 = exec.block_on_all( async { spawn_sleeper( &exec ) } );
+
+	assert!( done.get()  );
+	assert!( done2.get() );
+
+	assert_eq!( 5, exec.block_on_all( async { 5 } ) );
+}
+
+
+
+
+// spawn_send spawns a Send future through `impl LocalSpawn`, Spawn puts it on the same local set.
+//
+#[ test ]
+//
+fn spawn_send()
+{
+	fn local( exec: impl LocalSpawn, tx: mpsc::Sender<u8> )
+	{
+		exec.spawn_send( async move { tx.clone().send( 1 ).await.expect( "send" ) } ).expect( "spawn" );
+	}
+
+	fn send( exec: impl Spawn, tx: mpsc::Sender<u8> )
+	{
+		exec.spawn( async move { tx.clone().send( 2 ).await.expect( "send" ) } ).expect( "spawn" );
+	}
+
+	let exec     = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+	let set      = tokio::task::LocalSet::new();
+	let (tx, rx) = mpsc::channel( 4 );
+
+	local( &exec, tx.clone() );
+	send ( &exec, tx.clone() );
+
+	local( LocalSetSpawn( &set ), tx.clone() );
+	send ( LocalSetSpawn( &set ), tx       );
+
+	// Only the tasks on the local set of the executor ran.
+	//
+	let from_exec: Vec<u8> = exec.block_on( rx.take( 2 ).collect() );
+	assert_eq!( vec![ 1, 2 ], from_exec );
+}
+
+
+
+// spawn_blocking_stream yields results in completion order, or in input order with one job in flight.
+//
+#[ test ]
+//
+fn spawn_blocking_stream()
+{
+	use futures::TryStreamExt;
+
+	let exec = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+
+	let jobs = ||
+	{
+		[ 60u64, 10, 30 ].iter().map( |&ms| move ||
+		{
+			std::thread::sleep( Duration::from_millis( ms ) );
+			ms
+		})
+	};
+
+	let parallel: Result<Vec<u64>, _> = exec.block_on( exec.spawn_blocking_stream( jobs(), 3 ).try_collect() );
+	assert_eq!( vec![ 10, 30, 60 ], parallel.expect( "spawn" ) );
+
+	let serial: Result<Vec<u64>, _> = exec.block_on( exec.spawn_blocking_stream( jobs(), 1 ).try_collect() );
+	assert_eq!( vec![ 60, 10, 30 ], serial.expect( "spawn" ) );
+}
+
+
+
+// spawn_blocking_stream yields the error when spawning fails and stops taking jobs.
+//
+#[ test ]
+//
+fn spawn_blocking_stream_error()
+{
+	// Runs the first job on a new thread and refuses the others.
+	//
+	#[ derive( Default ) ]
+	//
+	struct Once( std::sync::atomic::AtomicBool );
+
+	impl SpawnBlocking<u8> for Once
+	{
+		fn spawn_blocking_obj( &self, func: Box<dyn FnOnce() -> u8 + Send> ) -> Result<JoinHandle<u8>, SpawnError>
+		{
+			if self.0.swap( true, std::sync::atomic::Ordering::SeqCst )
+			{
+				return Err( SpawnError::at_capacity() );
+			}
+
+			let (remote, handle) = async { func() }.remote_handle();
+
+			std::thread::spawn( move || futures::executor::block_on( remote ) );
+
+			Ok( handle.into() )
+		}
+	}
+
+	let exec = Once::default();
+	let jobs = (1..=3u8).map( |i| move || i );
+
+	let results: Vec<_> = futures::executor::block_on( exec.spawn_blocking_stream( jobs, 3 ).collect() );
+
+	assert_eq!( 2, results.len() );
+	assert_eq!( SpawnErrorKind::AtCapacity, results[0].as_ref().expect_err( "at capacity" ).kind() );
+	assert_eq!( 1, *results[1].as_ref().expect( "first job" ) );
+}
+
+
+
+// spawn_handle_local_catch_unwind resolves to Err with the payload, spawn_handle_local unwinds the awaiting thread.
+//
+#[ test ]
+//
+fn spawn_handle_local_catch_unwind()
+{
+	let exec     = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+	let not_send = Rc::new( 5u8 );
+
+	let caught = exec.spawn_handle_local_catch_unwind( async { panic!( "oops" ) } ).expect( "spawn" );
+	let fine   = exec.spawn_handle_local_catch_unwind( async move { *not_send } ).expect( "spawn" );
+
+	let payload = exec.block_on( caught ).expect_err( "catch panic" );
+
+	assert_eq!( Some( &"oops" ), payload.downcast_ref::<&str>() );
+	assert_eq!( 5, exec.block_on( fine ).expect( "no panic" ) );
+
+
+	let unwinds = exec.spawn_handle_local( async { panic!( "oops" ) } ).expect( "spawn" );
+
+	assert!( exec.block_on_catch_unwind( unwinds ).is_err() );
+}
+
+
+
+// flatten_result gives the output, the error of the task as Failed and a panic as Panicked.
+//
+#[ test ]
+//
+fn flatten_result()
+{
+	let exec = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+
+	let ok       = exec.spawn_handle( async { Ok::<u8, String>( 5 )                    } ).expect( "spawn" );
+	let failed   = exec.spawn_handle( async { Err::<u8, _>( "nope".to_string() )      } ).expect( "spawn" );
+	let panicked = exec.spawn_handle( async { if true { panic!( "oops" ) } Ok::<u8, String>( 5 ) } ).expect( "spawn" );
+
+	assert_eq!( 5, exec.block_on( ok.flatten_result() ).expect( "success" ) );
+
+	match exec.block_on( failed.flatten_result() )
+	{
+		Err( TaskFailure::Failed( e ) ) => assert_eq!( "nope", e ),
+		other                          => panic!( "expected Failed, got: {:?}", other ),
+	}
+
+	match exec.block_on( panicked.flatten_result() )
+	{
+		Err( TaskFailure::Panicked(_) ) => {}
+		other                           => panic!( "expected Panicked, got: {:?}", other ),
+	}
+
+	// The whole point: `?` covers both.
+	//
+	let sum = exec.block_on( async
+	{
+		let a = exec.spawn_handle( async { Ok::<u8, String>( 2 ) } ).expect( "spawn" ).flatten_result().await?;
+		let b = exec.spawn_handle( async { Ok::<u8, String>( 3 ) } ).expect( "spawn" ).flatten_result().await?;
+
+		Ok::<_, TaskFailure<String>>( a + b )
+	});
+
+	assert_eq!( 5, sum.expect( "success" ) );
+}
+
+
+
+// flatten_result gives Cancelled for an aborted task, without unwinding.
+//
+#[ test ]
+//
+fn flatten_result_cancelled()
+{
+	let exec = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+
+	let (handle, abort) = exec.spawn_handle_with_abort( async
+	{
+		futures::future::pending::<()>().await;
+		Ok::<u8, String>( 5 )
+
+	}).expect( "spawn" );
+
+	abort.abort();
+
+	match exec.block_on( handle.flatten_result() )
+	{
+		Err( TaskFailure::Cancelled ) => {}
+		other                         => panic!( "expected Cancelled, got: {:?}", other ),
+	}
+}
+
+
+
+#[ derive( Debug, PartialEq ) ]
+//
+enum ScopeError { Spawn, Task( u8 ) }
+
+impl From<SpawnError> for ScopeError
+{
+	fn from( _: SpawnError ) -> Self { ScopeError::Spawn }
+}
+
+
+// try_scope returns the outputs in spawn order when all tasks succeed.
+//
+#[ test ]
+//
+fn try_scope_ok()
+{
+	let exec = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+
+	let out = exec.block_on( try_scope( &exec, |s|
+	{
+		s.spawn( async { tokio::time::sleep( Duration::from_millis( 20 ) ).await; Ok::<_, ScopeError>( 1u8 ) } )?;
+		s.spawn( async { Ok( 2u8 ) } )?;
+		s.spawn( async { Ok( 3u8 ) } )?;
+		Ok(())
+	}));
+
+	assert_eq!( Ok( vec![ 1, 2, 3 ] ), out );
+}
+
+
+// try_scope returns the first error and aborts the siblings right away.
+//
+#[ test ]
+//
+fn try_scope_err()
+{
+	let exec = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+
+	let (dropped_tx, dropped_rx) = futures::channel::oneshot::channel::<()>();
+
+	exec.block_on( async
+	{
+		let out = try_scope( &exec, |s|
+		{
+			s.spawn( async move
+			{
+				// Tells us it got dropped when it's gone.
+				//
+				let _dropped = dropped_tx;
+				futures::future::pending::<Result<u8, ScopeError>>().await
+
+			})?;
+
+			s.spawn( async { Err( ScopeError::Task( 7 ) ) } )?;
+			Ok(())
+
+		}).await;
+
+		assert_eq!( Err( ScopeError::Task( 7 ) ), out );
+
+		let aborted = tokio::time::timeout( Duration::from_secs( 5 ), dropped_rx ).await;
+
+		assert!( aborted.expect( "sibling aborted in time" ).is_err() );
+	});
+}
+
+
+
+// spawn_until resolves to Some when the task completes before the token is cancelled.
+//
+#[ test ]
+//
+fn spawn_until_completed()
+{
+	let exec  = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+	let token = CancellationToken::new();
+
+	let handle = exec.spawn_until( async { 5u8 }, token.clone() ).expect( "spawn" );
+
+	assert_eq!( Some( 5 ), exec.block_on( handle ) );
+	assert!( !token.is_cancelled() );
+}
+
+
+
+// spawn_until drops the future when the token is cancelled, also when the handle is detached.
+//
+#[ test ]
+//
+fn spawn_until_cancelled()
+{
+	let exec  = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+	let token = CancellationToken::new();
+
+	let handle = exec.spawn_until( futures::future::pending::<u8>(), token.clone() ).expect( "spawn" );
+
+	token.cancel();
+
+	assert_eq!( None, exec.block_on( handle ) );
+
+
+	let token    = CancellationToken::new();
+	let (tx, rx) = oneshot::channel::<()>();
+
+	let task = async move
+	{
+		let _tx = tx;
+		futures::future::pending::<()>().await;
+	};
+
+	exec.spawn_until( task, token.clone() ).expect( "spawn" ).detach();
+
+	let cancel = async move
+	{
+		tokio::task::yield_now().await;
+		token.cancel();
+
+		// The sender is dropped with the cancelled task.
+		//
+		rx.await
+	};
+
+	assert!( exec.block_on( cancel ).is_err() );
+}
+
+
+
+// par_map returns the outputs in input order, whatever order the tasks complete in.
+//
+#[ test ]
+//
+fn par_map_order()
+{
+	let exec = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+
+	// Later items finish first.
+	//
+	let out = exec.block_on( par_map( &exec, 0..5u64, 5, |i| async move
+	{
+		tokio::time::sleep( Duration::from_millis( 50 - i * 10 ) ).await;
+		i
+	}));
+
+	assert_eq!( vec![ 0, 1, 2, 3, 4 ], out.expect( "spawn" ) );
+}
+
+
+
+// par_map never runs more than concurrency tasks at the same time.
+//
+#[ test ]
+//
+fn par_map_concurrency()
+{
+	use std::sync::atomic::{ AtomicUsize, Ordering::SeqCst };
+
+	let exec    = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+	let running = Arc::new( AtomicUsize::new( 0 ) );
+	let max     = Arc::new( AtomicUsize::new( 0 ) );
+
+	let out = exec.block_on( par_map( &exec, 0..20u8, 3, |i|
+	{
+		let running = running.clone();
+		let max     = max.clone();
+
+		async move
+		{
+			let now = running.fetch_add( 1, SeqCst ) + 1;
+			max.fetch_max( now, SeqCst );
+
+			tokio::time::sleep( Duration::from_millis( 5 ) ).await;
+
+			running.fetch_sub( 1, SeqCst );
+			i
+		}
+	}));
+
+	assert_eq!( ( 0..20 ).collect::<Vec<_>>(), out.expect( "spawn" ) );
+	assert_eq!( 3, max.load( SeqCst ) );
+}
+
+
+
+// dropping the par_map future cancels the tasks in flight.
+//
+#[ test ]
+//
+fn par_map_drop()
+{
+	let exec         = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+	let (tx, mut rx) = mpsc::unbounded::<()>();
+
+	exec.block_on( async
+	{
+		let map = par_map( &exec, 0..2u8, 2, |_|
+		{
+			let tx = tx.clone();
+
+			async move
+			{
+				let _tx = tx;
+				futures::future::pending::<()>().await;
+			}
+		});
+
+		// Let the tasks start, then drop them.
+		//
+		let timeout = tokio::time::timeout( Duration::from_millis( 10 ), map ).await;
+
+		assert!( timeout.is_err() );
+	});
+
+	drop( tx );
+
+	// All senders are dropped with the cancelled tasks.
+	//
+	let closed = exec.block_on( async { tokio::time::timeout( Duration::from_secs( 5 ), rx.next() ).await } );
+
+	assert_eq!( None, closed.expect( "tasks cancelled in time" ) );
+}
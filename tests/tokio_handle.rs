@@ -0,0 +1,99 @@
+#![ cfg( feature = "tokio_ct" ) ]
+
+// Tested:
+//
+// ✔ pass a     TokioHandle  to a function that takes exec: `impl Spawn`
+// ✔ pass a    &TokioHandle  to a function that takes exec: `&impl Spawn`
+// ✔ pass a     TokioHandle  to a function that takes exec: `impl SpawnHandle`
+// ✔ pass a    &TokioHandle  to a function that takes exec: `&dyn SpawnHandle`
+// ✔ spawn a blocking closure through TokioHandle.
+//
+mod common;
+
+use
+{
+	common  :: { *                        } ,
+	futures :: { channel::mpsc, StreamExt } ,
+	tokio   :: { runtime::Builder         } ,
+};
+
+
+// pass a TokioHandle to a function that takes exec: `impl Spawn`
+//
+#[ test ]
+//
+fn spawn()
+{
+	let (tx, mut rx) = mpsc::channel( 1 );
+	let rt           = Builder::new_current_thread().build().expect( "create tokio runtime" );
+	let exec         = TokioHandle( rt.handle().clone() );
+
+	increment( 4, exec, tx );
+
+	let result = rt.block_on( rx.next() ).expect( "Some" );
+
+	assert_eq!( 5u8, result );
+}
+
+
+// pass a &TokioHandle to a function that takes exec: `&impl Spawn`
+//
+#[ test ]
+//
+fn spawn_ref()
+{
+	let (tx, mut rx) = mpsc::channel( 1 );
+	let rt           = Builder::new_current_thread().build().expect( "create tokio runtime" );
+	let exec         = TokioHandle::from( rt.handle().clone() );
+
+	increment_ref( 4, &exec, tx );
+
+	let result = rt.block_on( rx.next() ).expect( "Some" );
+
+	assert_eq!( 5u8, result );
+}
+
+
+// pass a TokioHandle to a function that takes exec: `impl SpawnHandle`
+//
+#[ test ]
+//
+fn spawn_handle()
+{
+	let rt   = Builder::new_current_thread().build().expect( "create tokio runtime" );
+	let exec = TokioHandle( rt.handle().clone() );
+
+	let result = rt.block_on( increment_spawn_handle( 4, exec ) );
+
+	assert_eq!( 5u8, result );
+}
+
+
+// pass a &TokioHandle to a function that takes exec: `&dyn SpawnHandle`
+//
+#[ test ]
+//
+fn spawn_handle_os()
+{
+	let rt   = Builder::new_current_thread().build().expect( "create tokio runtime" );
+	let exec = TokioHandle( rt.handle().clone() );
+
+	let result = rt.block_on( increment_spawn_handle_os( 4, &exec ) );
+
+	assert_eq!( 5u8, result );
+}
+
+
+// spawn a blocking closure through TokioHandle.
+//
+#[ test ]
+//
+fn spawn_blocking()
+{
+	let rt   = Builder::new_current_thread().build().expect( "create tokio runtime" );
+	let exec = TokioHandle( rt.handle().clone() );
+
+	let join_handle = exec.spawn_blocking_obj( Box::new( || 5u8 ) ).expect( "spawn blocking" );
+
+	assert_eq!( 5u8, rt.block_on( join_handle ) );
+}
@@ -14,12 +14,43 @@
 //
 // ✔ Joinhandle::detach allows task to keep running.
 //
+// ✔ block_on can be called several times, on several clones, from several threads.
+// ✔ try_block_on returns an error instead of panicking when called inside the runtime.
+// ✔ block_on works while another runtime has been entered with Runtime::enter.
+// ✔ a failed shutdown leaves the executor usable.
+// ✔ shutdown_timeout returns an error instead of panicking when called inside a runtime.
+// ✔ shutdown_timeout works while another runtime has been entered with Runtime::enter.
+// ✔ Default and TryFrom<&mut TokioTpBuilder> create a working executor.
+// ✔ the scheduler intervals can be set on the builder.
+// ✔ worker and blocking threads can be set on the builder.
+// ✔ build returns an error for options that tokio would panic on.
+// ✔ thread_stack_size allows running deeply recursive futures on the workers.
+// ✔ metrics reflect the configuration of the runtime.
+// ✔ block_on_then_shutdown lets running blocking tasks finish.
+// ✔ into_inner gives back the runtime and other clones keep working.
+// ✔ RateLimitedSpawn starts at most rate tasks per interval.
+// ✔ RateLimitedSpawn ends tasks without a permit when the refill task can not be spawned.
+// ✔ with_blocking spawns futures on TokioTp and blocking closures on the other executor.
+// ✔ make_portable gives a handle that can be awaited from another executor.
+// ✔ spawn_supervised restarts a task that panics until it succeeds.
+// ✔ spawn_supervised gives up after max_restarts and returns the last failure.
+// ✔ spawn_supervised restarts when creating the future panics, and never waits longer than the max backoff.
+// ✔ spawn_supervised doesn't overflow when doubling a huge backoff.
+// ✔ spawn_handle_with_exec passes a clone of the executor, so a task can spawn a grandchild.
+// ✔ block_on_with_shutdown returns None and drops the future when the shutdown trigger fires.
+// ✔ block_on_with_driver keeps polling the driver while the main future runs.
+// ✔ run_with_shutdown returns the output when the future completes before Ctrl-C.
+// ✔ local_worker runs several !Send tasks on the same dedicated thread.
+// ✔ local_worker keeps running pending tasks after the last handle to the worker is dropped.
+// ✔ TieredTokio puts tasks on the high or low priority runtime, the spawn traits use the high one.
+//
 mod common;
 
 use
 {
-	common          :: { *                                     } ,
-	futures         :: { channel::{ mpsc, oneshot }, StreamExt } ,
+	common          :: { *                                                         } ,
+	futures         :: { channel::{ mpsc, oneshot }, future::BoxFuture, StreamExt } ,
+	std             :: { convert::TryFrom, time::Duration                          } ,
 };
 
 
@@ -210,3 +241,833 @@ fn join_handle_detach()
 		assert_eq!( out_rx.await, Ok(5) );
 	});
 }
+
+
+
+// block_on can be called several times, on several clones, from several threads.
+//
+#[ test ]
+//
+fn block_on_reuse()
+{
+	let exec  = TokioTpBuilder::new().build().expect( "create tokio threadpool" );
+	let exec2 = exec.clone();
+
+	assert_eq!( 1, exec.block_on( async { 1 } ) );
+	assert_eq!( 2, exec.block_on( async { 2 } ) );
+
+	let thread = std::thread::spawn( move || exec2.block_on( async { 3 } ) );
+
+	assert_eq!( 4, exec.block_on( async { 4 } ) );
+	assert_eq!( 3, thread.join().expect( "join thread" ) );
+}
+
+
+// try_block_on returns an error instead of panicking when called inside the runtime.
+//
+#[ test ]
+//
+fn try_block_on_nested()
+{
+	let exec  = TokioTpBuilder::new().build().expect( "create tokio threadpool" );
+	let inner = exec.clone();
+
+	let result = exec.block_on( async move { inner.try_block_on( async { 1 } ) } );
+
+	assert_eq!( Err( TokioTpError::InsideRuntime ), result );
+	assert_eq!( Ok(2), exec.try_block_on( async { 2 } ) );
+
+	// From a task, also on another runtime.
+	//
+	let other  = tokio::runtime::Builder::new_current_thread().build().expect( "create tokio runtime" );
+	let inner  = exec.clone();
+	let result = other.block_on( other.spawn( async move { inner.try_block_on( async { 3 } ) } ) );
+
+	assert_eq!( Err( TokioTpError::InsideRuntime ), result.expect( "join task" ) );
+}
+
+
+// block_on works while another runtime has been entered with Runtime::enter.
+//
+#[ test ]
+//
+fn block_on_in_enter_guard()
+{
+	let exec  = TokioTpBuilder::new().build().expect( "create tokio threadpool" );
+	let other = tokio::runtime::Builder::new_current_thread().build().expect( "create tokio runtime" );
+
+	let _guard = other.enter();
+
+	assert_eq!( Ok(1), exec.try_block_on( async { 1 } ) );
+	assert_eq!( 2, exec.block_on( async { 2 } ) );
+}
+
+
+// a failed shutdown leaves the executor usable.
+//
+#[ test ]
+//
+fn shutdown_with_clones()
+{
+	let exec  = TokioTpBuilder::new().build().expect( "create tokio threadpool" );
+	let clone = exec.clone();
+
+	let exec = exec.shutdown_background().expect_err( "other clone is alive" );
+
+	assert_eq!( 1, exec .block_on( async { 1 } ) );
+	assert_eq!( 2, clone.block_on( async { 2 } ) );
+
+	drop( clone );
+
+	assert!( exec.shutdown_background().is_ok() );
+}
+
+
+// shutdown_timeout returns an error instead of panicking when called inside a runtime.
+//
+#[ test ]
+//
+fn shutdown_timeout_inside_runtime()
+{
+	let exec  = TokioTpBuilder::new().build().expect( "create tokio threadpool" );
+	let outer = TokioTpBuilder::new().build().expect( "create tokio threadpool" );
+
+	let err = outer.block_on( async move
+	{
+		exec.shutdown_timeout( Duration::from_secs(1) ).expect_err( "inside a runtime" )
+	});
+
+	assert_eq!( TokioTpError::CannotShutdownFromWithin, err.kind() );
+
+	// The executor comes back usable and can be shut down from outside.
+	//
+	let exec = err.into_inner();
+
+	assert_eq!( 1, exec.block_on( async { 1 } ) );
+	assert!( exec.shutdown_timeout( Duration::from_secs(1) ).is_ok() );
+
+	// Clones still prevent the shutdown.
+	//
+	let clone = outer.clone();
+	let err   = outer.shutdown_timeout( Duration::from_secs(1) ).expect_err( "other clone is alive" );
+
+	assert_eq!( TokioTpError::ClonesAlive, err.kind() );
+
+	drop( clone );
+
+	assert!( err.into_inner().shutdown_timeout( Duration::from_secs(1) ).is_ok() );
+}
+
+
+// shutdown_timeout works while another runtime has been entered with Runtime::enter.
+//
+#[ test ]
+//
+fn shutdown_timeout_in_enter_guard()
+{
+	let exec  = TokioTpBuilder::new().build().expect( "create tokio threadpool" );
+	let other = tokio::runtime::Builder::new_current_thread().build().expect( "create tokio runtime" );
+
+	let _guard = other.enter();
+
+	assert!( exec.shutdown_timeout( Duration::from_secs(1) ).is_ok() );
+}
+
+
+// Default and TryFrom<&mut TokioTpBuilder> create a working executor.
+//
+#[ test ]
+//
+fn default_and_try_from()
+{
+	let exec = TokioTp::default();
+	assert_eq!( 1, exec.block_on( async { 1 } ) );
+
+	let exec = TokioTp::try_from( &mut TokioTpBuilder::new() ).expect( "create tokio threadpool" );
+	assert_eq!( 2, exec.block_on( async { 2 } ) );
+}
+
+
+
+// the scheduler intervals can be set on the builder.
+//
+#[ test ]
+//
+fn builder_scheduler_intervals()
+{
+	let exec = TokioTpBuilder::new()
+
+		.global_queue_interval( 1  )
+		.event_interval       ( 10 )
+		.build()
+		.expect( "create tokio threadpool" )
+	;
+
+	assert_eq!( 3, exec.block_on( async { 3 } ) );
+}
+
+
+
+// worker and blocking threads can be set on the builder.
+//
+#[ test ]
+//
+fn builder_threads()
+{
+	let exec = TokioTpBuilder::new()
+
+		.worker_threads      ( 2 )
+		.max_blocking_threads( 1 )
+		.build()
+		.expect( "create tokio threadpool" )
+	;
+
+	let out = exec.block_on( async { tokio::task::spawn_blocking( || 3u8 ).await.expect( "blocking task" ) } );
+
+	assert_eq!( 3, out );
+}
+
+
+
+// build returns an error for options that tokio would panic on.
+//
+#[ test ]
+//
+fn builder_invalid_config()
+{
+	let check = |builder: &mut TokioTpBuilder, option: &str|
+	{
+		let err = builder.build().expect_err( "invalid config" );
+
+		assert_eq!( std::io::ErrorKind::InvalidInput, err.kind() );
+		assert!( err.to_string().contains( option ) );
+	};
+
+	check( TokioTpBuilder::new().worker_threads       ( 0 ), "worker_threads"        );
+	check( TokioTpBuilder::new().max_blocking_threads ( 0 ), "max_blocking_threads"  );
+	check( TokioTpBuilder::new().global_queue_interval( 0 ), "global_queue_interval" );
+	check( TokioTpBuilder::new().event_interval       ( 0 ), "event_interval"        );
+}
+
+
+
+// thread_stack_size allows running deeply recursive futures on the workers.
+//
+#[ test ]
+//
+fn thread_stack_size()
+{
+	// Every level keeps a buffer alive across the await, and polling nests a frame per level.
+	//
+	fn recurse( depth: usize ) -> BoxFuture<'static, usize>
+	{
+		async move
+		{
+			if depth == 0 { return 0 }
+
+			let buf = [ depth as u8; 512 ];
+			let out = recurse( depth - 1 ).await;
+
+			out + std::hint::black_box( buf )[ 0 ] as usize % 2
+		}
+
+		.boxed()
+	}
+
+	let exec = TokioTpBuilder::new()
+
+		.thread_stack_size( 32 * 1024 * 1024 )
+		.build()
+		.expect( "create tokio threadpool" )
+	;
+
+	let handle = exec.spawn_handle( recurse( 5_000 ) ).expect( "spawn" );
+
+	assert_eq!( 2_500, exec.block_on( handle ) );
+}
+
+
+
+// metrics reflect the configuration of the runtime.
+//
+#[ cfg( feature = "tokio_metrics" ) ]
+#[ test ]
+//
+fn metrics()
+{
+	let mut builder = TokioTpBuilder::new();
+	builder.tokio_builder().worker_threads( 3 );
+
+	let exec    = builder.build().expect( "create tokio threadpool" );
+	let metrics = exec.metrics().expect( "runtime is running" );
+
+	assert_eq!( 3, metrics.num_workers() );
+	assert_eq!( 0, metrics.num_alive_tasks() );
+	assert_eq!( 0, metrics.active_tasks_count() );
+
+	#[ cfg( tokio_unstable ) ]
+	//
+	{
+		exec.block_on( async { tokio::task::spawn_blocking( || {} ).await.expect( "blocking task" ) } );
+
+		assert!( metrics.num_blocking_threads() >= 1 );
+	}
+}
+
+
+
+// block_on_then_shutdown lets running blocking tasks finish.
+//
+#[ test ]
+//
+fn block_on_then_shutdown()
+{
+	let exec     = TokioTpBuilder::new().build().expect( "create tokio threadpool" );
+	let (tx, rx) = std::sync::mpsc::channel();
+
+	let out = exec.block_on_then_shutdown( async move
+	{
+		let (started_tx, started_rx) = oneshot::channel();
+
+		tokio::task::spawn_blocking( move ||
+		{
+			started_tx.send(()).expect( "send" );
+			std::thread::sleep( std::time::Duration::from_millis(20) );
+			tx.send( 5u8 ).expect( "send" );
+		});
+
+		started_rx.await.expect( "blocking task started" );
+
+		3
+
+	}, std::time::Duration::from_secs(5) );
+
+	assert_eq!( 3, out );
+	assert_eq!( Ok(5), rx.try_recv() );
+}
+
+
+
+// into_inner gives back the runtime and other clones keep working.
+//
+#[ test ]
+//
+fn into_inner()
+{
+	let exec  = TokioTpBuilder::new().build().expect( "create tokio threadpool" );
+	let clone = exec.clone();
+	let rt    = exec.into_inner().expect( "runtime" );
+
+	assert_eq!( 5u8, rt.block_on( async { 5 } ) );
+	assert_eq!( 6u8, rt.block_on( clone.spawn_handle( async { 6 } ).expect( "spawn" ) ) );
+}
+
+
+
+// RateLimitedSpawn starts at most rate tasks per interval.
+//
+#[ cfg( feature = "channel" ) ]
+#[ test ]
+//
+fn rate_limited_spawn()
+{
+	use std::time::{ Duration, Instant };
+
+	let exec     = TokioTpBuilder::new().build().expect( "create tokio threadpool" );
+	let limited  = RateLimitedSpawn::new( exec.clone(), 2, Duration::from_millis( 50 ) );
+	let start    = Instant::now();
+
+	let handles: Vec<_> = (0..6)
+
+		.map( |_| limited.spawn_handle( async move { start.elapsed() } ).expect( "spawn" ) )
+		.collect()
+	;
+
+	let mut starts = exec.block_on( futures::future::join_all( handles ) );
+
+	starts.sort_unstable();
+
+	// Two per interval, so the third and fifth task have to wait for a refill.
+	//
+	assert!( starts[1] < Duration::from_millis(  50 ) );
+	assert!( starts[2] >= Duration::from_millis(  50 ) );
+	assert!( starts[4] >= Duration::from_millis( 100 ) );
+}
+
+
+
+// RateLimitedSpawn ends tasks without a permit when the refill task can not be spawned.
+//
+#[ cfg( feature = "channel" ) ]
+#[ test ]
+//
+fn rate_limited_spawn_no_refill()
+{
+	use std::{ panic::AssertUnwindSafe, sync::{ Arc, atomic::{ AtomicBool, Ordering::SeqCst } }, time::Duration };
+
+	// Refuses to spawn from within the runtime, which is where the refill task gets spawned, so it
+	// doesn't matter which runs first.
+	//
+	#[ derive( Clone ) ]
+	//
+	struct NoRefill { exec: TokioTp }
+
+	impl Spawn for NoRefill
+	{
+		fn spawn_obj( &self, future: futures::task::FutureObj<'static, ()> ) -> Result<(), SpawnError>
+		{
+			if tokio::runtime::Handle::try_current().is_ok()
+			{
+				return Err( SpawnError::shutdown() );
+			}
+
+			self.exec.spawn_obj( future )
+		}
+	}
+
+	impl Timer for NoRefill
+	{
+		fn sleep( &self, dur: Duration ) -> futures::future::BoxFuture<'static, ()>
+		{
+			self.exec.sleep( dur )
+		}
+	}
+
+	let exec    = TokioTpBuilder::new().build().expect( "create tokio threadpool" );
+	let limited = RateLimitedSpawn::new( NoRefill { exec: exec.clone() }, 1, Duration::from_millis( 10 ) );
+	let first   = Arc::new( AtomicBool::new( false ) );
+	let second  = Arc::new( AtomicBool::new( false ) );
+	let first2  = first.clone();
+	let second2 = second.clone();
+
+	limited.spawn( async move { first2.store( true, SeqCst ) } ).expect( "spawn" );
+	limited.spawn( async move { second2.store( true, SeqCst ) } ).expect( "spawn" );
+
+	let handle = limited.spawn_handle( async { 5u8 } ).expect( "spawn" );
+
+	// The handle resolves, by panicking like the handle of a task that was dropped.
+	//
+	assert!( exec.block_on( AssertUnwindSafe( handle ).catch_unwind() ).is_err() );
+
+	exec.block_on( exec.sleep( Duration::from_millis( 100 ) ) );
+
+	assert!(  first .load( SeqCst ) );
+	assert!( !second.load( SeqCst ) );
+}
+
+
+
+// with_blocking spawns futures on TokioTp and blocking closures on the other executor.
+//
+#[ test ]
+//
+fn with_blocking()
+{
+	use std::{ sync::{ Arc, Mutex }, thread::ThreadId };
+
+	// A minimal blocking pool that runs every closure on a new thread and remembers which threads it used.
+	//
+	#[ derive( Clone, Default ) ]
+	//
+	struct Threads( Arc<Mutex<Vec<ThreadId>>> );
+
+	impl SpawnBlocking<u8> for Threads
+	{
+		fn spawn_blocking_obj( &self, func: Box<dyn FnOnce() -> u8 + Send> ) -> Result<JoinHandle<u8>, SpawnError>
+		{
+			let (remote, handle) = async { func() }.remote_handle();
+			let used             = self.0.clone();
+
+			std::thread::spawn( move ||
+			{
+				used.lock().expect( "lock" ).push( std::thread::current().id() );
+				futures::executor::block_on( remote );
+			});
+
+			Ok( handle.into() )
+		}
+	}
+
+	// A single bound for both capabilities.
+	//
+	async fn needs_both( exec: impl SpawnHandle<u8> + SpawnBlocking<u8> ) -> u8
+	{
+		let blocking = exec.spawn_blocking( || 2 ).expect( "spawn blocking" ).await;
+		let task     = exec.spawn_handle( async { 3 } ).expect( "spawn" ).await;
+
+		blocking + task
+	}
+
+	let exec    = TokioTpBuilder::new().build().expect( "create tokio threadpool" );
+	let threads = Threads::default();
+	let full    = exec.clone().with_blocking( threads.clone() );
+
+	assert_eq!( 5, exec.block_on( needs_both( full ) ) );
+	assert_eq!( 1, threads.0.lock().expect( "lock" ).len() );
+}
+
+
+
+// make_portable gives a handle that can be awaited from another executor.
+//
+#[ test ]
+//
+fn make_portable()
+{
+	let exec   = TokioTpBuilder::new().build().expect( "create tokio threadpool" );
+	let handle = exec.spawn_handle( async { Tokio.yield_now().await; 5u8 } ).expect( "spawn" ).make_portable();
+
+	// Now it's backed by a oneshot channel instead of the native tokio handle.
+	//
+	let handle = handle.try_into_remote_handle().expect( "remote handle" );
+
+	assert_eq!( 5, futures::executor::block_on( handle ) );
+}
+
+
+
+// spawn_supervised restarts a task that panics until it succeeds.
+//
+#[ test ]
+//
+fn spawn_supervised()
+{
+	use std::{ sync::atomic::{ AtomicUsize, Ordering::SeqCst }, time::Duration };
+
+	let exec   = TokioTpBuilder::new().build().expect( "create tokio threadpool" );
+	let runs   = Arc::new( AtomicUsize::new( 0 ) );
+	let runs2  = runs.clone();
+	let policy = RestartPolicy::new( 3 ).backoff( Duration::from_millis( 1 ), Duration::from_millis( 10 ) );
+
+	let handle = exec.spawn_supervised( move ||
+	{
+		let run = runs2.fetch_add( 1, SeqCst );
+
+		async move
+		{
+			if run < 2 { panic!( "run {} fails", run ) }
+
+			Ok::<_, ()>( run )
+		}
+
+	}, policy, exec.clone() ).expect( "spawn" );
+
+	let out = exec.block_on( handle ).expect( "succeed eventually" );
+
+	assert_eq!( 2, out );
+	assert_eq!( 3, runs.load( SeqCst ) );
+}
+
+
+
+// spawn_supervised gives up after max_restarts and returns the last failure.
+//
+#[ test ]
+//
+fn spawn_supervised_gives_up()
+{
+	use std::sync::atomic::{ AtomicUsize, Ordering::SeqCst };
+
+	let exec  = TokioTpBuilder::new().build().expect( "create tokio threadpool" );
+	let runs  = Arc::new( AtomicUsize::new( 0 ) );
+	let runs2 = runs.clone();
+
+	let handle = exec.spawn_supervised( move ||
+	{
+		let run = runs2.fetch_add( 1, SeqCst );
+
+		async move { Err::<u8, _>( run ) }
+
+	}, RestartPolicy::new( 2 ), exec.clone() ).expect( "spawn" );
+
+	match exec.block_on( handle )
+	{
+		Err( TaskFailure::Failed( run ) ) => assert_eq!( 2, run ),
+		other                             => panic!( "unexpected outcome: {:?}", other ),
+	}
+
+	assert_eq!( 3, runs.load( SeqCst ) );
+}
+
+
+
+// spawn_supervised restarts when creating the future panics, and never waits longer than the max backoff.
+//
+#[ test ]
+//
+fn spawn_supervised_make_fut_panics()
+{
+	use std::{ sync::atomic::{ AtomicUsize, Ordering::SeqCst }, time::{ Duration, Instant } };
+
+	let exec   = TokioTpBuilder::new().build().expect( "create tokio threadpool" );
+	let runs   = Arc::new( AtomicUsize::new( 0 ) );
+	let runs2  = runs.clone();
+	let policy = RestartPolicy::new( 1 ).backoff( Duration::from_secs( 60 ), Duration::from_millis( 1 ) );
+	let start  = Instant::now();
+
+	let handle = exec.spawn_supervised( move ||
+	{
+		if runs2.fetch_add( 1, SeqCst ) == 0 { panic!( "can't create the future" ) }
+
+		async { Ok::<_, ()>( 5u8 ) }
+
+	}, policy, exec.clone() ).expect( "spawn" );
+
+	assert_eq!( 5, exec.block_on( handle ).expect( "succeed on restart" ) );
+	assert_eq!( 2, runs.load( SeqCst ) );
+	assert!( start.elapsed() < Duration::from_secs( 10 ) );
+}
+
+
+
+// spawn_supervised doesn't overflow when doubling a huge backoff.
+//
+#[ test ]
+//
+fn spawn_supervised_huge_backoff()
+{
+	use std::{ sync::Mutex, time::Duration };
+	use futures::future::BoxFuture;
+
+	// Records the requested sleeps and resolves them right away.
+	//
+	#[ derive( Clone, Default ) ]
+	//
+	struct Instant( Arc<Mutex<Vec<Duration>>> );
+
+	impl Timer for Instant
+	{
+		fn sleep( &self, dur: Duration ) -> BoxFuture<'static, ()>
+		{
+			self.0.lock().unwrap().push( dur );
+			Box::pin( async {} )
+		}
+	}
+
+	let exec   = TokioTpBuilder::new().build().expect( "create tokio threadpool" );
+	let timer  = Instant::default();
+	let policy = RestartPolicy::new( 2 ).backoff( Duration::MAX, Duration::MAX );
+
+	let handle = exec.spawn_supervised( || async { Err::<u8, _>( () ) }, policy, timer.clone() ).expect( "spawn" );
+
+	assert!( matches!( exec.block_on( handle ), Err( TaskFailure::Failed(()) ) ) );
+	assert_eq!( vec![ Duration::MAX, Duration::MAX ], *timer.0.lock().unwrap() );
+}
+
+
+
+// spawn_handle_with_exec passes a clone of the executor, so a task can spawn a grandchild.
+//
+#[ test ]
+//
+fn spawn_handle_with_exec()
+{
+	let exec = TokioTpBuilder::new().build().expect( "create tokio threadpool" );
+
+	let parent = exec.spawn_handle_with_exec( |exec| async move
+	{
+		let child = exec.spawn_handle_with_exec( |exec| async move
+		{
+			let grandchild = exec.spawn_handle( async { 3u8 } ).expect( "spawn grandchild" );
+
+			grandchild.await + 2
+
+		}).expect( "spawn child" );
+
+		child.await + 1
+
+	}).expect( "spawn parent" );
+
+	assert_eq!( 6, exec.block_on( parent ) );
+}
+
+
+
+// block_on_with_shutdown returns None and drops the future when the shutdown trigger fires.
+//
+#[ test ]
+//
+fn block_on_with_shutdown()
+{
+	let exec = TokioTpBuilder::new().build().expect( "create tokio threadpool" );
+
+	assert_eq!( Some( 5 ), exec.block_on_with_shutdown( async { 5u8 }, futures::future::pending::<()>() ) );
+
+
+	let (trigger   , shutdown  ) = oneshot::channel::<()>();
+	let (dropped_tx, dropped_rx) = oneshot::channel::<()>();
+
+	exec.spawn( async move { trigger.send(()).expect( "trigger shutdown" ); } ).expect( "spawn" );
+
+	let out = exec.block_on_with_shutdown( async move
+	{
+		// Tells us it got dropped when it's gone.
+		//
+		let _dropped = dropped_tx;
+		futures::future::pending::<u8>().await
+
+	}, shutdown );
+
+	assert_eq!( None, out );
+	assert!( exec.block_on( dropped_rx ).is_err() );
+}
+
+
+
+// block_on_with_driver keeps polling the driver while the main future runs.
+//
+#[ test ]
+//
+fn block_on_with_driver()
+{
+	use std::sync::atomic::{ AtomicUsize, Ordering::SeqCst };
+
+	let exec  = TokioTpBuilder::new().build().expect( "create tokio threadpool" );
+	let ticks = AtomicUsize::new( 0 );
+
+	let driver = async
+	{
+		loop
+		{
+			ticks.fetch_add( 1, SeqCst );
+			tokio::time::sleep( Duration::from_millis( 1 ) ).await;
+		}
+	};
+
+	let main = async
+	{
+		tokio::time::sleep( Duration::from_millis( 50 ) ).await;
+		5u8
+	};
+
+	assert_eq!( 5, exec.block_on_with_driver( main, driver ) );
+	assert!( ticks.load( SeqCst ) > 1 );
+}
+
+
+
+// run_with_shutdown returns the output when the future completes before Ctrl-C.
+//
+#[ cfg( feature = "signal" ) ]
+#[ test ]
+//
+fn run_with_shutdown()
+{
+	let exec = TokioTpBuilder::new().build().expect( "create tokio threadpool" );
+
+	assert_eq!( Some( 5 ), exec.run_with_shutdown( async { 5u8 } ) );
+}
+
+
+
+// local_worker runs several !Send tasks on the same dedicated thread.
+//
+#[ test ]
+//
+fn local_worker()
+{
+	use std::{ rc::Rc, thread };
+
+	let exec   = TokioTpBuilder::new().build().expect( "create tokio threadpool" );
+	let worker = exec.local_worker().expect( "start local worker" );
+
+	let handles: Vec<_> = ( 0..4u8 ).map( |i|
+	{
+		worker.spawn_local( move ||
+		{
+			let not_send = Rc::new( i );
+
+			async move
+			{
+				tokio::task::yield_now().await;
+				( *not_send, thread::current().id() )
+			}
+
+		}).expect( "spawn_local" )
+
+	}).collect();
+
+	let results = exec.block_on( futures::future::join_all( handles ) );
+	let worker_thread = results[0].1;
+
+	assert_ne!( thread::current().id(), worker_thread );
+
+	for (i, (out, id)) in results.into_iter().enumerate()
+	{
+		assert_eq!( i as u8     , out );
+		assert_eq!( worker_thread, id  );
+	}
+
+	// Send futures can go on the worker as well.
+	//
+	let id = exec.block_on( worker.spawn_handle( async { thread::current().id() } ).expect( "spawn_handle" ) );
+
+	assert_eq!( worker_thread, id );
+}
+
+
+
+// local_worker keeps running pending tasks after the last handle to the worker is dropped.
+//
+#[ test ]
+//
+fn local_worker_dropped()
+{
+	use std::time::Duration;
+
+	let exec   = TokioTpBuilder::new().build().expect( "create tokio threadpool" );
+	let handle = exec.local_worker().expect( "start local worker" ).spawn_local( ||
+	{
+		let not_send = std::rc::Rc::new( 5u8 );
+
+		async move
+		{
+			tokio::time::sleep( Duration::from_millis( 10 ) ).await;
+			*not_send
+		}
+
+	}).expect( "spawn" );
+
+	assert_eq!( 5, exec.block_on( handle ) );
+}
+
+
+
+// TieredTokio puts tasks on the high or low priority runtime, the spawn traits use the high one.
+//
+#[ test ]
+//
+fn tiered_tokio()
+{
+	let mut high = TokioTpBuilder::new();
+	let mut low  = TokioTpBuilder::new();
+
+	high.tokio_builder().thread_name( "high" );
+	low .tokio_builder().thread_name( "low"  );
+
+	let exec = TieredTokio::new
+	(
+		high.build().expect( "create high priority runtime" ),
+		low .build().expect( "create low priority runtime"  ),
+	);
+
+	let name = || std::thread::current().name().expect( "some name" ).to_string();
+
+	let (tx, rx) = oneshot::channel();
+	exec.spawn_high( async move { tx.send( name() ).expect( "send on oneshot" ); } ).expect( "spawn_high" );
+	assert_eq!( "high", exec.high().block_on( rx ).expect( "read channel" ) );
+
+	let (tx, rx) = oneshot::channel();
+	exec.spawn_low( async move { tx.send( name() ).expect( "send on oneshot" ); } ).expect( "spawn_low" );
+	assert_eq!( "low", exec.high().block_on( rx ).expect( "read channel" ) );
+
+	let (tx, rx) = oneshot::channel();
+	exec.spawn( async move { tx.send( name() ).expect( "send on oneshot" ); } ).expect( "spawn" );
+	assert_eq!( "high", exec.high().block_on( rx ).expect( "read channel" ) );
+
+	let high = exec.spawn_handle( async move { name() } ).expect( "spawn_handle" );
+	let low  = exec.spawn_handle_low( async move { name() } ).expect( "spawn_handle_low" );
+
+	assert_eq!( "high", exec.high().block_on( high ) );
+	assert_eq!( "low" , exec.high().block_on( low  ) );
+}
@@ -0,0 +1,98 @@
+#![ cfg(all( feature = "tracing", feature = "tokio_ct" )) ]
+
+// Tested:
+//
+// ✔ spawn_logged logs a panicking task with its name.
+// ✔ spawn_logged_result logs the error of a failing task with its name, and nothing for a successful one.
+//
+mod common;
+
+use
+{
+	common        :: { *                        } ,
+	std           :: { io, sync::Mutex          } ,
+	tracing_crate :: { subscriber::with_default } ,
+};
+
+
+// Collects the output of the fmt subscriber.
+//
+#[ derive( Clone, Default ) ]
+//
+struct Logs( Arc<Mutex<Vec<u8>>> );
+
+impl Logs
+{
+	fn text( &self ) -> String
+	{
+		String::from_utf8( self.0.lock().expect( "lock logs" ).clone() ).expect( "utf8 logs" )
+	}
+}
+
+impl io::Write for Logs
+{
+	fn write( &mut self, buf: &[u8] ) -> io::Result<usize>
+	{
+		self.0.lock().expect( "lock logs" ).extend_from_slice( buf );
+		Ok( buf.len() )
+	}
+
+	fn flush( &mut self ) -> io::Result<()> { Ok(()) }
+}
+
+
+// Run `f` on a TokioCt with a subscriber that writes into the returned logs.
+//
+fn capture( f: impl FnOnce( &TokioCt ) ) -> String
+{
+	let logs       = Logs::default();
+	let writer     = logs.clone();
+	let subscriber = tracing_subscriber::fmt().with_writer( move || writer.clone() ).finish();
+	let exec       = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+
+	with_default( subscriber, ||
+	{
+		f( &exec );
+		exec.run_until_idle();
+	});
+
+	logs.text()
+}
+
+
+
+// spawn_logged logs a panicking task with its name.
+//
+#[ test ]
+//
+fn spawn_logged()
+{
+	let logs = capture( |exec|
+	{
+		exec.spawn_logged( "cleanup", async { panic!( "disk on fire" ) } ).expect( "spawn" );
+	});
+
+	assert!( logs.contains( "task panicked" ), "{}", logs );
+	assert!( logs.contains( "cleanup"       ), "{}", logs );
+	assert!( logs.contains( "disk on fire"  ), "{}", logs );
+}
+
+
+
+// spawn_logged_result logs the error of a failing task with its name, and nothing for a successful one.
+//
+#[ test ]
+//
+fn spawn_logged_result()
+{
+	let logs = capture( |exec|
+	{
+		exec.spawn_logged_result( "sync"   , async { Err::<(), _>( "connection refused" ) } ).expect( "spawn" );
+		exec.spawn_logged_result( "healthy", async { Ok ::<_, String>( 5u8 )             } ).expect( "spawn" );
+	});
+
+	assert!(  logs.contains( "task failed"        ), "{}", logs );
+	assert!(  logs.contains( "sync"               ), "{}", logs );
+	assert!(  logs.contains( "connection refused" ), "{}", logs );
+	assert!( !logs.contains( "healthy"            ), "{}", logs );
+}